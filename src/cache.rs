@@ -1,16 +1,22 @@
 //! Documentation caching with fingerprint-based regeneration.
 //!
 //! This module provides hash types and digest computation for tracking when
-//! documentation needs regeneration based on file changes, version updates, or toolchain changes.
+//! documentation needs regeneration based on file changes, version updates, or toolchain changes,
+//! and the [`SharedCache`] that lets every workspace on the machine reuse dependency docs.
 
 use crate::error::{ParseHashError, Result};
+use crate::workspace::MemberSources;
 use anyhow::Context;
 use ignore::WalkBuilder;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeSet;
 use std::fmt;
 use std::hash::Hash as StdHash;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
 
 /// Type-safe representation of hash values used throughout rustdoc-mcp.
 ///
@@ -115,57 +121,85 @@ pub enum DigestVariant {
         manifest_hash: u64,
         /// Combined hash of all source files
         source_hash: u64,
-        /// Sorted list of enabled features
-        features: Vec<String>,
+        /// Sorted list of enabled features (`None` for the crate's defaults)
+        features: Option<Vec<String>>,
     },
     Dependency {
         /// Crate version
         version: String,
         /// SHA256 checksum from Cargo.lock (guarantees immutability)
         checksum: Hash,
+        /// Sorted list of enabled features (`None` for the crate's defaults)
+        #[serde(default)]
+        features: Option<Vec<String>>,
     },
 }
 
 /// Computes a digest for a workspace member based on manifest and source file contents.
-/// Regeneration is triggered by changes to Cargo.toml, any .rs file, or rustc version.
+///
+/// Regeneration is triggered by changes to the member's Cargo.toml (or the workspace's),
+/// any of its source files, the enabled features, or rustc version.
+///
+/// Without `sources`, the workspace root's `Cargo.toml` and `src` directory are hashed.
 pub async fn compute_workspace_digest(
     _crate_name: &str,
     workspace_root: &Path,
+    sources: Option<&MemberSources>,
+    features: Option<&[String]>,
 ) -> Result<CrateDigest> {
     let rustc_version_hash = get_rustc_version_hash().await?;
 
-    // Hash Cargo.toml
-    let manifest_path = workspace_root.join("Cargo.toml");
-    let manifest_hash = hash_file(&manifest_path)
+    // Hash Cargo.toml: the member's own, plus the workspace root's for inherited settings
+    let root_manifest = workspace_root.join("Cargo.toml");
+    let mut manifest_hash = hash_file(&root_manifest)
         .await
-        .with_context(|| format!("Failed to hash Cargo.toml at {}", manifest_path.display()))?;
+        .with_context(|| format!("Failed to hash Cargo.toml at {}", root_manifest.display()))?;
+    if let Some(sources) = sources
+        && sources.manifest_path != root_manifest
+    {
+        let member_hash = hash_file(&sources.manifest_path).await.with_context(|| {
+            format!(
+                "Failed to hash Cargo.toml at {}",
+                sources.manifest_path.display()
+            )
+        })?;
+        manifest_hash = xxhash_rust::xxh3::xxh3_64(
+            &[manifest_hash.to_le_bytes(), member_hash.to_le_bytes()].concat(),
+        );
+    }
 
     // Hash all source files
-    let src_dir = workspace_root.join("src");
-    let source_hash = hash_directory(&src_dir)
-        .await
-        .with_context(|| format!("Failed to hash source directory at {}", src_dir.display()))?;
-
-    // For now, we don't track features (would need to be passed in)
-    // This is acceptable because feature changes usually require explicit cargo invocations
-    let features = Vec::new();
+    let source_hash = if let Some(sources) = sources {
+        hash_member_sources(sources).await.with_context(|| {
+            format!(
+                "Failed to hash sources of {}",
+                sources.manifest_path.display()
+            )
+        })?
+    } else {
+        let src_dir = workspace_root.join("src");
+        hash_directory(&src_dir)
+            .await
+            .with_context(|| format!("Failed to hash source directory at {}", src_dir.display()))?
+    };
 
     Ok(CrateDigest {
         rustc_version_hash,
         crate_type: DigestVariant::WorkspaceMember {
             manifest_hash,
             source_hash,
-            features,
+            features: features.map(<[String]>::to_vec),
         },
     })
 }
 
 /// Computes a digest for an external dependency using its version and Cargo.lock checksum.
-/// Regeneration is triggered only by version changes or rustc updates.
+/// Regeneration is triggered only by version, feature set or rustc changes.
 pub async fn compute_dependency_digest(
     _crate_name: &str,
     version: &str,
     checksum: Hash,
+    features: Option<&[String]>,
 ) -> Result<CrateDigest> {
     let rustc_version_hash = get_rustc_version_hash().await?;
 
@@ -174,6 +208,7 @@ pub async fn compute_dependency_digest(
         crate_type: DigestVariant::Dependency {
             version: version.to_string(),
             checksum,
+            features: features.map(<[String]>::to_vec),
         },
     })
 }
@@ -200,6 +235,290 @@ pub async fn save_digest(path: &Path, digest: &CrateDigest) -> Result<()> {
     Ok(())
 }
 
+/// Default cache root: `<dirs::cache_dir()>/rustdoc-mcp`, falling back to
+/// `std::env::temp_dir().join("rustdoc-mcp")` when the platform has no known
+/// cache location. Never falls back to the current working directory — that
+/// would pollute user project directories.
+pub(crate) fn default_cache_root() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("rustdoc-mcp")
+}
+
+/// User-level store of dependency documentation, shared by every workspace.
+///
+/// Registry crates are immutable given their Cargo.lock checksum, so their rustdoc
+/// JSON and search index depend only on the crate name and its [`CrateDigest`]
+/// (version, checksum, features and rustc version). Each such combination gets an
+/// entry under `<root>/crates/`, which workspaces link into their own doc directory
+/// instead of running rustdoc again.
+#[derive(Debug, Clone)]
+pub struct SharedCache {
+    root: PathBuf,
+}
+
+impl SharedCache {
+    /// A cache rooted at `root`. Intended for tests; see [`Self::global`].
+    pub const fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// The per-user cache under `<dirs::cache_dir()>/rustdoc-mcp`.
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<SharedCache> = OnceLock::new();
+        GLOBAL.get_or_init(|| Self::new(default_cache_root()))
+    }
+
+    /// Directory holding this cache's entries.
+    pub fn crates_dir(&self) -> PathBuf {
+        self.root.join("crates")
+    }
+
+    /// Every entry with its disk usage.
+    pub async fn entries(&self) -> Vec<CacheDir> {
+        cache_dirs(&self.crates_dir()).await
+    }
+
+    /// Delete the entries of the crate with normalized name `name`, or only those
+    /// of `version` when given. Returns the number of bytes freed.
+    pub async fn remove_crate(&self, name: &str, version: Option<&str>) -> u64 {
+        // Normalized names have no `-`, so the prefix can't match another crate
+        let prefix = match version {
+            Some(version) => format!("{name}-{version}-"),
+            None => format!("{name}-"),
+        };
+        let mut freed = 0;
+        for entry in self.entries().await {
+            let name = entry.name();
+            let Some(rest) = name.strip_prefix(&prefix) else {
+                continue;
+            };
+            // With a version, only the key may follow; `1.0.0-` must not match `1.0.0-rc.1`
+            if version.is_some() && rest.contains('-') {
+                continue;
+            }
+            if tokio::fs::remove_dir_all(&entry.path).await.is_ok() {
+                freed += entry.bytes;
+            }
+        }
+        freed
+    }
+
+    /// The entry for `crate_name`'s docs as described by `digest`.
+    ///
+    /// `None` for docs that can't be shared: workspace members, and dependencies
+    /// without a Cargo.lock checksum (git or path dependencies), which
+    /// [`get_docs`](crate::workspace::get_docs) records with an all-zero checksum.
+    /// A `name@version` crate name is keyed by its bare name.
+    pub fn entry(&self, crate_name: &str, digest: &CrateDigest) -> Option<SharedEntry> {
+        let DigestVariant::Dependency {
+            version, checksum, ..
+        } = &digest.crate_type
+        else {
+            return None;
+        };
+        if *checksum == Hash::sha256([0; 32]) {
+            return None;
+        }
+
+        let name = crate_name
+            .split_once('@')
+            .map_or(crate_name, |(name, _)| name)
+            .replace('-', "_");
+        let key = serde_json::to_vec(&(&name, digest)).ok()?;
+        let key = xxhash_rust::xxh3::xxh3_64(&key);
+        Some(SharedEntry {
+            dir: self
+                .root
+                .join("crates")
+                .join(format!("{name}-{version}-{key:016x}")),
+            name,
+        })
+    }
+}
+
+/// A cache directory and its disk usage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheDir {
+    pub path: PathBuf,
+    /// Total size of the files inside, in bytes.
+    pub bytes: u64,
+    /// Latest modification time of the files inside (of the directory itself when empty).
+    pub modified: SystemTime,
+}
+
+impl CacheDir {
+    /// The directory's file name.
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// The subdirectories of `parent` with their disk usage; empty if `parent` doesn't exist.
+pub async fn cache_dirs(parent: &Path) -> Vec<CacheDir> {
+    let parent = parent.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let Ok(entries) = std::fs::read_dir(&parent) else {
+            return Vec::new();
+        };
+        let mut dirs: Vec<CacheDir> = entries
+            .filter_map(std::result::Result::ok)
+            .filter(|entry| entry.file_type().is_ok_and(|ft| ft.is_dir()))
+            .map(|entry| {
+                let path = entry.path();
+                let created = entry
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .unwrap_or(SystemTime::UNIX_EPOCH);
+                let (bytes, modified) = dir_usage(&path);
+                CacheDir {
+                    path,
+                    bytes,
+                    modified: modified.unwrap_or(created),
+                }
+            })
+            .collect();
+        dirs.sort_by(|a, b| a.path.cmp(&b.path));
+        dirs
+    })
+    .await
+    .unwrap_or_default()
+}
+
+/// Total size and latest modification time of the files under `dir`.
+fn dir_usage(dir: &Path) -> (u64, Option<SystemTime>) {
+    let mut bytes = 0;
+    let mut modified: Option<SystemTime> = None;
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(std::result::Result::ok) {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else {
+                bytes += metadata.len();
+                if let Ok(time) = metadata.modified() {
+                    modified = Some(modified.map_or(time, |latest| latest.max(time)));
+                }
+            }
+        }
+    }
+    (bytes, modified)
+}
+
+/// One crate's documentation in the [`SharedCache`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedEntry {
+    dir: PathBuf,
+    /// Normalized crate name the files are stored under.
+    name: String,
+}
+
+impl SharedEntry {
+    /// Path of the cached rustdoc JSON.
+    pub fn doc_path(&self) -> PathBuf {
+        self.dir.join(format!("{}.json", self.name))
+    }
+
+    /// Path of the cached search index.
+    pub fn index_path(&self) -> PathBuf {
+        self.dir.join(format!("{}.index", self.name))
+    }
+
+    /// Link the cached docs to `doc_path`, and the search index, if any, to `index_path`.
+    ///
+    /// Returns `false`, leaving both paths alone, when the entry has no docs yet. A
+    /// search index left over at `index_path` is removed when the entry has none,
+    /// since it would otherwise look newer than the restored docs.
+    pub async fn restore(&self, doc_path: &Path, index_path: &Path) -> bool {
+        let cached_docs = self.doc_path();
+        if !tokio::fs::try_exists(&cached_docs).await.unwrap_or(false) {
+            return false;
+        }
+        if let Err(e) = link_or_copy(&cached_docs, doc_path).await {
+            tracing::warn!(path = %doc_path.display(), error = ?e, "Failed to restore shared docs");
+            return false;
+        }
+
+        let cached_index = self.index_path();
+        if tokio::fs::try_exists(&cached_index).await.unwrap_or(false) {
+            if let Err(e) = link_or_copy(&cached_index, index_path).await {
+                tracing::debug!(path = %index_path.display(), error = ?e, "Failed to restore shared index");
+                let _ = tokio::fs::remove_file(index_path).await;
+            }
+        } else {
+            let _ = tokio::fs::remove_file(index_path).await;
+        }
+        true
+    }
+
+    /// Add the docs at `doc_path` to the entry, unless it already has them.
+    pub async fn store_docs(&self, doc_path: &Path) {
+        Self::store(doc_path, &self.doc_path()).await;
+    }
+
+    /// Add the search index at `index_path` to the entry, unless it already has
+    /// one. The index is only stored alongside the docs it was built from.
+    pub async fn store_index(&self, index_path: &Path) {
+        if tokio::fs::try_exists(self.doc_path())
+            .await
+            .unwrap_or(false)
+        {
+            Self::store(index_path, &self.index_path()).await;
+        }
+    }
+
+    async fn store(from: &Path, to: &Path) {
+        if tokio::fs::try_exists(to).await.unwrap_or(false) {
+            return;
+        }
+        match link_or_copy(from, to).await {
+            Ok(()) => tracing::debug!(path = %to.display(), "Stored in shared cache"),
+            Err(e) => {
+                tracing::warn!(path = %to.display(), error = ?e, "Failed to store in shared cache");
+            }
+        }
+    }
+}
+
+/// Hard-link `from` to `to`, replacing `to`, or copy it where linking fails (e.g.
+/// across filesystems). Goes through a temporary file so that readers never see
+/// a partial file.
+///
+/// A hard link shares its contents with the original: files reached this way must be
+/// replaced (unlinked) rather than rewritten in place.
+async fn link_or_copy(from: &Path, to: &Path) -> std::io::Result<()> {
+    static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    if let Some(parent) = to.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let file_name = to.file_name().unwrap_or_default().to_string_lossy();
+    let temp = to.with_file_name(format!(
+        ".{file_name}.{}.{}.tmp",
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    if tokio::fs::hard_link(from, &temp).await.is_err() {
+        tokio::fs::copy(from, &temp).await?;
+    }
+    let result = tokio::fs::rename(&temp, to).await;
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(&temp).await;
+    }
+    result
+}
+
 /// Hashes the rustc version output to invalidate caches on toolchain changes.
 async fn get_rustc_version_hash() -> Result<u64> {
     let output = tokio::process::Command::new("rustc")
@@ -267,6 +586,89 @@ async fn hash_directory(dir: &Path) -> Result<u64> {
     .context("Task panicked")?
 }
 
+/// Hashes every .rs file of a workspace member in deterministic order.
+///
+/// Covers the member's target source directories, build scripts, and files pulled in
+/// from elsewhere through `#[path = "..."]` attributes. Paths are hashed relative to
+/// the member's manifest directory so digests survive project moves.
+async fn hash_member_sources(sources: &MemberSources) -> Result<u64> {
+    let sources = sources.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let mut files: BTreeSet<PathBuf> = sources
+            .source_dirs
+            .iter()
+            .flat_map(|dir| rust_files(dir))
+            .chain(sources.extra_files.iter().filter(|f| f.is_file()).cloned())
+            .collect();
+
+        // Follow #[path] attributes, which may point outside the source directories
+        let mut pending: Vec<PathBuf> = files.iter().cloned().collect();
+        while let Some(file) = pending.pop() {
+            for target in path_attributes(&file) {
+                if files.insert(target.clone()) {
+                    pending.push(target);
+                }
+            }
+        }
+
+        let base = sources
+            .manifest_path
+            .parent()
+            .unwrap_or_else(|| Path::new(""));
+        let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+        for path in &files {
+            let rel_path = path.strip_prefix(base).unwrap_or(path);
+            hasher.update(rel_path.to_string_lossy().as_bytes());
+            if let Ok(content) = std::fs::read_to_string(path) {
+                hasher.update(content.as_bytes());
+            }
+        }
+
+        Ok(hasher.digest())
+    })
+    .await
+    .context("Task panicked")?
+}
+
+/// All .rs files under `dir`, skipping cargo target directories.
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    WalkBuilder::new(dir)
+        // Cargo marks its build directories with CACHEDIR.TAG; a target whose entry
+        // point sits in the package root would otherwise walk into `target/`.
+        .filter_entry(|entry| {
+            !entry.file_type().is_some_and(|ft| ft.is_dir())
+                || !entry.path().join("CACHEDIR.TAG").exists()
+        })
+        .build()
+        .filter_map(std::result::Result::ok)
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+        .map(ignore::DirEntry::into_path)
+        .collect()
+}
+
+/// Files referenced by `#[path = "..."]` attributes in a source file.
+///
+/// A textual scan rather than a parse: paths are resolved relative to the file's
+/// directory, and only ones that exist are returned.
+fn path_attributes(file: &Path) -> Vec<PathBuf> {
+    let Ok(content) = std::fs::read_to_string(file) else {
+        return Vec::new();
+    };
+    let dir = file.parent().unwrap_or_else(|| Path::new(""));
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim_start().strip_prefix("#[path")?.trim_start();
+            let rest = rest.strip_prefix('=')?.trim_start().strip_prefix('"')?;
+            let (path, _) = rest.split_once('"')?;
+            Some(dir.join(path))
+        })
+        .filter(|path| path.is_file())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -400,4 +802,195 @@ mod tests {
             .expect("Failed to get rustc version");
         check!(hash > 0);
     }
+
+    #[tokio::test]
+    async fn test_dependency_digest_tracks_features() {
+        let checksum = Hash::Sha256([7; 32]);
+        let default = compute_dependency_digest("serde", "1.0.0", checksum, None)
+            .await
+            .unwrap();
+        let derive = compute_dependency_digest(
+            "serde",
+            "1.0.0",
+            checksum,
+            Some(&["derive".to_string(), "std".to_string()]),
+        )
+        .await
+        .unwrap();
+        let none = compute_dependency_digest("serde", "1.0.0", checksum, Some(&[]))
+            .await
+            .unwrap();
+
+        check!(default != derive);
+        check!(default != none);
+        check!(derive != none);
+    }
+
+    /// A virtual workspace with members `a` (with a build script and a `#[path]`
+    /// module outside `src`) and `b`.
+    fn virtual_workspace() -> (tempfile::TempDir, MemberSources) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n");
+        write("crates/a/Cargo.toml", "[package]\nname = \"a\"\n");
+        write("crates/a/build.rs", "fn main() {}\n");
+        write(
+            "crates/a/src/lib.rs",
+            "#[path = \"../generated/gen.rs\"]\nmod generated;\n",
+        );
+        write("crates/a/generated/gen.rs", "pub struct Generated;\n");
+        write("crates/b/Cargo.toml", "[package]\nname = \"b\"\n");
+        write("crates/b/src/lib.rs", "pub struct B;\n");
+
+        let member = root.join("crates/a");
+        let sources = MemberSources {
+            manifest_path: member.join("Cargo.toml"),
+            source_dirs: vec![member.join("src")],
+            extra_files: vec![member.join("build.rs")],
+        };
+        (dir, sources)
+    }
+
+    async fn member_digest(root: &Path, sources: &MemberSources) -> CrateDigest {
+        compute_workspace_digest("a", root, Some(sources), None)
+            .await
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case::source("crates/a/src/lib.rs", true)]
+    #[case::build_script("crates/a/build.rs", true)]
+    #[case::path_attribute("crates/a/generated/gen.rs", true)]
+    #[case::member_manifest("crates/a/Cargo.toml", true)]
+    #[case::workspace_manifest("Cargo.toml", true)]
+    #[case::other_member("crates/b/src/lib.rs", false)]
+    #[tokio::test]
+    async fn test_member_digest_invalidation(#[case] edited: &str, #[case] invalidates: bool) {
+        let (dir, sources) = virtual_workspace();
+        let before = member_digest(dir.path(), &sources).await;
+
+        let path = dir.path().join(edited);
+        let mut content = std::fs::read_to_string(&path).unwrap();
+        content.push_str("// edited\n");
+        std::fs::write(&path, content).unwrap();
+
+        let after = member_digest(dir.path(), &sources).await;
+        check!((before != after) == invalidates);
+    }
+
+    fn dependency_digest(checksum: Hash, features: Option<Vec<String>>) -> CrateDigest {
+        CrateDigest {
+            rustc_version_hash: 1,
+            crate_type: DigestVariant::Dependency {
+                version: "1.0.0".to_string(),
+                checksum,
+                features,
+            },
+        }
+    }
+
+    #[test]
+    fn test_shared_entry_keys() {
+        let cache = SharedCache::new(PathBuf::from("/cache"));
+        let digest = dependency_digest(Hash::Sha256([7; 32]), None);
+
+        let_assert!(Some(entry) = cache.entry("serde-json", &digest));
+        check!(entry.doc_path().starts_with("/cache/crates"));
+        check!(entry.doc_path().file_name().unwrap() == "serde_json.json");
+        check!(cache.entry("serde_json@1.0.0", &digest) == Some(entry.clone()));
+        check!(cache.entry("serde", &digest) != Some(entry.clone()));
+
+        let derive = dependency_digest(Hash::Sha256([7; 32]), Some(vec!["derive".to_string()]));
+        check!(cache.entry("serde_json", &derive) != Some(entry.clone()));
+        let mut toolchain = digest;
+        toolchain.rustc_version_hash = 2;
+        check!(cache.entry("serde_json", &toolchain) != Some(entry));
+    }
+
+    #[test]
+    fn test_shared_entry_requires_checksum() {
+        let cache = SharedCache::new(PathBuf::from("/cache"));
+        let unchecked = dependency_digest(Hash::sha256([0; 32]), None);
+        check!(cache.entry("serde", &unchecked).is_none());
+
+        let member = CrateDigest {
+            rustc_version_hash: 1,
+            crate_type: DigestVariant::WorkspaceMember {
+                manifest_hash: 1,
+                source_hash: 2,
+                features: None,
+            },
+        };
+        check!(cache.entry("serde", &member).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shared_entry_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SharedCache::new(dir.path().join("cache"));
+        let_assert!(
+            Some(entry) = cache.entry("serde", &dependency_digest(Hash::Sha256([7; 32]), None))
+        );
+
+        let first = dir.path().join("first/doc");
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::write(first.join("serde.json"), "{}").unwrap();
+        std::fs::write(first.join("serde.index"), "index").unwrap();
+
+        // Nothing to restore yet, and an index is only stored next to its docs
+        check!(
+            !entry
+                .restore(&first.join("x.json"), &first.join("x.index"))
+                .await
+        );
+        entry.store_index(&first.join("serde.index")).await;
+        check!(!entry.index_path().exists());
+
+        entry.store_docs(&first.join("serde.json")).await;
+        entry.store_index(&first.join("serde.index")).await;
+
+        let second = dir.path().join("second/doc");
+        let (doc_path, index_path) = (second.join("serde.json"), second.join("serde.index"));
+        check!(entry.restore(&doc_path, &index_path).await);
+        check!(std::fs::read_to_string(&doc_path).unwrap() == "{}");
+        check!(std::fs::read_to_string(&index_path).unwrap() == "index");
+    }
+
+    #[tokio::test]
+    async fn test_shared_entry_restore_drops_stale_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SharedCache::new(dir.path().join("cache"));
+        let_assert!(
+            Some(entry) = cache.entry("serde", &dependency_digest(Hash::Sha256([7; 32]), None))
+        );
+        let source = dir.path().join("serde.json");
+        std::fs::write(&source, "{}").unwrap();
+        entry.store_docs(&source).await;
+
+        let doc = dir.path().join("doc");
+        std::fs::create_dir_all(&doc).unwrap();
+        std::fs::write(doc.join("serde.index"), "stale").unwrap();
+        check!(
+            entry
+                .restore(&doc.join("serde.json"), &doc.join("serde.index"))
+                .await
+        );
+        check!(!doc.join("serde.index").exists());
+    }
+
+    #[test]
+    fn test_dependency_digest_without_features_deserializes() {
+        let json = r#"{
+            "rustc_version_hash": 1,
+            "crate_type": {"Dependency": {"version": "1.0.0", "checksum": "00000000000000ff"}}
+        }"#;
+        let_assert!(Ok(digest) = serde_json::from_str::<CrateDigest>(json));
+        let_assert!(DigestVariant::Dependency { features, .. } = digest.crate_type);
+        check!(features.is_none());
+    }
 }
@@ -250,6 +250,14 @@ pub enum QueryError {
         query: String,
         expected_kind: crate::search::ItemKind,
     },
+
+    /// Signature query could not be parsed.
+    #[error("Invalid signature query '{query}': {reason}")]
+    InvalidSignature { query: String, reason: String },
+
+    /// Text search query could not be parsed.
+    #[error("Invalid search query '{query}': {reason}")]
+    InvalidQuery { query: String, reason: String },
 }
 
 impl QueryError {
@@ -271,6 +279,20 @@ impl QueryError {
             Self::WrongKind { .. } => {
                 Some("Try searching without the kind filter to see all matching items.")
             }
+            Self::InvalidSignature { .. } => Some(
+                "Signature queries look like Rust signatures without the name:\n\
+                 • '&str -> usize' (parameters and return type)\n\
+                 • '(&str, usize) -> bool' (several parameters)\n\
+                 • '-> Result<_, io::Error>' (return type only)\n\
+                 • Use '_' or single letters like 'T' as wildcards",
+            ),
+            Self::InvalidQuery { .. } => Some(
+                "Search queries combine words with:\n\
+                 • \"connection pool\" (words next to each other, in this order)\n\
+                 • -word or -\"some phrase\" (leave out items containing it)\n\
+                 • name:Pool, doc:retry, path:sync (only match that part of an item)\n\
+                 • kind:struct (only items of that kind, e.g. fn, trait, enum, mod)",
+            ),
         }
     }
 }
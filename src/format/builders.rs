@@ -6,7 +6,7 @@
 use crate::search::rustdoc::CrateIndex;
 use rustdoc_types::{
     AssocItemConstraintKind, GenericArg, GenericArgs, GenericBound, GenericParamDef,
-    GenericParamDefKind, Generics, Item, ItemEnum, Path, Term, TraitBoundModifier, Type,
+    GenericParamDefKind, Generics, Impl, Item, ItemEnum, Path, Term, TraitBoundModifier, Type,
     WherePredicate,
 };
 use std::fmt::{self, Write};
@@ -70,7 +70,30 @@ impl<'a> TypeFormatter<'a> {
                 self.write_type(w, type_)
             }
             Type::FunctionPointer(_) => w.write_str("fn(...)"),
-            Type::QualifiedPath { .. } => w.write_str("<qualified path>"),
+            Type::QualifiedPath {
+                name,
+                args,
+                self_type,
+                trait_,
+            } => {
+                // `<Self as Trait>::Ok` reads as `Self::Ok`; only concrete
+                // self types need the explicit `<T as Trait>` form.
+                match (self_type.as_ref(), trait_) {
+                    (Type::Generic(_), _) | (_, None) => self.write_type(w, self_type)?,
+                    (_, Some(trait_)) => {
+                        w.write_char('<')?;
+                        self.write_type(w, self_type)?;
+                        w.write_str(" as ")?;
+                        self.write_resolved_path(w, trait_)?;
+                        w.write_char('>')?;
+                    }
+                }
+                w.write_str("::")?;
+                match args {
+                    Some(args) => self.write_type_args(w, name, args),
+                    None => w.write_str(name),
+                }
+            }
             // TODO: Handle these properly
             Type::DynTrait(..) | Type::Pat { .. } | Type::ImplTrait(..) | Type::Infer => {
                 w.write_str("<type>")
@@ -180,6 +203,44 @@ impl<'a> TypeFormatter<'a> {
         }
     }
 
+    /// Write plain bounds for associated types: `: Clone + Debug`.
+    /// Writes nothing if there are no bounds.
+    pub fn write_bounds<W: Write>(&self, w: &mut W, bounds: &[GenericBound]) -> fmt::Result {
+        if bounds.is_empty() {
+            return Ok(());
+        }
+        let formatted: Vec<_> = bounds
+            .iter()
+            .map(|b| self.format_generic_bound(b))
+            .collect();
+        write!(w, ": {}", formatted.join(" + "))
+    }
+
+    /// Write an impl block header: `impl<T> Trait for Type where ...` or `impl<T> Type`.
+    /// Where clauses may span multiple lines; no trailing newline is written.
+    pub fn write_impl_header<W: Write>(&self, w: &mut W, impl_block: &Impl) -> fmt::Result {
+        let mut header = String::from("impl");
+        self.write_generics(&mut header, &impl_block.generics)?;
+        header.push(' ');
+        if let Some(trait_) = &impl_block.trait_ {
+            if impl_block.is_negative {
+                header.push('!');
+            }
+            self.write_resolved_path(&mut header, trait_)?;
+            header.push_str(" for ");
+        }
+        self.write_type(&mut header, &impl_block.for_)?;
+
+        let mut where_clause = String::new();
+        self.write_where_clause(
+            &mut where_clause,
+            &impl_block.generics.where_predicates,
+            header.len(),
+        )?;
+        w.write_str(&header)?;
+        w.write_str(where_clause.trim_end())
+    }
+
     /// Write a function signature including generics and parameters.
     /// Returns Ok(true) if signature was written, Ok(false) if item is not a function.
     pub fn write_function_signature<W: Write>(&self, w: &mut W, item: &Item) -> fmt::Result {
@@ -272,6 +333,17 @@ impl<'a> TypeFormatter<'a> {
         }
     }
 
+    /// Resolve a path to its canonical segments via the crate's `paths` table
+    /// (e.g. `["std", "io", "error", "Error"]`).
+    ///
+    /// Returns `None` when the path's target is not recorded in this crate's docs.
+    pub(crate) fn path_segments(&self, path: &Path) -> Option<&'a [String]> {
+        self.index
+            .paths()
+            .get(&path.id)
+            .map(|summary| summary.path.as_slice())
+    }
+
     /// Check if a path is from std/core/alloc (use short name).
     fn is_std_path(path: &[String]) -> bool {
         matches!(
@@ -286,15 +358,15 @@ impl<'a> TypeFormatter<'a> {
     /// For external crate types, qualifies with the crate name (e.g. `anyhow::Result`)
     /// to avoid tautological displays like `type Result<T> = Result<T>`.
     fn write_resolved_path<W: Write>(&self, w: &mut W, path: &Path) -> fmt::Result {
-        let Some(summary) = self.index.paths().get(&path.id) else {
+        let Some(segments) = self.path_segments(path) else {
             return w.write_str("<type>");
         };
 
-        let name = if Self::is_std_path(&summary.path) {
-            summary.path.last().map_or("?", String::as_str)
+        let name = if Self::is_std_path(segments) {
+            segments.last().map_or("?", String::as_str)
         } else {
             // For external types, return a qualified name to avoid ambiguity
-            match (summary.path.first(), summary.path.last()) {
+            match (segments.first(), segments.last()) {
                 (Some(crate_name), Some(item_name)) if crate_name != item_name => {
                     // Write qualified name inline and return early
                     let qualified = format!("{crate_name}::{item_name}");
@@ -303,7 +375,7 @@ impl<'a> TypeFormatter<'a> {
                         None => w.write_str(&qualified),
                     };
                 }
-                _ => summary.path.last().map_or("?", String::as_str),
+                _ => segments.last().map_or("?", String::as_str),
             }
         };
 
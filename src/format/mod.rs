@@ -2,6 +2,7 @@
 
 mod builders;
 pub(crate) mod renderers;
+pub(crate) mod source;
 
 use rmcp::schemars;
 use serde::{Deserialize, Serialize};
@@ -5,7 +5,7 @@
 
 use super::{DetailLevel, TypeFormatter};
 use crate::item::item_ref::ItemRef;
-use rustdoc_types::{Item, ItemEnum, ItemKind};
+use rustdoc_types::{Item, ItemEnum, ItemKind, MacroKind, ProcMacro};
 use std::collections::HashMap;
 use std::fmt::{self, Write as _};
 
@@ -185,11 +185,11 @@ pub(crate) fn render_function(
     // Low: signature only
     fmt.write_function_signature(output, &item)?;
     writeln!(output)?;
-    writeln!(output, "// in {}::{}", crate_name, path)?;
+    write_location(output, item, crate_name, &path)?;
 
     // Medium: add short docs
     if matches!(detail_level, DetailLevel::Medium | DetailLevel::High)
-        && let Some(docs) = item.comment()
+        && let Some(docs) = item_docs(item)
     {
         let short_docs = extract_summary(docs);
         writeln!(output, "\n{}", short_docs)?;
@@ -269,6 +269,8 @@ pub(crate) fn render_module(
         (ItemKind::Constant, "Constants"),
         (ItemKind::Static, "Statics"),
         (ItemKind::Macro, "Macros"),
+        (ItemKind::ProcDerive, "Derive Macros"),
+        (ItemKind::ProcAttribute, "Attribute Macros"),
     ];
 
     let default_name = crate_name.to_string();
@@ -461,6 +463,220 @@ pub(crate) fn render_static(
     Ok(())
 }
 
+/// Render associated constant output (`Type::CONST`, `Trait::CONST`)
+pub(crate) fn render_assoc_const(
+    output: &mut String,
+    item: ItemRef<'_, Item>,
+    type_: &rustdoc_types::Type,
+    value: Option<&str>,
+    detail_level: DetailLevel,
+    crate_name: &str,
+) -> fmt::Result {
+    let name = item.name().unwrap_or("<unnamed>");
+    let fmt = TypeFormatter::new(item.crate_index());
+
+    write!(output, "const {}: ", name)?;
+    fmt.write_type(output, type_)?;
+    if let Some(value) = value {
+        write!(output, " = {}", value)?;
+    }
+    writeln!(output, ";")?;
+    write_location(output, item, crate_name, name)?;
+
+    if matches!(detail_level, DetailLevel::Medium | DetailLevel::High)
+        && let Some(docs) = item_docs(item)
+    {
+        let short_docs = extract_summary(docs);
+        writeln!(output, "\n{}", short_docs)?;
+    }
+
+    Ok(())
+}
+
+/// Render associated type output (`Trait::Assoc`, or its definition in an impl)
+pub(crate) fn render_assoc_type(
+    output: &mut String,
+    item: ItemRef<'_, Item>,
+    generics: &rustdoc_types::Generics,
+    bounds: &[rustdoc_types::GenericBound],
+    type_: Option<&rustdoc_types::Type>,
+    detail_level: DetailLevel,
+    crate_name: &str,
+) -> fmt::Result {
+    let name = item.name().unwrap_or("<unnamed>");
+    let fmt = TypeFormatter::new(item.crate_index());
+
+    write!(output, "type {}", name)?;
+    fmt.write_generics(output, generics)?;
+    fmt.write_bounds(output, bounds)?;
+    if let Some(type_) = type_ {
+        write!(output, " = ")?;
+        fmt.write_type(output, type_)?;
+    }
+    writeln!(output, ";")?;
+    write_location(output, item, crate_name, name)?;
+
+    if matches!(detail_level, DetailLevel::Medium | DetailLevel::High)
+        && let Some(docs) = item_docs(item)
+    {
+        let short_docs = extract_summary(docs);
+        writeln!(output, "\n{}", short_docs)?;
+    }
+
+    Ok(())
+}
+
+/// Render declarative macro output, including its `macro_rules!` arms.
+///
+/// Rustdoc stores the macro's matchers with every transcriber collapsed to
+/// `{ ... }`, which is exactly the "signature" of each arm.
+pub(crate) fn render_macro(
+    output: &mut String,
+    item: ItemRef<'_, Item>,
+    source: &str,
+    detail_level: DetailLevel,
+    crate_name: &str,
+) -> fmt::Result {
+    let name = item.name().unwrap_or("<unnamed>");
+    let path = item
+        .path()
+        .map_or_else(|| name.to_string(), |p| p.to_string());
+
+    writeln!(output, "{}", source.trim_end())?;
+    writeln!(output, "// in {}::{}", crate_name, path)?;
+
+    if matches!(detail_level, DetailLevel::Medium | DetailLevel::High)
+        && let Some(docs) = item.comment()
+    {
+        let short_docs = extract_summary(docs);
+        writeln!(output, "\n{}", short_docs)?;
+    }
+
+    Ok(())
+}
+
+/// Render procedural macro output (function-like, derive, or attribute)
+pub(crate) fn render_proc_macro(
+    output: &mut String,
+    item: ItemRef<'_, Item>,
+    proc_macro: &ProcMacro,
+    detail_level: DetailLevel,
+    crate_name: &str,
+) -> fmt::Result {
+    let name = item.name().unwrap_or("<unnamed>");
+    let path = item
+        .path()
+        .map_or_else(|| name.to_string(), |p| p.to_string());
+
+    writeln!(output, "{}", proc_macro_usage(name, proc_macro.kind))?;
+    if !proc_macro.helpers.is_empty() {
+        let helpers: Vec<String> = proc_macro
+            .helpers
+            .iter()
+            .map(|helper| format!("#[{helper}]"))
+            .collect();
+        writeln!(output, "// helper attributes: {}", helpers.join(", "))?;
+    }
+    writeln!(output, "// in {}::{}", crate_name, path)?;
+
+    if matches!(detail_level, DetailLevel::Medium | DetailLevel::High)
+        && let Some(docs) = item.comment()
+    {
+        let short_docs = extract_summary(docs);
+        writeln!(output, "\n{}", short_docs)?;
+    }
+
+    Ok(())
+}
+
+/// How a procedural macro is invoked: `name!(...)`, `#[derive(Name)]`, or `#[name]`.
+fn proc_macro_usage(name: &str, kind: MacroKind) -> String {
+    match kind {
+        MacroKind::Bang => format!("{name}!(...)"),
+        MacroKind::Derive => format!("#[derive({name})]"),
+        MacroKind::Attr => format!("#[{name}]"),
+    }
+}
+
+/// Write the `// in ...` location line.
+///
+/// Associated items have no `paths` entry of their own, so instead of a module
+/// path they show the impl block or trait they belong to, and for trait impls,
+/// which trait the item comes from.
+fn write_location(
+    output: &mut String,
+    item: ItemRef<'_, Item>,
+    crate_name: &str,
+    path: &str,
+) -> fmt::Result {
+    let owner = if item.path().is_none() {
+        item.owner()
+    } else {
+        None
+    };
+    let Some(owner) = owner else {
+        return writeln!(output, "// in {}::{}", crate_name, path);
+    };
+
+    let fmt = TypeFormatter::new(owner.crate_index());
+    match owner.inner() {
+        ItemEnum::Impl(impl_block) => {
+            let mut header = String::new();
+            fmt.write_impl_header(&mut header, impl_block)?;
+            for line in header.lines() {
+                writeln!(output, "// {}", line)?;
+            }
+            if let Some(trait_) = &impl_block.trait_ {
+                let trait_path = fmt
+                    .path_segments(trait_)
+                    .map_or_else(|| trait_.path.clone(), |segments| segments.join("::"));
+                writeln!(output, "// from trait {}", trait_path)?;
+            }
+        }
+        ItemEnum::Trait(_) => {
+            let trait_path = owner
+                .path_string()
+                .unwrap_or_else(|| owner.name().unwrap_or("<unnamed>").to_string());
+            writeln!(output, "// in trait {}", trait_path)?;
+        }
+        _ => writeln!(output, "// in {}::{}", crate_name, path)?,
+    }
+    Ok(())
+}
+
+/// Documentation for an item, falling back to the trait's declaration for
+/// undocumented trait impl items (which inherit their docs in rustdoc HTML).
+fn item_docs(item: ItemRef<'_, Item>) -> Option<&str> {
+    if let Some(docs) = item.comment() {
+        return Some(docs);
+    }
+    if item.path().is_some() {
+        return None;
+    }
+
+    let owner = item.owner()?;
+    let ItemEnum::Impl(impl_block) = owner.inner() else {
+        return None;
+    };
+    let trait_ = impl_block.trait_.as_ref()?;
+    let trait_item = item.get(trait_.id).or_else(|| {
+        let segments = TypeFormatter::new(owner.crate_index()).path_segments(trait_)?;
+        item.query()
+            .resolve_path(&segments.join("::"), &mut Vec::new())
+    })?;
+    let ItemEnum::Trait(trait_def) = trait_item.inner() else {
+        return None;
+    };
+
+    let name = item.name()?;
+    trait_def
+        .items
+        .iter()
+        .filter_map(|id| trait_item.get(*id))
+        .find(|decl| decl.name() == Some(name))
+        .and_then(|decl| decl.comment())
+}
+
 /// Generate a signature string for an item
 pub(crate) fn render_item_signature(
     item: crate::item::ItemRef<'_, rustdoc_types::Item>,
@@ -508,8 +724,17 @@ pub(crate) fn render_item_signature(
         ItemEnum::Module(_) => {
             write!(&mut s, "mod {}", name)
         }
-        ItemEnum::Macro(_) => {
-            write!(&mut s, "macro {}", name)
+        ItemEnum::Macro(source) => {
+            // `macro_rules!` or a `macro` 2.0 definition
+            let keyword = if source.starts_with("macro_rules!") {
+                "macro_rules!"
+            } else {
+                "macro"
+            };
+            write!(&mut s, "{} {}", keyword, name)
+        }
+        ItemEnum::ProcMacro(pm) => {
+            write!(&mut s, "{}", proc_macro_usage(name, pm.kind))
         }
         _ => return None,
     };
@@ -0,0 +1,283 @@
+//! Source code display driven by rustdoc span information.
+//!
+//! Rustdoc records a [`Span`] for most items, but the filename is whatever path
+//! rustc was invoked with:
+//!
+//! - workspace members: relative to the workspace root (`src/lib.rs`)
+//! - registry and git dependencies: absolute paths under `$CARGO_HOME`, which
+//!   may point at another machine's cargo home if the JSON was generated elsewhere
+//! - stdlib: remapped to `/rustc/<commit>/library/...`, which maps onto the
+//!   `rust-src` component at `<sysroot>/lib/rustlib/src/rust/library/...`
+//!
+//! [`SourceLocator`] maps a span filename back to a readable file, and
+//! [`render_source`] prints the spanned lines with surrounding context.
+
+use crate::item::item_ref::ItemRef;
+use rustdoc_types::{Item, Span};
+use std::fmt::Write as _;
+use std::path::{Component, Path, PathBuf};
+
+/// Lines of context shown before and after an item's span.
+const SOURCE_CONTEXT_LINES: usize = 3;
+
+/// Maximum number of spanned lines shown; longer definitions (usually modules) are truncated.
+const MAX_SOURCE_LINES: usize = 300;
+
+/// Resolves rustdoc span filenames to files on disk.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SourceLocator {
+    workspace_root: Option<PathBuf>,
+    sysroot: Option<PathBuf>,
+    cargo_home: Option<PathBuf>,
+}
+
+impl SourceLocator {
+    /// Create a locator for the given workspace root and toolchain sysroot.
+    ///
+    /// The cargo home is taken from `CARGO_HOME`, falling back to `~/.cargo`.
+    pub(crate) fn new(workspace_root: Option<PathBuf>, sysroot: Option<PathBuf>) -> Self {
+        let cargo_home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+        Self {
+            workspace_root,
+            sysroot,
+            cargo_home,
+        }
+    }
+
+    /// Resolve a span filename to an existing file, if possible.
+    pub(crate) fn resolve(&self, filename: &Path) -> Option<PathBuf> {
+        self.candidates(filename).into_iter().find(|p| p.is_file())
+    }
+
+    /// All plausible on-disk locations for a span filename, most specific first.
+    fn candidates(&self, filename: &Path) -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+
+        if filename.is_absolute() {
+            candidates.push(filename.to_path_buf());
+        } else if let Some(root) = &self.workspace_root {
+            candidates.push(root.join(filename));
+        }
+
+        // `/rustc/<commit>/library/core/src/...` -> `<sysroot>/lib/rustlib/src/rust/library/...`
+        if let Some(sysroot) = &self.sysroot
+            && let Ok(rest) = filename.strip_prefix("/rustc")
+        {
+            let rest: PathBuf = rest.components().skip(1).collect();
+            candidates.push(sysroot.join("lib/rustlib/src/rust").join(rest));
+        }
+
+        // Registry and git checkouts generated under a different cargo home.
+        if let Some(cargo_home) = &self.cargo_home
+            && let Some(rest) = strip_through_cargo_home(filename)
+        {
+            candidates.push(cargo_home.join(rest));
+        }
+
+        candidates
+    }
+}
+
+/// Strip everything before a `registry/src` or `git/checkouts` segment pair.
+fn strip_through_cargo_home(filename: &Path) -> Option<PathBuf> {
+    let components: Vec<Component<'_>> = filename.components().collect();
+    components
+        .windows(2)
+        .position(|pair| {
+            matches!(
+                (pair[0].as_os_str().to_str(), pair[1].as_os_str().to_str()),
+                (Some("registry"), Some("src")) | (Some("git"), Some("checkouts"))
+            )
+        })
+        .map(|start| components[start..].iter().collect())
+}
+
+/// Render the source of an item's definition with surrounding context lines.
+///
+/// Lines inside the span are marked with `>` in the gutter. Errors are
+/// user-facing messages explaining why the source is unavailable.
+pub(crate) fn render_source(
+    output: &mut String,
+    item: ItemRef<'_, Item>,
+    locator: &SourceLocator,
+    crate_name: &str,
+) -> Result<(), String> {
+    let name = item.name().unwrap_or("<unnamed>");
+    let path = item
+        .path()
+        .map_or_else(|| format!("{crate_name}::{name}"), |p| p.to_string());
+
+    let Some(span) = &item.span else {
+        return Err(format!(
+            "No source location recorded for '{path}'. \
+             Re-exports and compiler-generated items have no span."
+        ));
+    };
+
+    let Some(file) = locator.resolve(&span.filename) else {
+        let mut msg = format!(
+            "Source file for '{path}' not found: {}",
+            span.filename.display()
+        );
+        if span.filename.starts_with("/rustc") {
+            msg.push_str(
+                "\n\nStandard library sources require the rust-src component:\n\
+                 • Run: rustup component add rust-src --toolchain nightly",
+            );
+        }
+        return Err(msg);
+    };
+
+    let contents = std::fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read {}: {e}", file.display()))?;
+
+    let _ = writeln!(output, "// {path}");
+    let _ = writeln!(
+        output,
+        "// {}:{}-{}\n",
+        file.display(),
+        span.begin.0,
+        span.end.0
+    );
+    write_span_lines(output, &contents, span, SOURCE_CONTEXT_LINES);
+    Ok(())
+}
+
+/// Write the spanned lines (1-based, inclusive) plus `context` lines on each side.
+fn write_span_lines(output: &mut String, contents: &str, span: &Span, context: usize) {
+    let lines: Vec<&str> = contents.lines().collect();
+    let begin = span.begin.0.max(1);
+    let end = span.end.0.clamp(begin, lines.len().max(begin));
+    let truncated_end = end.min(begin + MAX_SOURCE_LINES - 1);
+
+    let first = begin.saturating_sub(context).max(1);
+    let last = if truncated_end < end {
+        truncated_end
+    } else {
+        (end + context).min(lines.len())
+    };
+    let width = last.to_string().len();
+
+    for (idx, line) in lines
+        .iter()
+        .enumerate()
+        .take(last)
+        .skip(first - 1)
+        .map(|(i, l)| (i + 1, l))
+    {
+        let marker = if (begin..=end).contains(&idx) {
+            '>'
+        } else {
+            ' '
+        };
+        let _ = writeln!(output, "{marker}{idx:>width$} | {line}");
+    }
+
+    if truncated_end < end {
+        let _ = writeln!(
+            output,
+            "  ... {} more lines (definition spans {begin}-{end})",
+            end - truncated_end
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+
+    fn span(begin: usize, end: usize) -> Span {
+        Span {
+            filename: PathBuf::from("src/lib.rs"),
+            begin: (begin, 1),
+            end: (end, 1),
+        }
+    }
+
+    #[test]
+    fn rustc_paths_map_into_rust_src() {
+        let locator = SourceLocator {
+            workspace_root: None,
+            sysroot: Some(PathBuf::from("/toolchain")),
+            cargo_home: None,
+        };
+        let candidates =
+            locator.candidates(Path::new("/rustc/0123abcd/library/core/src/option.rs"));
+        check!(
+            candidates.last()
+                == Some(&PathBuf::from(
+                    "/toolchain/lib/rustlib/src/rust/library/core/src/option.rs"
+                ))
+        );
+    }
+
+    #[test]
+    fn registry_paths_are_rebased_onto_local_cargo_home() {
+        let locator = SourceLocator {
+            workspace_root: None,
+            sysroot: None,
+            cargo_home: Some(PathBuf::from("/local/cargo")),
+        };
+        let candidates = locator.candidates(Path::new(
+            "/home/ci/.cargo/registry/src/index.crates.io-abc/serde-1.0.0/src/lib.rs",
+        ));
+        check!(
+            candidates
+                == [
+                    PathBuf::from(
+                        "/home/ci/.cargo/registry/src/index.crates.io-abc/serde-1.0.0/src/lib.rs"
+                    ),
+                    PathBuf::from(
+                        "/local/cargo/registry/src/index.crates.io-abc/serde-1.0.0/src/lib.rs"
+                    ),
+                ]
+        );
+    }
+
+    #[test]
+    fn relative_paths_resolve_against_workspace_root() {
+        let locator = SourceLocator {
+            workspace_root: Some(PathBuf::from("/work")),
+            sysroot: None,
+            cargo_home: None,
+        };
+        check!(locator.candidates(Path::new("src/lib.rs")) == [PathBuf::from("/work/src/lib.rs")]);
+    }
+
+    #[test]
+    fn span_lines_include_context_and_markers() {
+        let contents = (1..=10)
+            .map(|i| format!("line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut output = String::new();
+        write_span_lines(&mut output, &contents, &span(4, 5), 2);
+        check!(
+            output
+                == " 2 | line 2\n 3 | line 3\n>4 | line 4\n>5 | line 5\n 6 | line 6\n 7 | line 7\n"
+        );
+    }
+
+    #[test]
+    fn context_is_clamped_to_file_bounds() {
+        let contents = "a\nb\nc\n";
+        let mut output = String::new();
+        write_span_lines(&mut output, contents, &span(1, 3), 5);
+        check!(output == ">1 | a\n>2 | b\n>3 | c\n");
+    }
+
+    #[test]
+    fn long_spans_are_truncated() {
+        let contents = (1..=MAX_SOURCE_LINES + 50)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut output = String::new();
+        write_span_lines(&mut output, &contents, &span(1, MAX_SOURCE_LINES + 50), 3);
+        check!(output.lines().count() == MAX_SOURCE_LINES + 1);
+        check!(output.contains("... 50 more lines"));
+    }
+}
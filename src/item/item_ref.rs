@@ -139,6 +139,23 @@ impl<'a> ItemRef<'a, Item> {
             .and_then(|(id, _)| self.get(*id))
     }
 
+    /// For associated items, find the impl block or trait definition that declares it.
+    ///
+    /// Associated items have no `paths` entry, so this scans the crate index.
+    /// Returns `None` for items that are not associated items.
+    pub fn owner(&self) -> Option<Self> {
+        let id = self.id;
+        self.crate_index
+            .index
+            .values()
+            .find(|candidate| match &candidate.inner {
+                ItemEnum::Impl(impl_block) => impl_block.items.contains(&id),
+                ItemEnum::Trait(trait_) => trait_.items.contains(&id),
+                _ => false,
+            })
+            .map(|owner| self.build_ref(owner))
+    }
+
     /// Build a new ItemRef for a different item type using the same context.
     pub fn build_ref<U>(&self, inner: &'a U) -> ItemRef<'a, U> {
         ItemRef::builder(self.query, self.crate_index, inner).build()
@@ -475,6 +475,44 @@ impl<'a> ItemRef<'a, Item> {
     pub const fn children(&self) -> ChildrenBuilder<'a> {
         ChildrenBuilder::new(*self)
     }
+
+    /// Get the associated items reachable as `Self::name`: methods, consts and types.
+    ///
+    /// For traits, yields the items declared in the trait definition. For types,
+    /// yields items from inherent impls first, then trait impls, then blanket
+    /// impls, so inherent methods win when names collide.
+    pub fn associated_items(&self) -> impl Iterator<Item = ItemRef<'a, Item>> + use<'a> {
+        let item = *self;
+        let (own, impl_ids): (&'a [Id], &'a [Id]) = match item.inner() {
+            ItemEnum::Trait(t) => (&t.items, &[]),
+            ItemEnum::Struct(s) => (&[], &s.impls),
+            ItemEnum::Enum(e) => (&[], &e.impls),
+            ItemEnum::Union(u) => (&[], &u.impls),
+            ItemEnum::Primitive(p) => (&[], &p.impls),
+            _ => (&[], &[]),
+        };
+
+        let mut impls: Vec<(u8, &'a [Id])> = impl_ids
+            .iter()
+            .filter_map(|id| item.get(*id))
+            .filter_map(|impl_item| match impl_item.inner() {
+                ItemEnum::Impl(impl_block) => {
+                    let rank = match (&impl_block.trait_, &impl_block.blanket_impl) {
+                        (None, _) => 0,
+                        (Some(_), None) => 1,
+                        (Some(_), Some(_)) => 2,
+                    };
+                    Some((rank, impl_block.items.as_slice()))
+                }
+                _ => None,
+            })
+            .collect();
+        impls.sort_by_key(|(rank, _)| *rank);
+
+        own.iter()
+            .chain(impls.into_iter().flat_map(|(_, items)| items))
+            .filter_map(move |id| item.get(*id))
+    }
 }
 
 /// Extension methods for ItemRef to create Id iterators
@@ -2,12 +2,16 @@ pub mod cache;
 pub mod error;
 pub mod format;
 pub mod item;
+pub mod memory;
+pub mod progress;
+pub mod resources;
 pub mod search;
 pub mod server;
 pub mod stdlib;
 pub mod tools;
 pub mod tracing;
 pub mod types;
+pub mod watcher;
 pub mod worker;
 pub mod workspace;
 
@@ -18,6 +22,6 @@ pub use error::{
 };
 pub use format::{DetailLevel, TypeFormatter};
 pub use search::{ItemKind, QueryContext, index_metrics};
-pub use types::CrateName;
+pub use types::{CrateKey, CrateName};
 pub use worker::{DocState, ServiceContext};
 pub use workspace::{CrateMetadata, CrateOrigin, WorkspaceContext};
@@ -1,6 +1,7 @@
 use rmcp::{ServiceExt, transport::stdio};
 use rustdoc_mcp::server::ItemServer;
 use rustdoc_mcp::stdlib::StdlibDocs;
+use rustdoc_mcp::tools::cache;
 use rustdoc_mcp::worker::spawn_background_worker;
 use std::sync::Arc;
 
@@ -8,6 +9,11 @@ use std::sync::Arc;
 async fn main() -> anyhow::Result<()> {
     rustdoc_mcp::tracing::init();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some((command, rest)) = args.split_first() {
+        return run_command(command, rest).await;
+    }
+
     tracing::info!("Starting rustdoc-mcp MCP server");
 
     // Discover stdlib documentation (optional - server works without it)
@@ -49,3 +55,23 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Run a subcommand instead of the MCP server.
+async fn run_command(command: &str, args: &[String]) -> anyhow::Result<()> {
+    match command {
+        "cache" if args.iter().any(|arg| arg == "--help" || arg == "-h") => {
+            println!("{}", cache::CLI_USAGE);
+            Ok(())
+        }
+        "cache" => {
+            let output = cache::run_cli(args)
+                .await
+                .map_err(|e| anyhow::anyhow!("{e}\n\n{}", cache::CLI_USAGE))?;
+            print!("{output}");
+            Ok(())
+        }
+        _ => anyhow::bail!(
+            "Unknown command '{command}'. Run without arguments to start the MCP server, or see `rustdoc-mcp cache --help`."
+        ),
+    }
+}
@@ -0,0 +1,396 @@
+//! Byte-budgeted memory cache of parsed crate documentation.
+//!
+//! Parsed rustdoc JSON ranges from a few kilobytes to hundreds of megabytes
+//! (`std`), so the cache is bounded by the approximate memory its entries hold
+//! ([`CrateIndex::memory_bytes`]) rather than by their number. A crate's search
+//! index is kept next to its docs and counts towards the same entry. Every
+//! workspace and the standard library share one [`MemoryCache`], and the least
+//! recently used crates are evicted once the budget is exceeded.
+//!
+//! The budget defaults to [`DEFAULT_BUDGET_MB`] and can be set through the
+//! `RUSTDOC_MCP_MEMORY_MB` environment variable.
+
+use crate::search::{CrateIndex, TermIndex};
+use crate::types::CrateName;
+use lru::LruCache;
+use rmcp::schemars;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+
+/// Environment variable overriding the memory budget, in megabytes.
+pub const BUDGET_ENV: &str = "RUSTDOC_MCP_MEMORY_MB";
+
+/// Memory budget used when [`BUDGET_ENV`] is unset.
+pub const DEFAULT_BUDGET_MB: usize = 2048;
+
+const BYTES_PER_MB: usize = 1024 * 1024;
+
+/// Identifies an entry of the [`MemoryCache`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MemoryKey {
+    /// A crate of the workspace rooted at the path, by its cache key (see
+    /// [`WorkspaceContext::canonical_crate_name`](crate::WorkspaceContext::canonical_crate_name)).
+    Workspace(PathBuf, CrateName),
+    /// A standard library crate.
+    Stdlib(CrateName),
+}
+
+impl MemoryKey {
+    const fn crate_name(&self) -> &CrateName {
+        match self {
+            Self::Workspace(_, name) | Self::Stdlib(name) => name,
+        }
+    }
+}
+
+/// A parsed crate in the memory cache.
+#[derive(Clone)]
+pub struct CachedDocs {
+    pub index: Arc<CrateIndex>,
+    /// Feature set explicitly requested via
+    /// [`DocState::get_docs_with_features`](crate::DocState::get_docs_with_features);
+    /// `None` when built with the workspace's resolved features.
+    pub features_override: Option<Vec<String>>,
+}
+
+impl CachedDocs {
+    /// Docs built with the workspace's resolved features.
+    pub const fn resolved(index: Arc<CrateIndex>) -> Self {
+        Self {
+            index,
+            features_override: None,
+        }
+    }
+}
+
+/// Memory held by the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, schemars::JsonSchema)]
+pub struct MemoryUsage {
+    /// Approximate bytes held by all entries.
+    pub used_bytes: u64,
+    /// Of which standard library crates.
+    pub stdlib_bytes: u64,
+    /// Budget past which entries are evicted.
+    pub budget_bytes: u64,
+    pub entries: usize,
+}
+
+/// A crate's search index, with the modification time of the docs it was built from.
+struct CachedSearch {
+    index: Arc<TermIndex>,
+    source_mtime: Option<SystemTime>,
+}
+
+/// Everything held in memory for one crate.
+#[derive(Default)]
+struct Slot {
+    docs: Option<CachedDocs>,
+    search: Option<CachedSearch>,
+}
+
+impl Slot {
+    fn memory_bytes(&self) -> usize {
+        self.docs
+            .as_ref()
+            .map_or(0, |docs| docs.index.memory_bytes())
+            + self
+                .search
+                .as_ref()
+                .map_or(0, |search| search.index.memory_bytes())
+    }
+}
+
+struct Entries {
+    lru: LruCache<MemoryKey, Slot>,
+    used: usize,
+}
+
+impl Entries {
+    /// Update the slot of `key` in place, keeping the usage total in sync.
+    fn update(&mut self, key: MemoryKey, update: impl FnOnce(&mut Slot)) {
+        let slot = self.lru.get_or_insert_mut(key, Slot::default);
+        let before = slot.memory_bytes();
+        update(slot);
+        let after = slot.memory_bytes();
+        self.used = self.used + after - before;
+    }
+
+    /// Evict the least recently used slots until `budget` is met, sparing the
+    /// most recent one.
+    fn enforce(&mut self, budget: usize) {
+        while self.used > budget && self.lru.len() > 1 {
+            let Some((evicted, slot)) = self.lru.pop_lru() else {
+                break;
+            };
+            self.used -= slot.memory_bytes();
+            tracing::debug!(
+                crate_name = %evicted.crate_name(),
+                bytes = slot.memory_bytes(),
+                "Evicted docs from memory"
+            );
+        }
+
+        tracing::debug!(
+            used_bytes = self.used,
+            budget_bytes = budget,
+            entries = self.lru.len(),
+            "Memory cache usage"
+        );
+    }
+
+    fn remove(&mut self, key: &MemoryKey) {
+        if let Some(slot) = self.lru.pop(key) {
+            self.used -= slot.memory_bytes();
+        }
+    }
+}
+
+/// LRU cache of parsed crates and their search indices, bounded by their
+/// approximate memory footprint.
+pub struct MemoryCache {
+    budget: usize,
+    entries: Mutex<Entries>,
+}
+
+impl std::fmt::Debug for MemoryCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryCache")
+            .field("usage", &self.usage())
+            .finish()
+    }
+}
+
+impl Default for MemoryCache {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl MemoryCache {
+    /// Create a cache holding at most about `budget` bytes.
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            entries: Mutex::new(Entries {
+                lru: LruCache::unbounded(),
+                used: 0,
+            }),
+        }
+    }
+
+    /// Create a cache with the budget configured through [`BUDGET_ENV`].
+    pub fn from_env() -> Self {
+        let megabytes = match std::env::var(BUDGET_ENV) {
+            Ok(value) => value.trim().parse().unwrap_or_else(|_| {
+                tracing::warn!(
+                    value,
+                    default = DEFAULT_BUDGET_MB,
+                    "Invalid {BUDGET_ENV}, using the default memory budget"
+                );
+                DEFAULT_BUDGET_MB
+            }),
+            Err(_) => DEFAULT_BUDGET_MB,
+        };
+        Self::new(megabytes.saturating_mul(BYTES_PER_MB))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Get a crate's docs, marking them as recently used.
+    pub fn get(&self, key: &MemoryKey) -> Option<CachedDocs> {
+        self.lock().lru.get(key)?.docs.clone()
+    }
+
+    /// Check for a crate's docs without marking them as used.
+    pub fn contains(&self, key: &MemoryKey) -> bool {
+        self.lock()
+            .lru
+            .peek(key)
+            .is_some_and(|slot| slot.docs.is_some())
+    }
+
+    /// Insert a crate's docs, then evict the least recently used crates until
+    /// the cache fits its budget again.
+    ///
+    /// The crate itself is never evicted, even if it alone exceeds the budget.
+    /// Replacing docs drops the search index built from the previous ones.
+    pub fn insert(&self, key: MemoryKey, docs: CachedDocs) {
+        let mut entries = self.lock();
+        entries.update(key, |slot| {
+            slot.docs = Some(docs);
+            slot.search = None;
+        });
+        entries.enforce(self.budget);
+    }
+
+    /// Get a crate's search index, if it was built from docs last modified at
+    /// `source_mtime`.
+    pub(crate) fn search_index(
+        &self,
+        key: &MemoryKey,
+        source_mtime: Option<SystemTime>,
+    ) -> Option<Arc<TermIndex>> {
+        self.lock()
+            .lru
+            .get(key)?
+            .search
+            .as_ref()
+            .filter(|search| source_mtime.is_some() && search.source_mtime == source_mtime)
+            .map(|search| search.index.clone())
+    }
+
+    /// Keep a crate's search index, built from docs last modified at `source_mtime`.
+    pub(crate) fn insert_search_index(
+        &self,
+        key: MemoryKey,
+        index: Arc<TermIndex>,
+        source_mtime: Option<SystemTime>,
+    ) {
+        let mut entries = self.lock();
+        entries.update(key, |slot| {
+            slot.search = Some(CachedSearch {
+                index,
+                source_mtime,
+            });
+        });
+        entries.enforce(self.budget);
+    }
+
+    /// Remove a crate's docs and search index.
+    pub fn remove(&self, key: &MemoryKey) {
+        self.lock().remove(key);
+    }
+
+    /// Remove every crate for which `keep` returns false.
+    pub fn retain(&self, mut keep: impl FnMut(&MemoryKey) -> bool) {
+        let mut entries = self.lock();
+        let dropped: Vec<_> = entries
+            .lru
+            .iter()
+            .filter(|(key, _)| !keep(key))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &dropped {
+            entries.remove(key);
+        }
+    }
+
+    /// Crates of the workspace rooted at `root` whose docs are held in memory.
+    pub fn workspace_crates(&self, root: &Path) -> Vec<CrateName> {
+        self.lock()
+            .lru
+            .iter()
+            .filter_map(|(key, slot)| match key {
+                MemoryKey::Workspace(key_root, name) if key_root == root && slot.docs.is_some() => {
+                    Some(name.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Current memory use against the budget.
+    pub fn usage(&self) -> MemoryUsage {
+        let entries = self.lock();
+        let stdlib_bytes = entries
+            .lru
+            .iter()
+            .filter(|(key, _)| matches!(key, MemoryKey::Stdlib(_)))
+            .map(|(_, slot)| slot.memory_bytes())
+            .sum::<usize>();
+        MemoryUsage {
+            used_bytes: entries.used as u64,
+            stdlib_bytes: stdlib_bytes as u64,
+            budget_bytes: self.budget as u64,
+            entries: entries.lru.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+
+    fn index(dir: &Path, name: &str, padding: usize) -> Arc<CrateIndex> {
+        let path = dir.join(format!("{name}.json"));
+        let json = format!(
+            r#"{{"root":0,"crate_version":null,"includes_private":false,"index":{{}},"paths":{{}},"external_crates":{{}},"target":{{"triple":"x86_64-unknown-linux-gnu","target_features":[]}},"format_version":56}}{}"#,
+            " ".repeat(padding)
+        );
+        std::fs::write(&path, json).unwrap();
+        Arc::new(CrateIndex::load(&path).unwrap())
+    }
+
+    fn key(name: &str) -> MemoryKey {
+        MemoryKey::Workspace(PathBuf::from("/ws"), CrateName::new_unchecked(name))
+    }
+
+    #[test]
+    fn evicts_least_recently_used_past_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let small = index(dir.path(), "small", 0);
+        let cache = MemoryCache::new(small.memory_bytes() * 2);
+
+        cache.insert(key("a"), CachedDocs::resolved(small.clone()));
+        cache.insert(key("b"), CachedDocs::resolved(small.clone()));
+        check!(cache.get(&key("a")).is_some());
+        cache.insert(key("c"), CachedDocs::resolved(small.clone()));
+
+        check!(cache.contains(&key("a")));
+        check!(!cache.contains(&key("b")));
+        check!(cache.contains(&key("c")));
+        check!(cache.usage().used_bytes == 2 * small.memory_bytes() as u64);
+    }
+
+    #[test]
+    fn keeps_an_entry_larger_than_the_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MemoryCache::new(1);
+
+        cache.insert(key("a"), CachedDocs::resolved(index(dir.path(), "a", 0)));
+        cache.insert(key("b"), CachedDocs::resolved(index(dir.path(), "b", 0)));
+
+        check!(!cache.contains(&key("a")));
+        check!(cache.contains(&key("b")));
+        check!(cache.usage().entries == 1);
+    }
+
+    #[test]
+    fn counts_stdlib_in_the_same_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let large = index(dir.path(), "std", 4096);
+        let small = index(dir.path(), "serde", 0);
+        let cache = MemoryCache::new(large.memory_bytes() + small.memory_bytes());
+
+        let std_key = MemoryKey::Stdlib(CrateName::new_unchecked("std"));
+        cache.insert(std_key.clone(), CachedDocs::resolved(large.clone()));
+        cache.insert(key("serde"), CachedDocs::resolved(small.clone()));
+        check!(cache.usage().stdlib_bytes == large.memory_bytes() as u64);
+
+        cache.insert(key("tokio"), CachedDocs::resolved(small));
+        check!(!cache.contains(&std_key));
+        check!(cache.usage().stdlib_bytes == 0);
+    }
+
+    #[test]
+    fn replacing_and_removing_keep_usage_exact() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MemoryCache::new(usize::MAX);
+        let index = index(dir.path(), "a", 100);
+
+        cache.insert(key("a"), CachedDocs::resolved(index.clone()));
+        cache.insert(key("a"), CachedDocs::resolved(index.clone()));
+        check!(cache.usage().used_bytes == index.memory_bytes() as u64);
+
+        cache.insert(key("b"), CachedDocs::resolved(index));
+        cache.retain(|key| key.crate_name() != "a");
+        cache.remove(&key("b"));
+        check!(cache.usage().used_bytes == 0);
+        check!(cache.workspace_crates(Path::new("/ws")).is_empty());
+    }
+}
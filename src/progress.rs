@@ -0,0 +1,214 @@
+//! Progress of long-running documentation work.
+//!
+//! Generation and index building call [`report`], which forwards to the reporter
+//! of the task they run in, if any. The MCP server installs a reporter per tool
+//! call that has a progress token and turns reports into `notifications/progress`.
+//! Outside such a scope (e.g. in the background worker), reports are dropped, and
+//! [`is_observed`] lets callers skip work that only serves progress reporting.
+
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// A step of documentation work.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Progress {
+    /// `cargo rustdoc` for `crate_name` finished `done` of `total` compilation
+    /// units; `total` is unknown when cargo couldn't report its unit graph.
+    Compiling {
+        crate_name: String,
+        done: usize,
+        total: Option<usize>,
+    },
+    /// Building the search index of `crate_name`.
+    Indexing { crate_name: String },
+}
+
+impl Progress {
+    /// Units of work left in this step, if known.
+    pub fn remaining(&self) -> Option<usize> {
+        match self {
+            Self::Compiling { done, total, .. } => total.map(|total| total.saturating_sub(*done)),
+            Self::Indexing { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compiling {
+                crate_name,
+                done,
+                total: Some(total),
+            } => write!(f, "Documenting {crate_name}: {done}/{total} units compiled"),
+            Self::Compiling {
+                crate_name,
+                done,
+                total: None,
+            } => write!(f, "Documenting {crate_name}: {done} units compiled"),
+            Self::Indexing { crate_name } => write!(f, "Building search index for {crate_name}"),
+        }
+    }
+}
+
+/// Receives the progress reported within a [`scope`].
+pub type Reporter = Arc<dyn Fn(&Progress) + Send + Sync>;
+
+/// Where a task's progress goes.
+#[derive(Clone)]
+enum Sink {
+    Reporter(Reporter),
+    /// Published for the tasks [`follow`]ing it.
+    Channel(Arc<watch::Sender<Option<Progress>>>),
+}
+
+tokio::task_local! {
+    static SINK: Sink;
+}
+
+/// Report progress to the current task's reporter; a no-op without one.
+pub fn report(progress: &Progress) {
+    let _ = SINK.try_with(|sink| match sink {
+        Sink::Reporter(reporter) => reporter(progress),
+        Sink::Channel(updates) => {
+            updates.send_replace(Some(progress.clone()));
+        }
+    });
+}
+
+/// Whether progress reported by the current task reaches a reporter. False
+/// outside a [`scope`], and for a [`publish`]ed task nobody follows.
+pub fn is_observed() -> bool {
+    SINK.try_with(|sink| match sink {
+        Sink::Reporter(_) => true,
+        Sink::Channel(updates) => updates.receiver_count() > 0,
+    })
+    .unwrap_or(false)
+}
+
+/// Run `future` with `reporter` receiving its progress.
+pub async fn scope<F: Future>(reporter: Reporter, future: F) -> F::Output {
+    SINK.scope(Sink::Reporter(reporter), future).await
+}
+
+/// Run `future` with its progress published on `updates`, so that every task
+/// awaiting a shared generation can [`follow`] it.
+pub async fn publish<F: Future>(
+    updates: Arc<watch::Sender<Option<Progress>>>,
+    future: F,
+) -> F::Output {
+    SINK.scope(Sink::Channel(updates), future).await
+}
+
+/// Await `future`, re-reporting the progress published on `updates` to the
+/// current task's reporter meanwhile.
+///
+/// Without a reporter this only awaits `future`, so the publisher isn't
+/// [observed](is_observed) on its behalf.
+pub async fn follow<F: Future>(updates: &watch::Sender<Option<Progress>>, future: F) -> F::Output {
+    if !is_observed() {
+        return future.await;
+    }
+    let mut updates = updates.subscribe();
+    // Relay what was published before subscribing
+    updates.mark_changed();
+    tokio::pin!(future);
+    loop {
+        tokio::select! {
+            // Relay pending progress before finishing, so the last update isn't lost
+            biased;
+            changed = updates.changed() => {
+                if changed.is_err() {
+                    // The publisher is gone; nothing more to relay
+                    return future.await;
+                }
+                let latest = updates.borrow_and_update().clone();
+                if let Some(progress) = latest {
+                    report(&progress);
+                }
+            }
+            output = &mut future => return output,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+    use std::sync::Mutex;
+
+    fn collector() -> (Reporter, Arc<Mutex<Vec<Progress>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let reporter: Reporter = Arc::new(move |progress| {
+            sink.lock().unwrap().push(progress.clone());
+        });
+        (reporter, seen)
+    }
+
+    fn indexing(name: &str) -> Progress {
+        Progress::Indexing {
+            crate_name: name.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_reach_the_scoped_reporter() {
+        let (reporter, seen) = collector();
+        report(&indexing("outside"));
+        scope(reporter, async { report(&indexing("serde")) }).await;
+
+        check!(*seen.lock().unwrap() == [indexing("serde")]);
+    }
+
+    #[tokio::test]
+    async fn followers_receive_published_progress() {
+        let tx = Arc::new(watch::Sender::new(None));
+        let (gate_tx, gate_rx) = tokio::sync::oneshot::channel::<()>();
+        let generation = tokio::spawn(publish(tx.clone(), async move {
+            report(&indexing("serde"));
+            gate_rx.await.unwrap();
+        }));
+
+        let (reporter, seen) = collector();
+        let waiter = scope(
+            reporter,
+            follow(&tx, async {
+                tokio::task::yield_now().await;
+                gate_tx.send(()).unwrap();
+                generation.await.unwrap();
+            }),
+        );
+        waiter.await;
+
+        check!(*seen.lock().unwrap() == [indexing("serde")]);
+    }
+
+    #[tokio::test]
+    async fn published_progress_is_observed_only_while_followed() {
+        check!(!is_observed());
+        let (reporter, _) = collector();
+        check!(scope(reporter.clone(), async { is_observed() }).await);
+
+        let tx = Arc::new(watch::Sender::new(None));
+        check!(!publish(tx.clone(), async { is_observed() }).await);
+        let unobserved = publish(tx.clone(), async { is_observed() });
+        check!(!follow(&tx, unobserved).await);
+        let observed = publish(tx.clone(), async { is_observed() });
+        check!(scope(reporter, follow(&tx, observed)).await);
+    }
+
+    #[test]
+    fn compiling_messages_show_units() {
+        let progress = Progress::Compiling {
+            crate_name: "serde".to_string(),
+            done: 3,
+            total: Some(10),
+        };
+        check!(progress.to_string() == "Documenting serde: 3/10 units compiled");
+        check!(progress.remaining() == Some(7));
+    }
+}
@@ -0,0 +1,233 @@
+//! MCP resources exposing crate and item documentation.
+//!
+//! Every workspace crate is listed as `rustdoc://{crate}`, and any item can be
+//! read through `rustdoc://{crate}/{+path}` with `/`-separated path segments,
+//! e.g. `rustdoc://serde/de/Deserialize`. Reading a resource renders the item
+//! exactly like `inspect_item` at high detail.
+
+use crate::format::DetailLevel;
+use crate::tools::inspect_item::{
+    InspectItemRequest, StructuredInspectResult, handle_inspect_item_structured,
+    render_inspect_result,
+};
+use crate::types::CrateName;
+use crate::worker::DocState;
+use crate::workspace::CrateOrigin;
+use rmcp::model::{AnnotateAble, RawResource, RawResourceTemplate, Resource, ResourceTemplate};
+use std::sync::Arc;
+
+/// URI scheme prefix for documentation resources.
+pub const URI_SCHEME: &str = "rustdoc://";
+
+/// MIME type of rendered documentation.
+pub const MIME_TYPE: &str = "text/plain";
+
+/// A parsed `rustdoc://` URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceUri {
+    /// Crate the resource belongs to.
+    pub crate_name: String,
+    /// Item path within the crate; empty for the crate root.
+    pub path: Vec<String>,
+}
+
+impl ResourceUri {
+    /// Parse a `rustdoc://{crate}/{path}` URI.
+    ///
+    /// Path segments may be separated by `/` or `::`, and a trailing `/` is ignored.
+    /// Percent-encoded characters are decoded first, so a client that expanded the
+    /// template without reserved expansion (`de%2FDeserialize`) reads the same item.
+    pub fn parse(uri: &str) -> Result<Self, String> {
+        let rest = uri
+            .strip_prefix(URI_SCHEME)
+            .ok_or_else(|| format!("Unsupported resource URI '{uri}': expected {URI_SCHEME}"))?;
+        let rest = percent_decode(rest)
+            .ok_or_else(|| format!("Resource URI '{uri}' has an invalid percent-encoding"))?;
+
+        let mut segments = rest
+            .split('/')
+            .flat_map(|segment| segment.split("::"))
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string);
+
+        let crate_name = segments
+            .next()
+            .ok_or_else(|| format!("Resource URI '{uri}' does not name a crate"))?;
+
+        Ok(Self {
+            crate_name,
+            path: segments.collect(),
+        })
+    }
+
+    /// The `inspect_item` query this URI refers to (e.g. `serde::de::Deserialize`).
+    pub fn query(&self) -> String {
+        std::iter::once(self.crate_name.as_str())
+            .chain(self.path.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+}
+
+/// Decode `%XX` escapes in `text`, or `None` if an escape is malformed or the
+/// decoded bytes aren't UTF-8.
+fn percent_decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail
+                .get(..2)
+                .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))?;
+            bytes.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Build the URI for a crate root.
+pub fn crate_uri(crate_name: &str) -> String {
+    format!("{URI_SCHEME}{crate_name}")
+}
+
+/// Resource templates for reading arbitrary crates and items.
+pub fn resource_templates() -> Vec<ResourceTemplate> {
+    vec![
+        RawResourceTemplate::new(format!("{URI_SCHEME}{{crate}}"), "crate")
+            .with_title("Crate documentation")
+            .with_description("Root module of a crate, e.g. rustdoc://serde")
+            .with_mime_type(MIME_TYPE)
+            .no_annotation(),
+        // Reserved expansion, so the '/' between path segments isn't escaped
+        RawResourceTemplate::new(format!("{URI_SCHEME}{{crate}}/{{+path}}"), "item")
+            .with_title("Item documentation")
+            .with_description(
+                "Any item by path, with segments separated by '/', \
+                 e.g. rustdoc://serde/de/Deserialize or rustdoc://std/vec/Vec",
+            )
+            .with_mime_type(MIME_TYPE)
+            .no_annotation(),
+    ]
+}
+
+/// List one resource per workspace crate, plus the standard library crates
+/// when their docs are installed.
+///
+/// Workspace members come first, followed by dependencies in generation
+/// priority order. Crates whose docs haven't been generated yet are still
+/// listed and say so in their description; the list changes (and clients are
+/// notified) as the background worker finishes them.
+pub async fn list_resources(state: &Arc<DocState>) -> Vec<Resource> {
+    let mut resources = Vec::new();
+
+    if let Some(workspace) = state.workspace().await {
+        let target_doc = workspace.doc_dir();
+        for crate_name in workspace.prioritized_crates() {
+            let Some(meta) = workspace.get_crate(crate_name.as_str()) else {
+                continue;
+            };
+            let ready = state.is_cached(&workspace.root, crate_name.as_str()).await
+                || crate_name.doc_json_path(&target_doc).exists();
+
+            let mut description = match meta.origin {
+                CrateOrigin::Local => "Workspace member".to_string(),
+                CrateOrigin::External | CrateOrigin::Standard => meta
+                    .version
+                    .as_deref()
+                    .map_or_else(|| "Dependency".to_string(), |v| format!("Dependency v{v}")),
+            };
+            if let Some(desc) = &meta.description {
+                description = format!("{description}: {}", desc.trim());
+            }
+            if !ready {
+                description.push_str(" (documentation not generated yet)");
+            }
+
+            resources.push(crate_resource(&crate_name, description));
+        }
+    }
+
+    if state.stdlib().is_some() {
+        for crate_name in ["std", "core", "alloc"] {
+            resources.push(crate_resource(
+                &CrateName::new_unchecked(crate_name),
+                "Standard library".to_string(),
+            ));
+        }
+    }
+
+    resources
+}
+
+fn crate_resource(crate_name: &CrateName, description: String) -> Resource {
+    RawResource::new(crate_uri(crate_name.as_str()), crate_name.as_str())
+        .with_description(description)
+        .with_mime_type(MIME_TYPE)
+        .no_annotation()
+}
+
+/// Render the documentation behind a `rustdoc://` URI.
+///
+/// Resolution is the same as `inspect_item` for the equivalent path query. A
+/// URI that matches several items is an error listing the candidates, since a
+/// resource has to identify exactly one item.
+pub async fn read_resource(state: &Arc<DocState>, uri: &str) -> Result<String, String> {
+    let resource = ResourceUri::parse(uri)?;
+
+    let request = InspectItemRequest {
+        query: resource.query(),
+        kind: None,
+        detail_level: DetailLevel::High,
+        workspace: None,
+        features: None,
+    };
+
+    match handle_inspect_item_structured(state, request).await? {
+        StructuredInspectResult::Item { rendered, .. } => Ok(rendered),
+        ambiguous @ StructuredInspectResult::Disambiguation { .. } => Err(format!(
+            "Resource '{uri}' is ambiguous.{}",
+            render_inspect_result(&ambiguous)
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("rustdoc://serde", "serde", &[])]
+    #[case("rustdoc://serde/", "serde", &[])]
+    #[case("rustdoc://serde/de/Deserialize", "serde", &["de", "Deserialize"])]
+    #[case("rustdoc://std/vec::Vec/push", "std", &["vec", "Vec", "push"])]
+    #[case("rustdoc://serde/de%2FDeserialize", "serde", &["de", "Deserialize"])]
+    #[case("rustdoc://anyhow%401.0.0/Error", "anyhow@1.0.0", &["Error"])]
+    fn parses_resource_uris(#[case] uri: &str, #[case] crate_name: &str, #[case] path: &[&str]) {
+        let_assert!(Ok(parsed) = ResourceUri::parse(uri));
+        check!(parsed.crate_name == crate_name);
+        check!(parsed.path == path);
+    }
+
+    #[rstest]
+    #[case("file:///tmp/lib.rs")]
+    #[case("rustdoc://")]
+    #[case("rustdoc://serde/de%2")]
+    #[case("rustdoc://serde/%zzde")]
+    #[case("rustdoc://serde/%+1")]
+    #[case("rustdoc://serde/%FF")]
+    fn rejects_invalid_uris(#[case] uri: &str) {
+        check!(ResourceUri::parse(uri).is_err());
+    }
+
+    #[test]
+    fn query_joins_crate_and_path() {
+        let_assert!(Ok(parsed) = ResourceUri::parse("rustdoc://serde/de/Deserialize"));
+        check!(parsed.query() == "serde::de::Deserialize");
+    }
+}
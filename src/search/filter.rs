@@ -0,0 +1,132 @@
+//! Filters narrowing search results by item properties.
+
+use super::rustdoc::{ItemKind, matches_kind};
+use crate::types::CrateName;
+use rustdoc_types::{Item, ItemEnum};
+
+/// Item properties a search can be narrowed by. The default keeps every item.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ItemFilter {
+    /// Keep items of any of these kinds; empty keeps every kind.
+    pub kinds: Vec<ItemKind>,
+    /// Keep items under this module path, as `::`-separated segments.
+    pub path_prefix: Vec<String>,
+    pub deprecated: Option<bool>,
+    pub is_unsafe: Option<bool>,
+    pub is_async: Option<bool>,
+}
+
+impl ItemFilter {
+    /// Build a filter, splitting `path_prefix` into segments.
+    pub(crate) fn new(
+        kinds: &[ItemKind],
+        path_prefix: Option<&str>,
+        deprecated: Option<bool>,
+        is_unsafe: Option<bool>,
+        is_async: Option<bool>,
+    ) -> Self {
+        Self {
+            kinds: kinds.to_vec(),
+            path_prefix: path_prefix
+                .into_iter()
+                .flat_map(|prefix| prefix.split("::"))
+                .map(str::trim)
+                .filter(|segment| !segment.is_empty())
+                .map(str::to_string)
+                .collect(),
+            deprecated,
+            is_unsafe,
+            is_async,
+        }
+    }
+
+    /// Whether the filter keeps every item.
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+            && self.path_prefix.is_empty()
+            && self.deprecated.is_none()
+            && self.is_unsafe.is_none()
+            && self.is_async.is_none()
+    }
+
+    /// Whether `item`, found at `path` (crate name first), passes the filter.
+    pub(crate) fn matches<S: AsRef<str>>(&self, item: &Item, path: &[S]) -> bool {
+        (self.kinds.is_empty()
+            || self
+                .kinds
+                .iter()
+                .any(|&kind| matches_kind(&item.inner, kind)))
+            && self.matches_path(path)
+            && self
+                .deprecated
+                .is_none_or(|wanted| item.deprecation.is_some() == wanted)
+            && self
+                .is_unsafe
+                .is_none_or(|wanted| is_unsafe(&item.inner) == wanted)
+            && self
+                .is_async
+                .is_none_or(|wanted| is_async(&item.inner) == wanted)
+    }
+
+    /// Whether `path` lies under the prefix. The crate segment compares
+    /// normalized, so `serde-json` and `serde_json` are the same crate.
+    fn matches_path<S: AsRef<str>>(&self, path: &[S]) -> bool {
+        if path.len() < self.path_prefix.len() {
+            return false;
+        }
+        self.path_prefix
+            .iter()
+            .zip(path)
+            .enumerate()
+            .all(|(idx, (wanted, actual))| {
+                let actual = actual.as_ref();
+                if idx == 0 {
+                    CrateName::normalize(wanted) == CrateName::normalize(actual)
+                } else {
+                    wanted == actual
+                }
+            })
+    }
+}
+
+/// Unsafe functions and unsafe traits.
+const fn is_unsafe(inner: &ItemEnum) -> bool {
+    match inner {
+        ItemEnum::Function(function) => function.header.is_unsafe,
+        ItemEnum::Trait(trait_) => trait_.is_unsafe,
+        _ => false,
+    }
+}
+
+const fn is_async(inner: &ItemEnum) -> bool {
+    matches!(inner, ItemEnum::Function(function) if function.header.is_async)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(None, &["tokio", "sync", "Mutex"], true)]
+    #[case(Some("tokio::sync"), &["tokio", "sync", "Mutex"], true)]
+    #[case(Some("tokio::sync"), &["tokio", "sync"], true)]
+    #[case(Some("tokio::sync"), &["tokio", "net", "TcpStream"], false)]
+    #[case(Some("tokio::sync::mpsc"), &["tokio", "sync"], false)]
+    #[case(Some("serde-json"), &["serde_json", "Value"], true)]
+    fn matches_path_prefix(
+        #[case] prefix: Option<&str>,
+        #[case] path: &[&str],
+        #[case] kept: bool,
+    ) {
+        let filter = ItemFilter::new(&[], prefix, None, None, None);
+        check!(filter.matches_path(path) == kept);
+    }
+
+    #[test]
+    fn default_filter_is_empty() {
+        check!(ItemFilter::default().is_empty());
+        check!(!ItemFilter::new(&[ItemKind::Struct], None, None, None, None).is_empty());
+    }
+}
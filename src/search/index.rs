@@ -1,25 +1,37 @@
-//! TF-IDF inverted index implementation for full-text search.
+//! BM25F inverted index implementation for full-text search.
 
 // Query scoring multiplies usize match counts by f32 weights; precision loss
 // beyond the mantissa limit is irrelevant for ranking.
 #![allow(clippy::cast_precision_loss)]
 
+use crate::cache::SharedCache;
 use crate::item::ItemRef;
+use crate::memory::{MemoryCache, MemoryKey};
 use crate::types::CrateName;
 use postcard::{from_io, to_io};
+use rapidfuzz::distance::levenshtein;
 use rustdoc_types::Item;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::Path, time::SystemTime};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
 
-use super::tokenize::{TermBuilder, hash_term, tokenize_and_stem};
+use super::query::{QueryTerm, SearchQuery};
+use super::scoring::{Field, FieldCounts, FieldStats};
+use super::tokenize::{TermBuilder, Token, hash_term, tokenize};
 use rust_stemmers::{Algorithm, Stemmer};
 
-/// Process-wide counters for observing [`TermIndex`] cache behavior.
+/// Process-wide counters for observing [`TermIndex`] and documentation cache behavior.
 ///
 /// These atomics are incremented whenever the search index is loaded from
-/// disk or rebuilt from scratch. Tests can read deltas (snapshot before,
-/// snapshot after) to verify cache-reuse behavior without depending on
-/// wall-clock timing or log parsing.
+/// memory or disk or rebuilt from scratch, and as crate docs are served from memory,
+/// restored from the shared cache, or generated. Tests can read deltas
+/// (snapshot before, snapshot after) to verify cache-reuse behavior without
+/// depending on wall-clock timing or log parsing; the `cache` tool reports
+/// them through [`counters`](metrics::counters).
 ///
 /// # Concurrency
 ///
@@ -28,10 +40,36 @@ use rust_stemmers::{Algorithm, Stemmer};
 /// only that the expected operation occurred at least once — never that it
 /// occurred *exactly* N times.
 pub mod metrics {
+    use rmcp::schemars;
+    use serde::Serialize;
     use std::sync::atomic::{AtomicUsize, Ordering};
 
     static INDEX_BUILDS: AtomicUsize = AtomicUsize::new(0);
     static INDEX_LOADS: AtomicUsize = AtomicUsize::new(0);
+    static INDEX_MEMORY_LOADS: AtomicUsize = AtomicUsize::new(0);
+    static MEMORY_HITS: AtomicUsize = AtomicUsize::new(0);
+    static MEMORY_MISSES: AtomicUsize = AtomicUsize::new(0);
+    static SHARED_HITS: AtomicUsize = AtomicUsize::new(0);
+    static GENERATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Every counter at a point in time.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, schemars::JsonSchema)]
+    pub struct Counters {
+        /// Search indices built from rustdoc JSON.
+        pub index_builds: usize,
+        /// Search indices loaded from their on-disk cache.
+        pub index_loads: usize,
+        /// Search indices reused from the memory cache.
+        pub index_memory_loads: usize,
+        /// Docs requests answered from the in-memory cache.
+        pub memory_hits: usize,
+        /// Docs requests that had to load or generate the docs.
+        pub memory_misses: usize,
+        /// Docs restored from the shared cache instead of running rustdoc.
+        pub shared_hits: usize,
+        /// `cargo rustdoc` runs.
+        pub generations: usize,
+    }
 
     /// Snapshot of `(builds, loads)` counters at a point in time.
     #[must_use]
@@ -42,6 +80,20 @@ pub mod metrics {
         )
     }
 
+    /// Read every counter.
+    #[must_use]
+    pub fn counters() -> Counters {
+        Counters {
+            index_builds: INDEX_BUILDS.load(Ordering::Relaxed),
+            index_loads: INDEX_LOADS.load(Ordering::Relaxed),
+            index_memory_loads: INDEX_MEMORY_LOADS.load(Ordering::Relaxed),
+            memory_hits: MEMORY_HITS.load(Ordering::Relaxed),
+            memory_misses: MEMORY_MISSES.load(Ordering::Relaxed),
+            shared_hits: SHARED_HITS.load(Ordering::Relaxed),
+            generations: GENERATIONS.load(Ordering::Relaxed),
+        }
+    }
+
     pub(super) fn record_build() {
         INDEX_BUILDS.fetch_add(1, Ordering::Relaxed);
     }
@@ -49,59 +101,239 @@ pub mod metrics {
     pub(super) fn record_load() {
         INDEX_LOADS.fetch_add(1, Ordering::Relaxed);
     }
+
+    pub(super) fn record_memory_load() {
+        INDEX_MEMORY_LOADS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_memory_hit() {
+        MEMORY_HITS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_memory_miss() {
+        MEMORY_MISSES.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_shared_hit() {
+        SHARED_HITS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_generation() {
+        GENERATIONS.fetch_add(1, Ordering::Relaxed);
+    }
 }
 
 /// Term hash for fast lookup
 type TermHash = u64;
 
-/// A searchable term index with TF-IDF scoring.
+/// Version of the serialized [`InvertedIndex`] layout. Cached indices of any
+/// other version are rebuilt.
+const FORMAT_VERSION: u32 = 4;
+
+/// Query words shorter than this only match exactly.
+pub(super) const MIN_EXPANSION_LEN: usize = 3;
+
+/// Most indexed words a query word expands to by prefix.
+const MAX_PREFIX_EXPANSIONS: usize = 32;
+
+/// Score multiplier for terms reached by prefix expansion.
+const PREFIX_WEIGHT: f32 = 0.7;
+
+/// Score multiplier per edit for terms reached by typo-tolerant matching.
+const FUZZY_WEIGHT: f32 = 0.5;
+
+/// Edits a query word of `len` characters may be away from an indexed word.
+pub(super) const fn max_edits(len: usize) -> usize {
+    match len {
+        0..4 => 0,
+        4..8 => 1,
+        _ => 2,
+    }
+}
+
+/// Edits between the query word `query`, of `len` characters, and `word`, if
+/// at most `max_edits`.
+///
+/// Words whose length alone differs by more than `max_edits` are rejected
+/// without computing the distance.
+pub(super) fn fuzzy_distance(
+    query: &str,
+    len: usize,
+    word: &str,
+    max_edits: usize,
+) -> Option<usize> {
+    if word.chars().count().abs_diff(len) > max_edits {
+        return None;
+    }
+    let args = levenshtein::Args::default().score_cutoff(max_edits);
+    levenshtein::distance_with_args(query.chars(), word.chars(), &args)
+}
+
+/// The occurrences of a term in one document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Posting {
+    /// Index of the document in the index's document table
+    pub doc: usize,
+    /// Field and offset among the field's terms of each occurrence, sorted
+    pub positions: Vec<(u8, u16)>,
+}
+
+impl Posting {
+    /// Occurrences per field, counting only those in `fields`.
+    fn counts(&self, fields: &[Field]) -> FieldCounts {
+        let mut counts = FieldCounts::default();
+        for &field in fields {
+            counts[field as usize] = self
+                .positions
+                .iter()
+                .filter(|(f, _)| *f == field as u8)
+                .count()
+                .try_into()
+                .unwrap_or(u16::MAX);
+        }
+        counts
+    }
+}
+
+/// Fields `term` matches in.
+fn term_fields(term: &QueryTerm) -> &'static [Field] {
+    term.qualifier
+        .map_or(&Field::ALL, |qualifier| qualifier.fields())
+}
+
+/// A searchable term index with BM25F scoring.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct InvertedIndex {
-    /// Map from term hash to list of (crate_index, tf_idf_score) pairs, sorted by score descending
-    terms: HashMap<TermHash, Vec<(usize, f32)>>,
-    /// Map from crate_index to id_path (sequence of u32 IDs from root to item)
+    /// Layout version, serialized first so older indices are rejected on load
+    format: u32,
+    /// Map from term hash to the documents holding the term, sorted by doc_index
+    terms: HashMap<TermHash, Vec<Posting>>,
+    /// Map from doc_index to id_path (sequence of u32 IDs from root to item)
     ids: Vec<Vec<u32>>,
+    /// Map from doc_index to the number of terms in each field
+    lengths: Vec<FieldCounts>,
+    /// Field statistics over every document, for length normalization
+    stats: FieldStats,
+    /// Every indexed word, lowercased and unstemmed, with the hash of its stem.
+    /// Sorted by word, for prefix expansion and typo-tolerant matching.
+    words: Vec<(String, TermHash)>,
 }
 
 impl InvertedIndex {
-    /// Create a new InvertedIndex with the given terms and document IDs
+    /// Create a new InvertedIndex with the given terms, documents with their
+    /// field lengths, field statistics and sorted words
     pub(super) const fn new(
-        terms: HashMap<TermHash, Vec<(usize, f32)>>,
+        terms: HashMap<TermHash, Vec<Posting>>,
         ids: Vec<Vec<u32>>,
+        lengths: Vec<FieldCounts>,
+        stats: FieldStats,
+        words: Vec<(String, TermHash)>,
     ) -> Self {
-        Self { terms, ids }
+        Self {
+            format: FORMAT_VERSION,
+            terms,
+            ids,
+            lengths,
+            stats,
+            words,
+        }
     }
 
-    /// Searches for items matching the query term using TF-IDF scoring.
+    /// Searches for items matching the query using BM25F scoring.
+    /// A match in an item's name counts for more than one in its path,
+    /// signature or docs (see [`Field`]).
     /// Returns item ID paths sorted by relevance score (highest first).
     ///
-    /// The query is tokenized and stemmed just like indexed terms, so:
+    /// Query terms are tokenized and stemmed just like indexed terms, so:
     /// - "BackgroundWorker" matches items with "background", "worker", or "backgroundwork"
     /// - CamelCase, snake_case, and hyphen-case are all handled
-    pub(crate) fn search(&self, query: &str, limit: usize) -> Vec<(Vec<u32>, f32)> {
+    ///
+    /// A query word whose stem isn't indexed falls back to the indexed words it
+    /// is a prefix of ("Deser" → "deserialize") or is a few typos away from
+    /// ("HashMapp" → "hashmap"), at a discount (see [`Self::expand`]).
+    ///
+    /// Phrases only match documents holding their words next to each other,
+    /// and excluded terms drop the documents holding them. Both match exact
+    /// stems only. Qualified terms only match in their qualifier's fields.
+    ///
+    /// Only documents whose ID path passes `keep` are returned. They are
+    /// filtered before `limit` applies, so a selective filter still fills the
+    /// result set.
+    pub(crate) fn search(
+        &self,
+        query: &SearchQuery,
+        limit: usize,
+        mut keep: impl FnMut(&[u32]) -> bool,
+    ) -> Vec<(Vec<u32>, f32)> {
         let stemmer = Stemmer::create(Algorithm::English);
-        let tokens = tokenize_and_stem(query, &stemmer);
-
-        if tokens.is_empty() {
-            return vec![];
-        }
 
         // Collect results from all tokens, combining scores for documents that match multiple.
         // Also track how many distinct tokens each document matched.
         let mut combined_scores: HashMap<usize, f32> = HashMap::new();
         let mut token_match_counts: HashMap<usize, usize> = HashMap::new();
+        let mut total_tokens = 0;
+        // Documents holding every phrase, once the query has one
+        let mut phrase_matches: Option<HashSet<usize>> = None;
+
+        for term in &query.terms {
+            let tokens = tokenize(&term.text, &stemmer);
+            let fields = term_fields(term);
+            total_tokens += tokens.len();
+
+            if term.is_phrase
+                && let Some(docs) = self.phrase_docs(&tokens, fields)
+            {
+                phrase_matches = Some(match phrase_matches {
+                    Some(matches) => matches.intersection(&docs).copied().collect(),
+                    None => docs,
+                });
+            }
 
-        for token in &tokens {
-            let term_hash = hash_term(token);
-            if let Some(results) = self.terms.get(&term_hash) {
-                for (doc_idx, score) in results {
-                    *combined_scores.entry(*doc_idx).or_insert(0.0) += score;
-                    *token_match_counts.entry(*doc_idx).or_insert(0) += 1;
+            for token in &tokens {
+                // A document reached through several expansions of one token
+                // counts once, with its best score.
+                let mut token_scores: HashMap<usize, f32> = HashMap::new();
+                let expansions = if term.is_phrase {
+                    vec![(hash_term(&token.stem), 1.0)]
+                } else {
+                    self.expand(token)
+                };
+                for (term_hash, weight) in expansions {
+                    let Some(postings) = self.terms.get(&term_hash) else {
+                        continue;
+                    };
+                    let idf = self.stats.idf(postings.len());
+                    for posting in postings {
+                        let counts = posting.counts(fields);
+                        if counts.iter().all(|&count| count == 0) {
+                            continue;
+                        }
+                        let score = self.stats.score(idf, &counts, &self.lengths[posting.doc]);
+                        let weighted = score * weight;
+                        token_scores
+                            .entry(posting.doc)
+                            .and_modify(|best| *best = best.max(weighted))
+                            .or_insert(weighted);
+                    }
+                }
+                for (doc_idx, score) in token_scores {
+                    *combined_scores.entry(doc_idx).or_insert(0.0) += score;
+                    *token_match_counts.entry(doc_idx).or_insert(0) += 1;
                 }
             }
         }
 
-        // Apply a quadratic coverage penalty only for multi-word (space-separated) queries.
+        if let Some(matches) = phrase_matches {
+            combined_scores.retain(|doc_idx, _| matches.contains(doc_idx));
+        }
+        for term in &query.excluded {
+            let tokens = tokenize(&term.text, &stemmer);
+            if let Some(docs) = self.phrase_docs(&tokens, term_fields(term)) {
+                combined_scores.retain(|doc_idx, _| !docs.contains(doc_idx));
+            }
+        }
+
+        // Apply a quadratic coverage penalty only for multi-term queries.
         // When the user types "cache invalidation", items that only match "invalid" (not
         // "cache") should rank below items matching both words. The penalty is (matched/total)^2,
         // so a document matching 1 of 2 words gets a 0.25× multiplier.
@@ -110,8 +342,8 @@ impl InvertedIndex {
         // path queries like "serde::Serialize" — those produce multiple tokens via CamelCase
         // splitting, but every token comes from the same user-supplied name, so partial matches
         // are still meaningful and penalizing them would cause real items to drop out of results.
-        let total_tokens = tokens.len() as f32;
-        if query.contains(' ') && total_tokens > 1.0 {
+        let total_tokens = total_tokens as f32;
+        if query.terms.len() > 1 && total_tokens > 1.0 {
             for (doc_idx, score) in &mut combined_scores {
                 let matched = token_match_counts.get(doc_idx).copied().unwrap_or(0) as f32;
                 let coverage = matched / total_tokens;
@@ -125,11 +357,99 @@ impl InvertedIndex {
 
         results
             .into_iter()
+            .filter(|(doc_idx, _)| keep(&self.ids[*doc_idx]))
             .take(limit)
             .map(|(doc_idx, score)| (self.ids[doc_idx].clone(), score))
             .collect()
     }
 
+    /// Documents in which `tokens` occur next to each other and in order,
+    /// within one of `fields`. `None` if there are no tokens to look for.
+    fn phrase_docs(&self, tokens: &[Token], fields: &[Field]) -> Option<HashSet<usize>> {
+        let (first, rest) = tokens.split_first()?;
+        let postings = |token: &Token| {
+            self.terms
+                .get(&hash_term(&token.stem))
+                .map_or(&[][..], Vec::as_slice)
+        };
+        let rest: Vec<&[Posting]> = rest.iter().map(postings).collect();
+
+        let mut docs = HashSet::new();
+        for posting in postings(first) {
+            // The following tokens' occurrences in this document
+            let following: Option<Vec<&Posting>> = rest
+                .iter()
+                .map(|postings| {
+                    postings
+                        .binary_search_by_key(&posting.doc, |p| p.doc)
+                        .ok()
+                        .map(|idx| &postings[idx])
+                })
+                .collect();
+            let Some(following) = following else {
+                continue;
+            };
+            let found = posting.positions.iter().any(|&(field, offset)| {
+                fields.iter().any(|&f| f as u8 == field)
+                    && following.iter().zip(1..).all(|(next, distance)| {
+                        offset.checked_add(distance).is_some_and(|offset| {
+                            next.positions.binary_search(&(field, offset)).is_ok()
+                        })
+                    })
+            });
+            if found {
+                docs.insert(posting.doc);
+            }
+        }
+        Some(docs)
+    }
+
+    /// Terms a query token matches, with the weight their scores count at.
+    ///
+    /// An indexed stem matches exactly at full weight. Otherwise a word of at
+    /// least [`MIN_EXPANSION_LEN`] characters expands to the indexed words it
+    /// is a prefix of, weighted by [`PREFIX_WEIGHT`], and to those within
+    /// [`max_edits`] edits of it, weighted by [`FUZZY_WEIGHT`] per edit.
+    fn expand(&self, token: &Token) -> Vec<(TermHash, f32)> {
+        let exact = hash_term(&token.stem);
+        let len = token.word.chars().count();
+        if self.terms.contains_key(&exact) || len < MIN_EXPANSION_LEN {
+            return vec![(exact, 1.0)];
+        }
+
+        let mut expansions: HashMap<TermHash, f32> = HashMap::new();
+        let mut add = |term_hash: TermHash, weight: f32| {
+            expansions
+                .entry(term_hash)
+                .and_modify(|best| *best = best.max(weight))
+                .or_insert(weight);
+        };
+
+        let start = self
+            .words
+            .partition_point(|(word, _)| word.as_str() < token.word.as_str());
+        for (_, term_hash) in self.words[start..]
+            .iter()
+            .take_while(|(word, _)| word.starts_with(&token.word))
+            .take(MAX_PREFIX_EXPANSIONS)
+        {
+            add(*term_hash, PREFIX_WEIGHT);
+        }
+
+        let max_edits = max_edits(len);
+        if max_edits > 0 {
+            for (word, term_hash) in &self.words {
+                if let Some(edits) = fuzzy_distance(&token.word, len, word, max_edits)
+                    && edits > 0
+                {
+                    add(*term_hash, FUZZY_WEIGHT.powf(edits as f32));
+                }
+            }
+        }
+
+        expansions.into_iter().collect()
+    }
+
     /// Get the number of unique terms in the index
     pub(crate) fn term_count(&self) -> usize {
         self.terms.len()
@@ -139,6 +459,35 @@ impl InvertedIndex {
     pub(crate) const fn document_count(&self) -> usize {
         self.ids.len()
     }
+
+    /// Approximate memory held by the term and document tables.
+    fn memory_bytes(&self) -> usize {
+        let postings: usize = self
+            .terms
+            .values()
+            .map(|postings| {
+                size_of::<(TermHash, Vec<Posting>)>()
+                    + postings
+                        .iter()
+                        .map(|posting| {
+                            size_of::<Posting>() + posting.positions.len() * size_of::<(u8, u16)>()
+                        })
+                        .sum::<usize>()
+            })
+            .sum();
+        let ids: usize = self
+            .ids
+            .iter()
+            .map(|path| size_of::<Vec<u32>>() + path.len() * size_of::<u32>())
+            .sum::<usize>()
+            + self.lengths.len() * size_of::<FieldCounts>();
+        let words: usize = self
+            .words
+            .iter()
+            .map(|(word, _)| size_of::<(String, TermHash)>() + word.len())
+            .sum();
+        postings + ids + words
+    }
 }
 
 /// Location information for a documentation item.
@@ -168,30 +517,95 @@ pub(crate) struct DetailedSearchResult {
     pub source_crate: Option<CrateName>,
 }
 
+/// Where a crate's search index is built from and cached.
+struct IndexSource {
+    crate_name: CrateName,
+    doc_path: PathBuf,
+    index_path: PathBuf,
+    /// Digest of the crate's docs, which locates them in the shared cache.
+    digest_path: Option<PathBuf>,
+    /// Memory cache keeping the index between queries, and the crate's key in it.
+    memory: Option<(Arc<MemoryCache>, MemoryKey)>,
+}
+
+impl IndexSource {
+    /// Modification time of the rustdoc JSON the index is built from.
+    async fn doc_mtime(&self) -> Option<SystemTime> {
+        tokio::fs::metadata(&self.doc_path)
+            .await
+            .ok()
+            .and_then(|m| m.modified().ok())
+    }
+
+    /// Find an index built from the current docs, in memory or on disk.
+    async fn cached(&self, mtime: Option<SystemTime>) -> Option<Arc<TermIndex>> {
+        if let Some((memory, key)) = &self.memory
+            && let Some(index) = memory.search_index(key, mtime)
+        {
+            metrics::record_memory_load();
+            tracing::debug!(crate_name = %self.crate_name, "Using search index held in memory");
+            return Some(index);
+        }
+
+        let terms = TermIndex::load(&self.index_path, mtime).await?;
+        metrics::record_load();
+        tracing::debug!(
+            crate_name = %self.crate_name,
+            terms = terms.terms.len(),
+            docs = terms.ids.len(),
+            "Loaded cached search index"
+        );
+        Some(self.keep(terms, mtime))
+    }
+
+    /// Persist a freshly built index to disk and the shared cache.
+    async fn store(&self, terms: InvertedIndex, mtime: Option<SystemTime>) -> Arc<TermIndex> {
+        metrics::record_build();
+        TermIndex::store(&terms, &self.index_path).await;
+        if let Some(digest_path) = &self.digest_path
+            && let Some(digest) = crate::cache::load_digest(digest_path).await
+            && let Some(entry) = SharedCache::global().entry(self.crate_name.as_str(), &digest)
+        {
+            entry.store_index(&self.index_path).await;
+        }
+        self.keep(terms, mtime)
+    }
+
+    /// Wrap `terms` as this crate's index and keep it in memory.
+    fn keep(&self, terms: InvertedIndex, mtime: Option<SystemTime>) -> Arc<TermIndex> {
+        let index = Arc::new(TermIndex::new(self.crate_name.clone(), terms));
+        if let Some((memory, key)) = &self.memory {
+            memory.insert_search_index(key.clone(), index.clone(), mtime);
+        }
+        index
+    }
+}
+
 /// A search index for a specific crate.
 pub(crate) struct TermIndex {
     crate_name: CrateName,
     terms: InvertedIndex,
+    memory_bytes: usize,
 }
 
 impl TermIndex {
-    /// Prepares index data synchronously, resolving crate and building the index.
-    /// Returns data needed for async cache operations.
+    fn new(crate_name: CrateName, terms: InvertedIndex) -> Self {
+        let memory_bytes = terms.memory_bytes();
+        Self {
+            crate_name,
+            terms,
+            memory_bytes,
+        }
+    }
+
+    /// Resolves the crate and where its index lives.
     ///
-    /// This is split from `load_or_build_async` to avoid holding non-Send QueryContext
-    /// references in async functions.
-    fn prepare_index<'a>(
+    /// This is split from the async cache steps to avoid holding non-Send
+    /// QueryContext references in async functions.
+    fn resolve<'a>(
         request: &'a super::query::QueryContext,
         crate_name: &str,
-    ) -> Result<
-        (
-            CrateName,
-            std::path::PathBuf,
-            std::path::PathBuf,
-            InvertedIndex,
-        ),
-        Vec<super::query::PathSuggestion<'a>>,
-    > {
+    ) -> Result<(ItemRef<'a, Item>, IndexSource), Vec<super::query::PathSuggestion<'a>>> {
         let mut suggestions = vec![];
 
         // Use QueryContext::resolve_path for crate validation
@@ -199,84 +613,80 @@ impl TermIndex {
             .resolve_path(crate_name, &mut suggestions)
             .ok_or(suggestions)?;
 
-        let crate_index = item.crate_index();
-        let crate_name = CrateName::new_unchecked(crate_index.name());
+        // Crates present in several versions keep their `name@version` so results
+        // load back from the right docs.
+        let canonical = request.workspace().canonical_crate_name(crate_name);
+        let crate_name = if canonical.contains('@') {
+            CrateName::new_unchecked(canonical)
+        } else {
+            CrateName::new_unchecked(item.crate_index().name())
+        };
 
         // Get paths for docs and index. Preloaded crates (e.g., stdlib) supply
         // their own source and cache paths via QueryContext, so the source can
         // live in a read-only sysroot while the cache goes to a writable dir.
-        let doc_path = request.doc_source_path(crate_name.as_str());
-        let index_path = request.index_cache_path(crate_name.as_str());
-
-        // Build index synchronously
-        let start = std::time::Instant::now();
-        tracing::info!(crate_name = %crate_name, "Building search index");
-        let terms = build_index(item);
-        tracing::debug!(crate_name = %crate_name, elapsed = ?start.elapsed(), "Index build completed");
-
-        Ok((crate_name, doc_path, index_path, terms))
-    }
-
-    /// Async portion: checks cache and stores/returns the index.
-    async fn load_or_build_async(
-        crate_name: CrateName,
-        doc_path: std::path::PathBuf,
-        index_path: std::path::PathBuf,
-        prepared_terms: InvertedIndex,
-    ) -> Self {
-        // Get mtime of rustdoc JSON
-        let mtime = tokio::fs::metadata(&doc_path)
-            .await
-            .ok()
-            .and_then(|m| m.modified().ok());
-
-        // Try loading cached index
-        if let Some(terms) = Self::load(&index_path, mtime).await {
-            metrics::record_load();
-            tracing::debug!(
-                crate_name = %crate_name,
-                terms = terms.terms.len(),
-                docs = terms.ids.len(),
-                "Loaded cached search index"
-            );
-            return Self { crate_name, terms };
-        }
-
-        // Cache miss — use the freshly built index and persist it.
-        metrics::record_build();
-        Self::store(&prepared_terms, &index_path).await;
-        Self {
-            terms: prepared_terms,
+        let source = IndexSource {
+            doc_path: request.doc_source_path(crate_name.as_str()),
+            index_path: request.index_cache_path(crate_name.as_str()),
+            digest_path: request.digest_path(crate_name.as_str()),
+            memory: request
+                .memory()
+                .map(|memory| (memory.clone(), request.memory_key(crate_name.as_str()))),
             crate_name,
-        }
+        };
+        Ok((item, source))
     }
 
-    /// Loads a cached search index or builds a new one if cache is stale.
-    /// Cache is invalidated when rustdoc JSON is newer than the index file.
+    /// Loads the search index from memory or its on-disk cache, building it
+    /// only if neither holds one for the current docs.
+    /// The disk cache is invalidated when rustdoc JSON is newer than the index file.
     ///
     /// This is a blocking wrapper around async cache operations to avoid
     /// Send/Sync issues with QueryContext in async functions.
     pub(crate) fn load_or_build<'a>(
         request: &'a super::query::QueryContext,
         crate_name: &str,
-    ) -> Result<Self, Vec<super::query::PathSuggestion<'a>>> {
-        // Synchronous: resolve crate and build index
-        let (crate_name, doc_path, index_path, terms) = Self::prepare_index(request, crate_name)?;
+    ) -> Result<Arc<Self>, Vec<super::query::PathSuggestion<'a>>> {
+        let (item, source) = Self::resolve(request, crate_name)?;
 
-        // Use tokio::task::block_in_place to allow blocking within an async runtime
-        // This works whether called from sync or async context
-        Ok(tokio::task::block_in_place(|| {
-            let handle = tokio::runtime::Handle::current();
-            handle.block_on(Self::load_or_build_async(
-                crate_name, doc_path, index_path, terms,
-            ))
-        }))
+        let mtime = block_on(source.doc_mtime());
+        if let Some(index) = block_on(source.cached(mtime)) {
+            return Ok(index);
+        }
+
+        // Cache miss: build synchronously, then persist
+        let start = std::time::Instant::now();
+        tracing::info!(crate_name = %source.crate_name, "Building search index");
+        crate::progress::report(&crate::progress::Progress::Indexing {
+            crate_name: source.crate_name.to_string(),
+        });
+        let terms = build_index(item);
+        tracing::debug!(crate_name = %source.crate_name, elapsed = ?start.elapsed(), "Index build completed");
+
+        Ok(block_on(source.store(terms, mtime)))
+    }
+
+    /// Approximate memory held by this index.
+    pub(crate) const fn memory_bytes(&self) -> usize {
+        self.memory_bytes
     }
 
-    /// Searches within this index and returns matches with location and rank.
+    /// Searches within this index for `query` taken literally (see
+    /// [`SearchQuery::plain`]) and returns matches with location and rank.
     pub(crate) fn search(&self, query: &str, limit: usize) -> Vec<SearchMatch> {
+        self.search_where(&SearchQuery::plain(query), limit, |_| true)
+    }
+
+    /// Like [`Self::search`], keeping only items whose ID path passes `keep`
+    /// (see [`InvertedIndex::search`]).
+    pub(crate) fn search_where(
+        &self,
+        query: &SearchQuery,
+        limit: usize,
+        keep: impl FnMut(&[u32]) -> bool,
+    ) -> Vec<SearchMatch> {
         self.terms
-            .search(query, limit)
+            .search(query, limit, keep)
             .into_iter()
             .map(|(item_path, rank)| SearchMatch {
                 item: ItemLocation {
@@ -288,6 +698,25 @@ impl TermIndex {
             .collect()
     }
 
+    /// Like [`Self::search_where`], with ranks divided by the logarithm of the crate's
+    /// item count so ranks from crates of different sizes can be merged.
+    ///
+    /// A term's IDF grows with the number of items in the crate, which would
+    /// otherwise let a large crate outrank a small one on the same match.
+    pub(crate) fn search_normalized(
+        &self,
+        query: &SearchQuery,
+        limit: usize,
+        keep: impl FnMut(&[u32]) -> bool,
+    ) -> Vec<SearchMatch> {
+        let scale = (self.terms.document_count() as f32).ln_1p().max(1.0);
+        let mut matches = self.search_where(query, limit, keep);
+        for m in &mut matches {
+            m.rank /= scale;
+        }
+        matches
+    }
+
     /// Load a cached index from disk.
     async fn load(path: &Path, mtime: Option<SystemTime>) -> Option<InvertedIndex> {
         let file = tokio::fs::File::open(path).await.ok()?;
@@ -301,11 +730,15 @@ impl TermIndex {
             tokio::task::spawn_blocking(move || {
                 let mut file = std::fs::File::open(&path).ok()?;
                 let mut buf = [0u8; 8192];
-                if let Ok((terms, _)) = from_io((&mut file, &mut buf)) {
+                if let Ok((terms, _)) = from_io::<InvertedIndex, _>((&mut file, &mut buf))
+                    && terms.format == FORMAT_VERSION
+                {
                     tracing::debug!(path = %path.display(), "Using cached index (newer than source)");
                     return Some(terms);
                 }
-                tracing::warn!(path = %path.display(), "Failed to deserialize cached index");
+                // Remove it so the rebuilt index can take its place
+                tracing::warn!(path = %path.display(), "Cached index is unreadable or outdated, will rebuild");
+                let _ = std::fs::remove_file(&path);
                 None
             })
             .await
@@ -366,37 +799,81 @@ impl TermIndex {
     }
 }
 
+/// Run `future` to completion from synchronous code on a runtime thread.
+///
+/// `block_in_place` allows blocking within an async runtime, so this works
+/// whether called from sync or async context.
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(future))
+}
+
 /// Builds an inverted index from a crate's documentation tree.
 fn build_index(root_item: ItemRef<'_, Item>) -> InvertedIndex {
     let mut builder = TermBuilder::default();
-    builder.recurse(root_item, &[], false);
+    builder.recurse(root_item, &[], &[], false);
     builder.finalize()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::search::query::parse_search_query;
+    use crate::search::scoring::Field;
+    use crate::search::tokenize::tokenize_and_stem;
     use assert2::check;
 
-    /// Build a minimal InvertedIndex directly from (token, doc_idx, score) triples.
+    /// Build a minimal InvertedIndex directly from (word, doc_idx, field) triples,
+    /// each one occurrence of the word in that field of the document, following
+    /// the previous word given for that field and document.
     /// Useful for testing scoring behavior without a real crate loaded.
-    fn make_index(entries: Vec<(&str, usize, f32)>, doc_count: usize) -> InvertedIndex {
-        let mut terms: HashMap<TermHash, Vec<(usize, f32)>> = HashMap::new();
-        for (token, doc_idx, score) in entries {
+    fn make_index(entries: Vec<(&str, usize, Field)>, doc_count: usize) -> InvertedIndex {
+        let mut positions: HashMap<(TermHash, usize), Vec<(u8, u16)>> = HashMap::new();
+        let mut lengths = vec![FieldCounts::default(); doc_count];
+        let mut words = Vec::new();
+        for (word, doc_idx, field) in entries {
+            let term_hash = hash_term(&stem(word));
+            let offset = lengths[doc_idx][field as usize];
+            positions
+                .entry((term_hash, doc_idx))
+                .or_default()
+                .push((field as u8, offset));
+            lengths[doc_idx][field as usize] += 1;
+            words.push((word.to_lowercase(), term_hash));
+        }
+        words.sort();
+        words.dedup();
+
+        let mut terms: HashMap<TermHash, Vec<Posting>> = HashMap::new();
+        for ((term_hash, doc), mut positions) in positions {
+            positions.sort_unstable();
             terms
-                .entry(hash_term(token))
+                .entry(term_hash)
                 .or_default()
-                .push((doc_idx, score));
+                .push(Posting { doc, positions });
+        }
+        for postings in terms.values_mut() {
+            postings.sort_unstable_by_key(|posting| posting.doc);
         }
-        // Sort each bucket by score descending (as the real index does)
-        for bucket in terms.values_mut() {
-            bucket.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        let mut stats = FieldStats::default();
+        for doc_lengths in &lengths {
+            stats.add(doc_lengths);
         }
         // IDs: each doc gets a singleton path [doc_idx as u32]
         let ids: Vec<Vec<u32>> = (0..doc_count)
             .map(|i| vec![u32::try_from(i).expect("test doc_count fits in u32")])
             .collect();
-        InvertedIndex::new(terms, ids)
+        InvertedIndex::new(terms, ids, lengths, stats, words)
+    }
+
+    /// Run `query`, in the query language, against `index` without a filter.
+    fn search(index: &InvertedIndex, query: &str) -> Vec<(Vec<u32>, f32)> {
+        let query = parse_search_query(query).expect("test query parses");
+        index.search(&query, 10, |_| true)
+    }
+
+    /// Document indices of `results`, in rank order.
+    fn docs(results: &[(Vec<u32>, f32)]) -> Vec<u32> {
+        results.iter().map(|(path, _)| path[0]).collect()
     }
 
     /// Stem a single word using the same stemmer the index uses.
@@ -410,28 +887,25 @@ mod tests {
 
     /// When a multi-word query is issued, a document matching ALL query tokens should
     /// rank above one that matches only a subset, even if the partial-match document has
-    /// a higher raw TF-IDF score for its single matching token.
+    /// a higher raw score for its single matching token.
     ///
     /// Without a coverage penalty, "cache invalidation" can surface items named
     /// "InvalidCharacter" (which match only the "invalid" stem with a high score) above
     /// the actual cache module (which matches both "cach" and "invalid" with lower scores).
     #[test]
     fn full_match_ranks_above_partial_match() {
-        let cach = stem("cache");
-        let invalid = stem("invalidation");
-
-        // Doc 0: "cache_invalidation" — matches both stems (full match, low raw scores)
-        // Doc 1: "invalid_char"       — matches only "invalid" with a much higher raw score
+        // Doc 0: docs mention "cache invalidation" — matches both stems in a low-weight field
+        // Doc 1: named "invalid"                — matches only "invalid", in its name
         let index = make_index(
             vec![
-                (&cach, 0, 0.5),    // doc 0 contributes "cach"
-                (&invalid, 0, 0.5), // doc 0 contributes "invalid"
-                (&invalid, 1, 2.0), // doc 1 contributes "invalid" with 4x the score
+                ("cache", 0, Field::Body),
+                ("invalidation", 0, Field::Body),
+                ("invalid", 1, Field::Name),
             ],
             2,
         );
 
-        let results = index.search("cache invalidation", 10);
+        let results = search(&index, "cache invalidation");
         check!(!results.is_empty(), "Should return at least one result");
         check!(
             results[0].0 == vec![0u32],
@@ -440,4 +914,176 @@ mod tests {
             results[0].0
         );
     }
+
+    /// A query word that is the start of an indexed word matches it, below an
+    /// exact match.
+    #[test]
+    fn prefix_matches_rank_below_exact_matches() {
+        let index = make_index(
+            vec![
+                ("deserialize", 0, Field::Name),
+                ("serialize", 1, Field::Name),
+            ],
+            2,
+        );
+
+        let results = search(&index, "deser");
+        check!(results.len() == 1);
+        check!(results[0].0 == vec![0u32]);
+        check!(results[0].1 < search(&index, "deserialize")[0].1);
+    }
+
+    /// A misspelled query word matches indexed words within its edit budget.
+    #[test]
+    fn typos_match_within_edit_distance() {
+        let index = make_index(
+            vec![("hashmap", 0, Field::Name), ("hashset", 1, Field::Name)],
+            2,
+        );
+
+        let results = search(&index, "hashmapp");
+        check!(results.len() == 1);
+        check!(results[0].0 == vec![0u32]);
+        check!(results[0].1 < search(&index, "hashmap")[0].1);
+    }
+
+    /// Edit distances count characters, and words too long or short to be in
+    /// budget are rejected up front.
+    #[test]
+    fn fuzzy_distance_bounds_by_length() {
+        check!(fuzzy_distance("hashmapp", 8, "hashmap", 2) == Some(1));
+        check!(fuzzy_distance("hashmapp", 8, "hash", 2).is_none());
+        check!(fuzzy_distance("hashmapp", 8, "hashmapping", 2).is_none());
+        check!(fuzzy_distance("résumé", 6, "resume", 2) == Some(2));
+    }
+
+    /// Short query words only match exactly.
+    #[test]
+    fn short_words_do_not_expand() {
+        let index = make_index(vec![("map", 0, Field::Name)], 1);
+        check!(search(&index, "mp").is_empty());
+        check!(search(&index, "mab").is_empty());
+    }
+
+    /// A term in an item's name outweighs the same term in its docs.
+    #[test]
+    fn name_matches_outrank_doc_matches() {
+        let index = make_index(
+            vec![
+                ("parser", 0, Field::Body),
+                ("parser", 1, Field::Name),
+                ("token", 2, Field::Name),
+            ],
+            3,
+        );
+
+        let results = search(&index, "parser");
+        check!(results.len() == 2);
+        check!(results[0].0 == vec![1u32]);
+    }
+
+    /// Repeating a term saturates instead of growing the score without bound,
+    /// and a long doc dilutes a match more than a short one.
+    #[test]
+    fn repeated_and_diluted_terms() {
+        let mut entries = vec![("cache", 0, Field::Body)];
+        entries.extend(std::iter::repeat_n(("cache", 1, Field::Body), 20));
+        entries.push(("cache", 2, Field::Body));
+        entries.extend(std::iter::repeat_n(("filler", 2, Field::Body), 20));
+        entries.push(("other", 3, Field::Body));
+        let index = make_index(entries, 4);
+
+        let results = search(&index, "cache");
+        let score = |doc: u32| {
+            results
+                .iter()
+                .find(|(path, _)| path == &[doc])
+                .map(|(_, score)| *score)
+                .expect("document matched")
+        };
+        check!(score(1) > score(0));
+        check!(score(1) < score(0) * 3.0);
+        check!(score(2) < score(0));
+    }
+
+    /// A phrase only matches its words next to each other and in order.
+    #[test]
+    fn phrases_match_adjacent_words_in_order() {
+        let index = make_index(
+            vec![
+                ("connection", 0, Field::Summary),
+                ("pool", 0, Field::Summary),
+                ("pool", 1, Field::Summary),
+                ("of", 1, Field::Summary),
+                ("connection", 1, Field::Summary),
+                ("connection", 2, Field::Summary),
+                ("limit", 2, Field::Summary),
+                ("pool", 2, Field::Summary),
+                ("connection", 3, Field::Name),
+                ("pool", 3, Field::Summary),
+            ],
+            4,
+        );
+
+        check!(docs(&search(&index, "connection pool")).len() == 4);
+        check!(docs(&search(&index, "\"connection pool\"")) == vec![0]);
+        check!(docs(&search(&index, "\"pool connection\"")).is_empty());
+    }
+
+    /// Excluded words and phrases drop the documents holding them.
+    #[test]
+    fn exclusions_drop_matching_documents() {
+        let index = make_index(
+            vec![
+                ("cache", 0, Field::Name),
+                ("cache", 1, Field::Name),
+                ("deprecated", 1, Field::Body),
+                ("cache", 2, Field::Name),
+                ("disk", 2, Field::Summary),
+                ("cache", 2, Field::Summary),
+            ],
+            3,
+        );
+
+        let mut kept = docs(&search(&index, "cache -deprecated"));
+        kept.sort_unstable();
+        check!(kept == vec![0, 2]);
+        check!(docs(&search(&index, "cache -\"disk cache\"")).len() == 2);
+        check!(!docs(&search(&index, "cache -\"disk cache\"")).contains(&2));
+    }
+
+    /// A qualified term only matches in its qualifier's fields.
+    #[test]
+    fn qualifiers_restrict_fields() {
+        let index = make_index(
+            vec![
+                ("pool", 0, Field::Name),
+                ("pool", 1, Field::Summary),
+                ("pool", 2, Field::Path),
+            ],
+            3,
+        );
+
+        check!(docs(&search(&index, "name:pool")) == vec![0]);
+        check!(docs(&search(&index, "doc:pool")) == vec![1]);
+        check!(docs(&search(&index, "path:pool")) == vec![2]);
+        check!(docs(&search(&index, "pool")).len() == 3);
+    }
+
+    /// A cached index in an older layout is discarded so it gets rebuilt.
+    #[tokio::test]
+    async fn outdated_cached_index_is_discarded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("crate.index");
+        let mut index = make_index(vec![("cache", 0, Field::Name)], 1);
+        index.format = FORMAT_VERSION - 1;
+        to_io(&index, std::fs::File::create(&path).unwrap()).unwrap();
+
+        check!(
+            TermIndex::load(&path, Some(SystemTime::UNIX_EPOCH))
+                .await
+                .is_none()
+        );
+        check!(!path.exists());
+    }
 }
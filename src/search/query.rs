@@ -1,21 +1,24 @@
 //! QueryContext provides request-scoped context for documentation queries with automatic caching.
-//! Also includes path parsing utilities for resolving item queries.
+//! Also includes path parsing utilities for resolving item queries, and the
+//! parser for text search queries.
 
-use crate::error::LoadError;
+use crate::error::{LoadError, QueryError};
 use crate::item::ItemRef;
-use crate::search::rustdoc::CrateIndex;
+use crate::memory::{CachedDocs, MemoryCache, MemoryKey};
+use crate::search::index_metrics;
+use crate::search::rustdoc::{CrateIndex, ItemKind};
+use crate::search::scoring::Field;
 use crate::types::CrateName;
-use crate::workspace::WorkspaceContext;
-use bumpalo::Bump;
+use crate::worker::DocState;
+use crate::workspace::{WorkspaceContext, feature_doc_name, features_override};
+use elsa::FrozenMap;
 use rapidfuzz::distance::jaro_winkler;
 use rustdoc_types::{Id, Item, ItemEnum};
 use std::{
     cell::RefCell,
     collections::HashMap,
     fmt::{self, Debug, Formatter},
-    marker::PhantomData,
     path::{Path, PathBuf},
-    ptr::NonNull,
     sync::Arc,
 };
 
@@ -120,54 +123,232 @@ pub(crate) fn resolve_crate_from_path(
     }
 }
 
-/// A Send-safe wrapper around a raw pointer to bump-allocated data.
+/// A parsed text search query.
 ///
-/// SAFETY INVARIANT: The pointer must remain valid for the lifetime of the QueryContext.
-/// We enforce this by:
-/// 1. Only creating ArenaPtr from arena allocations within QueryContext
-/// 2. Never exposing ArenaPtr outside QueryContext's lifetime
-/// 3. QueryContext is not Clone, preventing arena from being freed while refs exist
-struct ArenaPtr<T> {
-    ptr: NonNull<T>,
-    // Ensure we're not Send/Sync unless T is
-    _marker: PhantomData<*const T>,
+/// Built by [`parse_search_query`] from the query language, or by
+/// [`SearchQuery::plain`] from text taken literally.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SearchQuery {
+    /// Words and phrases results are ranked by.
+    pub terms: Vec<QueryTerm>,
+    /// Words and phrases results must not contain.
+    pub excluded: Vec<QueryTerm>,
+    /// Kinds named by `kind:` qualifiers; results must be one of them.
+    pub kinds: Vec<ItemKind>,
 }
 
-// SAFETY: ArenaPtr can be Send because:
-// 1. The arena allocation lives as long as QueryContext
-// 2. QueryContext is not Clone - when it drops, all ArenaPtr become invalid
-// 3. We only dereference through &QueryContext methods, ensuring lifetime bounds
-unsafe impl<T> Send for ArenaPtr<T> where T: Send {}
+/// A word or quoted phrase of a [`SearchQuery`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QueryTerm {
+    pub text: String,
+    /// Whether `text` is a phrase, whose words must appear next to each other
+    /// and in order.
+    pub is_phrase: bool,
+    /// Part of an item the term must match in; `None` matches anywhere.
+    pub qualifier: Option<Qualifier>,
+}
+
+/// A field qualifier such as `name:`, restricting a term to part of an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Qualifier {
+    Name,
+    Doc,
+    Path,
+}
 
-impl<T> ArenaPtr<T> {
-    /// Create a new ArenaPtr from a reference.
-    /// SAFETY: The reference must remain valid for the lifetime of the containing QueryContext.
-    fn new(reference: &T) -> Self {
+impl Qualifier {
+    /// Index fields the qualifier matches in.
+    pub(crate) const fn fields(self) -> &'static [Field] {
+        match self {
+            Self::Name => &[Field::Name],
+            Self::Doc => &[Field::Summary, Field::Body],
+            Self::Path => &[Field::Path],
+        }
+    }
+}
+
+impl SearchQuery {
+    /// A query for `text` taken literally: each whitespace-separated word is a
+    /// term, with no phrases, exclusions or qualifiers.
+    pub(crate) fn plain(text: &str) -> Self {
         Self {
-            ptr: NonNull::from(reference),
-            _marker: PhantomData,
+            terms: text
+                .split_whitespace()
+                .map(|word| QueryTerm {
+                    text: word.to_string(),
+                    is_phrase: false,
+                    qualifier: None,
+                })
+                .collect(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Parse a text search query.
+///
+/// Whitespace separates clauses, each a word or a `"quoted phrase"`:
+///
+/// - `-word` or `-"some phrase"` leaves out items containing it
+/// - `name:`, `doc:` and `path:` restrict the word or phrase after them to the
+///   item's name, its docs, or the modules and types it's nested in
+/// - `kind:struct` keeps only items of that kind (`fn`, `trait`, `mod`, ...)
+///
+/// Any other `prefix:`, such as in `Safety:` or `https://docs.rs`, is part of
+/// the word, as are words with `::` such as `serde::Serialize`, and a `-` on
+/// its own is just a word. A blank query parses to an empty one; a query of
+/// only exclusions and kinds is an error, since nothing would rank its
+/// results.
+pub(crate) fn parse_search_query(query: &str) -> Result<SearchQuery, QueryError> {
+    let invalid = |reason: String| QueryError::InvalidQuery {
+        query: query.to_string(),
+        reason,
+    };
+
+    let mut parsed = SearchQuery::default();
+    let mut rest = query.trim_start();
+    while !rest.is_empty() {
+        let negated = rest
+            .strip_prefix('-')
+            .is_some_and(|after| after.starts_with(|c: char| !c.is_whitespace()));
+        if negated {
+            rest = &rest[1..];
+        }
+
+        let mut qualifier = None;
+        let mut is_kind = false;
+        if let Some((prefix, after)) = split_qualifier(rest) {
+            match prefix {
+                Prefix::Field(field) => qualifier = Some(field),
+                Prefix::Kind => is_kind = true,
+            }
+            rest = after;
+        }
+
+        let (text, is_phrase, after) = if let Some(quoted) = rest.strip_prefix('"') {
+            let Some(end) = quoted.find('"') else {
+                return Err(invalid("unclosed '\"'".to_string()));
+            };
+            let phrase = quoted[..end].trim();
+            if phrase.is_empty() {
+                return Err(invalid("empty phrase '\"\"'".to_string()));
+            }
+            (phrase, true, &quoted[end + 1..])
+        } else {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '"')
+                .unwrap_or(rest.len());
+            (&rest[..end], false, &rest[end..])
+        };
+        rest = after.trim_start();
+
+        if text.is_empty() {
+            let clause = if is_kind {
+                "'kind:'"
+            } else if qualifier.is_some() {
+                "a qualifier"
+            } else {
+                "'-'"
+            };
+            return Err(invalid(format!(
+                "{clause} must be followed by a word or phrase"
+            )));
+        }
+
+        if is_kind {
+            if negated || is_phrase {
+                return Err(invalid(
+                    "'kind:' takes a single kind and can't be negated".to_string(),
+                ));
+            }
+            let kind = parse_kind(text).ok_or_else(|| {
+                invalid(format!(
+                    "unknown kind '{text}', expected one of mod, struct, enum, fn, trait, \
+                     type, const, static, macro, derive or attribute"
+                ))
+            })?;
+            if !parsed.kinds.contains(&kind) {
+                parsed.kinds.push(kind);
+            }
+            continue;
+        }
+
+        let term = QueryTerm {
+            text: text.to_string(),
+            is_phrase,
+            qualifier,
+        };
+        if negated {
+            parsed.excluded.push(term);
+        } else {
+            parsed.terms.push(term);
         }
     }
 
-    /// Dereference the pointer with proper lifetime bounds.
-    /// SAFETY: Caller must ensure the reference doesn't outlive the arena allocation.
-    const unsafe fn as_ref<'a>(&self) -> &'a T {
-        // SAFETY: Upheld by caller - reference is valid for QueryContext lifetime
-        unsafe { self.ptr.as_ref() }
+    if parsed.terms.is_empty() && (!parsed.excluded.is_empty() || !parsed.kinds.is_empty()) {
+        return Err(invalid(
+            "nothing to search for; add a word or phrase besides exclusions and 'kind:'"
+                .to_string(),
+        ));
     }
+    Ok(parsed)
+}
+
+/// A recognised `prefix:` at the start of a clause.
+enum Prefix {
+    Field(Qualifier),
+    Kind,
+}
+
+/// Split a leading `name:`, `doc:`, `path:` or `kind:` off `clause`. Other
+/// prefixes and the `::` path separator are not qualifiers, so `Safety:` and
+/// `std::io` stay whole.
+fn split_qualifier(clause: &str) -> Option<(Prefix, &str)> {
+    let end = clause.find(|c: char| !c.is_ascii_alphabetic())?;
+    let after = clause[end..].strip_prefix(':')?;
+    if after.starts_with(':') {
+        return None;
+    }
+    let prefix = match clause[..end].to_ascii_lowercase().as_str() {
+        "name" => Prefix::Field(Qualifier::Name),
+        "doc" => Prefix::Field(Qualifier::Doc),
+        "path" => Prefix::Field(Qualifier::Path),
+        "kind" => Prefix::Kind,
+        _ => return None,
+    };
+    Some((prefix, after))
+}
+
+/// The item kind a `kind:` qualifier names, accepting Rust keywords as shorthand.
+fn parse_kind(name: &str) -> Option<ItemKind> {
+    Some(match name.to_ascii_lowercase().as_str() {
+        "mod" | "module" => ItemKind::Module,
+        "struct" => ItemKind::Struct,
+        "enum" => ItemKind::Enum,
+        "fn" | "function" | "method" => ItemKind::Function,
+        "trait" => ItemKind::Trait,
+        "type" | "typealias" => ItemKind::TypeAlias,
+        "const" | "constant" => ItemKind::Constant,
+        "static" => ItemKind::Static,
+        "macro" => ItemKind::Macro,
+        "derive" => ItemKind::Derive,
+        "attribute" | "attr" => ItemKind::Attribute,
+        _ => return None,
+    })
 }
 
 /// Represents a single query context with its own cache and state.
 /// Automatically cleans up when dropped.
 pub struct QueryContext {
     workspace: Arc<WorkspaceContext>,
-    /// Bump allocator for request-scoped memory management
-    arena: Bump,
-    /// Per-query cache of loaded documentation indices
-    doc_cache: RefCell<HashMap<CrateName, ArenaPtr<CrateIndex>>>,
-    /// Negative cache: crate names for which doc generation already failed this session.
-    /// Prevents retrying expensive cargo rustdoc invocations for the same crate.
-    failed_crates: RefCell<std::collections::HashSet<String>>,
+    /// Documentation indices loaded by this context.
+    ///
+    /// Append-only, so a `&CrateIndex` borrowed from it stays valid for as
+    /// long as `&self`.
+    doc_cache: FrozenMap<CrateName, Arc<CrateIndex>>,
+    /// Negative cache: crate names for which doc generation already failed this session,
+    /// with the reason. Prevents retrying expensive cargo rustdoc invocations for the same crate.
+    failed_crates: RefCell<HashMap<String, String>>,
     /// Pre-loaded crate indices registered at construction time (e.g., stdlib).
     ///
     /// Plain `HashMap` (not `RefCell`) because preloading is a construction-time
@@ -175,13 +356,20 @@ pub struct QueryContext {
     /// [`Self::load_crate`] return `&CrateIndex` bound to `&self` via a plain
     /// `Arc::deref`, with no `unsafe` required.
     preloaded: HashMap<CrateName, PreloadedCrate>,
+    /// Long-lived cache keeping parsed docs and search indices across queries, if any.
+    memory: Option<Arc<MemoryCache>>,
+    /// Generates missing docs, sharing each generation with concurrent requests.
+    /// Without it, only docs already on disk can be loaded.
+    docs: Option<Arc<DocState>>,
+    /// Features overriding the resolved ones, by canonical crate name.
+    features: HashMap<CrateName, Vec<String>>,
 }
 
 impl Debug for QueryContext {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("QueryContext")
             .field("workspace", &self.workspace.root)
-            .field("doc_cache_len", &self.doc_cache.borrow().len())
+            .field("doc_cache_len", &self.doc_cache.len())
             .field("failed_crates_len", &self.failed_crates.borrow().len())
             .field("preloaded_len", &self.preloaded.len())
             .finish_non_exhaustive()
@@ -221,10 +409,69 @@ impl QueryContext {
         );
         Self {
             workspace,
-            arena: Bump::new(),
-            doc_cache: RefCell::new(HashMap::new()),
-            failed_crates: RefCell::new(std::collections::HashSet::new()),
+            doc_cache: FrozenMap::new(),
+            failed_crates: RefCell::new(HashMap::new()),
             preloaded,
+            memory: None,
+            docs: None,
+            features: HashMap::new(),
+        }
+    }
+
+    /// Keep the docs loaded and search indices built for this context in
+    /// `memory`, and reuse the ones it already holds.
+    #[must_use]
+    pub fn with_memory(mut self, memory: Arc<MemoryCache>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Generate docs missing from disk through `docs`, so a generation already
+    /// running for another request is awaited rather than started again.
+    #[must_use]
+    pub fn with_doc_state(mut self, docs: Arc<DocState>) -> Self {
+        self.docs = Some(docs);
+        self
+    }
+
+    /// Load `crate_name`'s docs built with exactly `features` enabled, rather than
+    /// the features cargo resolves for the workspace. Ignored for crates the
+    /// workspace doesn't know.
+    #[must_use]
+    pub fn with_features(mut self, crate_name: &str, features: &[String]) -> Self {
+        if let Some(features) = self
+            .workspace
+            .get_crate(crate_name)
+            .and_then(|meta| features_override(meta, features))
+        {
+            let crate_name = self.workspace.canonical_crate_name(crate_name);
+            self.features
+                .insert(CrateName::new_unchecked(crate_name), features);
+        }
+        self
+    }
+
+    /// The memory cache docs and search indices are kept in, if any.
+    pub(crate) const fn memory(&self) -> Option<&Arc<MemoryCache>> {
+        self.memory.as_ref()
+    }
+
+    /// Key of a crate in the [memory cache](Self::memory): preloaded crates are
+    /// standard library crates, any other belongs to the workspace.
+    pub(crate) fn memory_key(&self, crate_name: &str) -> MemoryKey {
+        if self.preloaded.contains_key(crate_name) {
+            return MemoryKey::Stdlib(CrateName::new_unchecked(crate_name));
+        }
+        MemoryKey::Workspace(self.workspace.root.clone(), self.doc_name(crate_name))
+    }
+
+    /// Name a workspace crate's docs are stored under: its canonical name,
+    /// feature-qualified when this context [overrides its features](Self::with_features).
+    fn doc_name(&self, crate_name: &str) -> CrateName {
+        let doc_name = CrateName::new_unchecked(self.workspace.canonical_crate_name(crate_name));
+        match self.features.get(doc_name.normalized()) {
+            Some(features) => feature_doc_name(&doc_name, features),
+            None => doc_name,
         }
     }
 
@@ -232,12 +479,13 @@ impl QueryContext {
     ///
     /// For preloaded crates (e.g., stdlib), returns the path stored in the
     /// [`PreloadedCrate`] — typically a sysroot path. For all other crates,
-    /// returns the standard `<workspace>/target/doc/<crate>.json` location.
+    /// returns `<crate>.json` in the workspace's [doc directory](WorkspaceContext::doc_dir).
     pub fn doc_source_path(&self, crate_name: &str) -> PathBuf {
         if let Some(pre) = self.preloaded.get(crate_name) {
             return pre.source_path.clone();
         }
-        CrateName::new_unchecked(crate_name).doc_json_path(&self.workspace.root.join("target/doc"))
+        self.doc_name(crate_name)
+            .doc_json_path(&self.workspace.doc_dir())
     }
 
     /// Resolve the compiled search-index cache path for a crate, respecting preloaded entries.
@@ -249,13 +497,27 @@ impl QueryContext {
         if let Some(pre) = self.preloaded.get(crate_name) {
             return pre.index_cache_path.clone();
         }
-        CrateName::new_unchecked(crate_name).index_path(&self.workspace.root.join("target/doc"))
+        self.doc_name(crate_name)
+            .index_path(&self.workspace.doc_dir())
+    }
+
+    /// Resolve the path of the digest recorded when a crate's docs were generated.
+    ///
+    /// `None` for preloaded crates, whose docs don't come from the workspace.
+    pub fn digest_path(&self, crate_name: &str) -> Option<PathBuf> {
+        if self.preloaded.contains_key(crate_name) {
+            return None;
+        }
+        Some(
+            self.doc_name(crate_name)
+                .digest_path(&self.workspace.doc_dir()),
+        )
     }
 
     /// Returns true if documentation generation for this crate failed earlier in this
     /// query context's lifetime. Used to skip redundant retry attempts.
     pub fn is_generation_failed(&self, crate_name: &str) -> bool {
-        self.failed_crates.borrow().contains(crate_name)
+        self.failed_crates.borrow().contains_key(crate_name)
     }
 
     /// Get the workspace root directory.
@@ -263,10 +525,20 @@ impl QueryContext {
         &self.workspace.root
     }
 
+    /// Get the workspace context this query runs against.
+    pub fn workspace(&self) -> &WorkspaceContext {
+        &self.workspace
+    }
+
     /// Load a crate's documentation by name, using the cache if available.
     ///
-    /// Attempts to load existing documentation first. If not found and the environment
-    /// supports doc generation (has Cargo.toml, source files, etc.), generates docs.
+    /// `crate_name` may be a `name@version` spec; a bare name of a crate present in
+    /// several versions loads its primary version.
+    ///
+    /// Docs already in the [memory cache](Self::with_memory) are served without
+    /// parsing. Otherwise attempts to load existing documentation first, and if
+    /// not found and the environment supports doc generation (has Cargo.toml,
+    /// source files, etc.), generates docs through the [`DocState`](Self::with_doc_state).
     /// Returns a reference bound to the lifetime of this QueryContext.
     pub fn load_crate(&self, crate_name: &str) -> Result<&CrateIndex, LoadError> {
         // Preloaded crates (e.g., stdlib) bypass the workspace target/doc/ lookup.
@@ -276,12 +548,32 @@ impl QueryContext {
         if let Some(pre) = self.preloaded.get(crate_name) {
             return Ok(pre.index.as_ref());
         }
+        let canonical = self.workspace.canonical_crate_name(crate_name);
+        let crate_index = self.load_index(&canonical)?;
+        Ok(self.cache_crate_index(&canonical, crate_index))
+    }
+
+    /// Load a crate's documentation like [`Self::load_crate`], returning the
+    /// shared index so it can outlive this context.
+    pub fn load_shared(&self, crate_name: &str) -> Result<Arc<CrateIndex>, LoadError> {
+        if let Some(pre) = self.preloaded.get(crate_name) {
+            return Ok(pre.index.clone());
+        }
+        let canonical = self.workspace.canonical_crate_name(crate_name);
+        let crate_index = self.load_index(&canonical)?;
+        self.cache_crate_index(&canonical, crate_index.clone());
+        Ok(crate_index)
+    }
+
+    /// Load the docs of the canonically named `crate_name` from this
+    /// context's cache, the memory cache or disk, generating them if needed.
+    fn load_index(&self, crate_name: &str) -> Result<Arc<CrateIndex>, LoadError> {
+        if let Some(cached) = self.cached_crate_index(crate_name) {
+            return Ok(cached);
+        }
 
-        // Check cache first and return reference with proper lifetime
-        if let Some(cached_ptr) = self.doc_cache.borrow().get(crate_name) {
-            // SAFETY: The ArenaPtr is valid for the lifetime of self (arena allocation).
-            // We control all access through &self methods, ensuring the reference cannot outlive QueryContext.
-            return Ok(unsafe { cached_ptr.as_ref() });
+        if let Some(cached) = self.remembered(crate_name) {
+            return Ok(cached);
         }
 
         // Note: stdlib handlers construct a sentinel workspace root at "/".
@@ -296,8 +588,19 @@ impl QueryContext {
 
         // If documentation doesn't exist, check if we can generate it
         if !doc_path.exists() {
+            if let Some(reason) = self.failed_crates.borrow().get(crate_name) {
+                return Err(LoadError::GenerationFailed {
+                    crate_name: crate_name_typed,
+                    reason: reason.clone(),
+                });
+            }
+
             // Check if we have the minimum requirements to generate docs
-            if !self.can_generate_docs(crate_name) {
+            let Some(docs) = self
+                .docs
+                .as_ref()
+                .filter(|_| self.can_generate_docs(crate_name))
+            else {
                 tracing::debug!(
                     crate_name,
                     reason = "environment not suitable",
@@ -306,51 +609,37 @@ impl QueryContext {
                 return Err(LoadError::NotFound {
                     crate_name: crate_name_typed,
                 });
-            }
+            };
 
             tracing::info!(crate_name, "Documentation not found, generating");
 
-            let is_workspace_member = self.workspace.members.iter().any(|m| m.matches(crate_name));
-            let version = self.workspace.get_version(crate_name);
-
-            let cargo_lock_path = self.workspace.root.join("Cargo.lock");
-            let cargo_lock_path = cargo_lock_path.exists().then_some(cargo_lock_path);
-
             // Use block_in_place to allow blocking within async context
+            let root = &self.workspace.root;
             let result = tokio::task::block_in_place(|| {
                 tokio::runtime::Handle::current().block_on(async {
-                    crate::workspace::get_docs(
-                        &crate_name_typed,
-                        version,
-                        &self.workspace.root,
-                        is_workspace_member,
-                        cargo_lock_path.as_deref(),
-                    )
-                    .await
+                    match self.features.get(crate_name) {
+                        Some(features) => {
+                            docs.get_docs_with_features(root, crate_name, features)
+                                .await
+                        }
+                        None => docs.get_docs(root, crate_name).await,
+                    }
                 })
             });
 
-            if let Err(e) = result {
-                tracing::error!(crate_name, error = ?e, "Failed to generate docs");
+            return result.map_err(|reason| {
+                tracing::error!(crate_name, error = %reason, "Failed to generate docs");
                 self.failed_crates
                     .borrow_mut()
-                    .insert(crate_name.to_string());
-                return Err(LoadError::GenerationFailed {
+                    .insert(crate_name.to_string(), reason.clone());
+                LoadError::GenerationFailed {
                     crate_name: crate_name_typed,
-                    reason: e.to_string(),
-                });
-            }
-        }
-
-        // Verify the file now exists (generation might have failed silently)
-        if !doc_path.exists() {
-            return Err(LoadError::NotFoundAt {
-                crate_name: crate_name_typed,
-                path: doc_path,
+                    reason,
+                }
             });
         }
 
-        // Load the documentation (either existing or just generated)
+        // Load the existing documentation
         let crate_index = CrateIndex::load(&doc_path).map_err(|e| {
             tracing::error!(crate_name, error = ?e, "Failed to load docs");
             LoadError::ParseFailed {
@@ -359,17 +648,61 @@ impl QueryContext {
             }
         })?;
 
-        Ok(self.cache_crate_index(crate_name, crate_index))
+        Ok(self.remember(crate_name, crate_index))
+    }
+
+    /// Whether a crate's docs can be loaded without generating them: they are
+    /// preloaded, held in memory, or already on disk.
+    pub fn has_docs(&self, crate_name: &str) -> bool {
+        self.preloaded.contains_key(crate_name)
+            || self
+                .cached_crate_index(&self.workspace.canonical_crate_name(crate_name))
+                .is_some()
+            || self
+                .memory
+                .as_ref()
+                .is_some_and(|memory| memory.contains(&self.memory_key(crate_name)))
+            || self.doc_source_path(crate_name).exists()
+    }
+
+    /// A crate index this context already loaded.
+    fn cached_crate_index(&self, crate_name: &str) -> Option<Arc<CrateIndex>> {
+        // Keys borrow as their normalized name
+        self.doc_cache
+            .map_get(CrateName::normalize(crate_name).as_ref(), Arc::clone)
     }
 
-    /// Allocate a CrateIndex in the arena and cache it for future lookups.
-    fn cache_crate_index(&self, crate_name: &str, crate_index: CrateIndex) -> &CrateIndex {
-        let allocated: &CrateIndex = self.arena.alloc(crate_index);
-        let arena_ptr = ArenaPtr::new(allocated);
+    /// The crate's docs in the memory cache, built with the features this
+    /// context loads the crate with.
+    fn remembered(&self, crate_name: &str) -> Option<Arc<CrateIndex>> {
+        let memory = self.memory.as_ref()?;
+        if let Some(cached) = memory.get(&self.memory_key(crate_name)) {
+            index_metrics::record_memory_hit();
+            Some(cached.index)
+        } else {
+            index_metrics::record_memory_miss();
+            None
+        }
+    }
+
+    /// Cache a CrateIndex for future lookups through this context, keeping
+    /// the entry already there if another load cached the crate first.
+    fn cache_crate_index(&self, crate_name: &str, crate_index: Arc<CrateIndex>) -> &CrateIndex {
         self.doc_cache
-            .borrow_mut()
-            .insert(CrateName::new_unchecked(crate_name), arena_ptr);
-        allocated
+            .insert(CrateName::new_unchecked(crate_name), crate_index)
+    }
+
+    /// Keep a freshly parsed index in the memory cache for later contexts.
+    fn remember(&self, crate_name: &str, crate_index: CrateIndex) -> Arc<CrateIndex> {
+        let crate_index = Arc::new(crate_index);
+        if let Some(memory) = &self.memory {
+            let cached = CachedDocs {
+                index: crate_index.clone(),
+                features_override: self.features.get(crate_name).cloned(),
+            };
+            memory.insert(self.memory_key(crate_name), cached);
+        }
+        crate_index
     }
 
     /// Check if we have the minimum requirements to generate documentation.
@@ -377,6 +710,15 @@ impl QueryContext {
     /// This guards against attempting doc generation in isolated test environments
     /// or read-only filesystems where it would fail.
     fn can_generate_docs(&self, crate_name: &str) -> bool {
+        // Only crates in the workspace's metadata can be documented
+        if self.workspace.get_crate(crate_name).is_none() {
+            tracing::debug!(
+                "Cannot generate docs for '{}': not in the workspace",
+                crate_name
+            );
+            return false;
+        }
+
         let cargo_toml = self.workspace.root.join("Cargo.toml");
 
         // Must have Cargo.toml
@@ -428,12 +770,12 @@ impl QueryContext {
                 self.workspace
                     .members
                     .iter()
-                    .map(super::super::types::CrateName::as_str)
+                    .map(|member| member.as_str().to_string())
                     .chain(self.workspace.dependency_names())
                     .map(|name| PathSuggestion {
-                        path: name.to_string(),
-                        item: None,
                         score: jaro_winkler::similarity(crate_name.chars(), name.chars()),
+                        path: name,
+                        item: None,
                     }),
             );
             return None;
@@ -451,11 +793,24 @@ impl QueryContext {
         }
     }
 
+    /// Resolve a `::`-separated path relative to an already-resolved item,
+    /// e.g. `push` relative to `Vec`, descending into associated items.
+    pub fn resolve_relative<'a>(
+        &'a self,
+        item: ItemRef<'a, Item>,
+        path: &str,
+    ) -> Option<ItemRef<'a, Item>> {
+        self.find_children_recursive(item, path, 0, &mut Vec::new())
+    }
+
     /// Load a crate, discovering it from existing doc files if not in known crates.
     ///
     /// This is useful for loading crates like `serde_core` that are internal
     /// dependencies of `serde` but not directly listed in the workspace's dependencies.
     pub fn load_crate_with_discovery(&self, crate_name: &str) -> Result<&CrateIndex, LoadError> {
+        let canonical = self.workspace.canonical_crate_name(crate_name);
+        let crate_name = canonical.as_ref();
+
         // First try normal loading (checks cache, generates if needed)
         match self.load_crate(crate_name) {
             Ok(index) => return Ok(index),
@@ -483,6 +838,7 @@ impl QueryContext {
                 reason: e.to_string(),
             })?;
 
+            let crate_index = self.remember(crate_name, crate_index);
             return Ok(self.cache_crate_index(crate_name, crate_index));
         }
 
@@ -529,6 +885,18 @@ impl QueryContext {
             }
         }
 
+        // Associated items: `Type::method`, `Trait::method`, `Type::CONST`, `Trait::Assoc`.
+        // Module children above already cover inherent methods of structs and enums;
+        // this adds trait definitions, trait impls and associated consts/types.
+        for assoc in item.associated_items() {
+            if assoc.name() == Some(segment)
+                && let Some(found) =
+                    self.find_children_recursive(assoc, path, next_segment_start, suggestions)
+            {
+                return Some(found);
+            }
+        }
+
         // No match found - generate suggestions
         suggestions.extend(Self::generate_suggestions(item, path, index));
         None
@@ -613,7 +981,7 @@ impl Drop for QueryContext {
     fn drop(&mut self) {
         tracing::trace!(
             "QueryContext dropped, cleaned up {} crates",
-            self.doc_cache.borrow().len()
+            self.doc_cache.len()
         );
     }
 }
@@ -642,3 +1010,119 @@ impl<'a> PathSuggestion<'a> {
         self.score
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use rstest::rstest;
+
+    fn term(text: &str, is_phrase: bool, qualifier: Option<Qualifier>) -> QueryTerm {
+        QueryTerm {
+            text: text.to_string(),
+            is_phrase,
+            qualifier,
+        }
+    }
+
+    #[test]
+    fn parses_phrases_exclusions_and_qualifiers() {
+        let_assert!(
+            Ok(query) =
+                parse_search_query(r#""connection pool" -deprecated kind:struct name:Pool"#)
+        );
+        check!(
+            query.terms
+                == vec![
+                    term("connection pool", true, None),
+                    term("Pool", false, Some(Qualifier::Name)),
+                ]
+        );
+        check!(query.excluded == vec![term("deprecated", false, None)]);
+        check!(query.kinds == vec![ItemKind::Struct]);
+    }
+
+    #[test]
+    fn qualifiers_apply_to_phrases_and_exclusions() {
+        let_assert!(Ok(query) = parse_search_query(r#"doc:"retry policy" -path:"internal""#));
+        check!(query.terms == vec![term("retry policy", true, Some(Qualifier::Doc))]);
+        check!(query.excluded == vec![term("internal", true, Some(Qualifier::Path))]);
+    }
+
+    #[rstest]
+    #[case("serde::Serialize")]
+    #[case("std::io::Error")]
+    #[case("foo-bar")]
+    #[case("Safety:")]
+    #[case("https://docs.rs")]
+    #[case("size:4")]
+    #[case("-")]
+    fn non_qualifiers_stay_plain_words(#[case] input: &str) {
+        let_assert!(Ok(query) = parse_search_query(input));
+        check!(query.terms == vec![term(input, false, None)]);
+    }
+
+    #[rstest]
+    #[case("fn", ItemKind::Function)]
+    #[case("Module", ItemKind::Module)]
+    #[case("type", ItemKind::TypeAlias)]
+    fn kind_accepts_keywords(#[case] name: &str, #[case] kind: ItemKind) {
+        let_assert!(Ok(query) = parse_search_query(&format!("parse kind:{name}")));
+        check!(query.kinds == vec![kind]);
+    }
+
+    #[rstest]
+    #[case(r#""connection pool"#)]
+    #[case(r#"pool """#)]
+    #[case("pool name:")]
+    #[case("pool kind:widget")]
+    #[case("pool -kind:struct")]
+    #[case("-deprecated")]
+    #[case("kind:struct")]
+    fn malformed_queries_are_rejected(#[case] input: &str) {
+        let_assert!(Err(QueryError::InvalidQuery { query, .. }) = parse_search_query(input));
+        check!(query == input);
+    }
+
+    #[test]
+    fn unknown_prefixes_and_lone_dashes_are_literal() {
+        let_assert!(Ok(query) = parse_search_query("Safety: must be aligned"));
+        check!(
+            query.terms
+                == vec![
+                    term("Safety:", false, None),
+                    term("must", false, None),
+                    term("be", false, None),
+                    term("aligned", false, None),
+                ]
+        );
+        let_assert!(Ok(query) = parse_search_query("a - b"));
+        check!(query.excluded.is_empty());
+        check!(
+            query.terms
+                == vec![
+                    term("a", false, None),
+                    term("-", false, None),
+                    term("b", false, None),
+                ]
+        );
+    }
+
+    #[test]
+    fn blank_query_is_empty() {
+        let_assert!(Ok(query) = parse_search_query("   "));
+        check!(query == SearchQuery::default());
+    }
+
+    #[test]
+    fn plain_query_takes_syntax_literally() {
+        let query = SearchQuery::plain(r#"-"pool" kind:struct"#);
+        check!(
+            query.terms
+                == vec![
+                    term(r#"-"pool""#, false, None),
+                    term("kind:struct", false, None),
+                ]
+        );
+    }
+}
@@ -34,6 +34,9 @@ pub enum ItemKind {
     TypeAlias,
     Constant,
     Static,
+    Macro,
+    Derive,
+    Attribute,
 }
 
 /// Check if an ItemEnum matches a specific ItemKind.
@@ -48,6 +51,28 @@ pub(crate) const fn matches_kind(inner: &ItemEnum, kind: ItemKind) -> bool {
             | (ItemEnum::TypeAlias(_), ItemKind::TypeAlias)
             | (ItemEnum::Constant { .. }, ItemKind::Constant)
             | (ItemEnum::Static(_), ItemKind::Static)
+            | (
+                ItemEnum::Macro(_)
+                    | ItemEnum::ProcMacro(ProcMacro {
+                        kind: MacroKind::Bang,
+                        ..
+                    }),
+                ItemKind::Macro
+            )
+            | (
+                ItemEnum::ProcMacro(ProcMacro {
+                    kind: MacroKind::Derive,
+                    ..
+                }),
+                ItemKind::Derive
+            )
+            | (
+                ItemEnum::ProcMacro(ProcMacro {
+                    kind: MacroKind::Attr,
+                    ..
+                }),
+                ItemKind::Attribute
+            )
     )
 }
 
@@ -105,8 +130,19 @@ pub(crate) const fn item_kind_str(inner: &ItemEnum) -> &'static str {
         ItemEnum::Impl(_) => "impl",
         ItemEnum::Use(_) => "use",
         ItemEnum::Union(_) => "union",
-        ItemEnum::Macro(_) => "macro",
-        ItemEnum::ProcMacro(_) => "proc_macro",
+        ItemEnum::Macro(_)
+        | ItemEnum::ProcMacro(ProcMacro {
+            kind: MacroKind::Bang,
+            ..
+        }) => "macro",
+        ItemEnum::ProcMacro(ProcMacro {
+            kind: MacroKind::Derive,
+            ..
+        }) => "derive",
+        ItemEnum::ProcMacro(ProcMacro {
+            kind: MacroKind::Attr,
+            ..
+        }) => "attribute",
         ItemEnum::Primitive(_) => "primitive",
         ItemEnum::AssocConst { .. } => "assoc_const",
         ItemEnum::AssocType { .. } => "assoc_type",
@@ -114,10 +150,16 @@ pub(crate) const fn item_kind_str(inner: &ItemEnum) -> &'static str {
     }
 }
 
+/// Approximate memory held per byte of rustdoc JSON: the parsed crate takes
+/// about as much as its JSON, and [`CrateIndex::index`] holds a second copy of
+/// the items.
+const MEMORY_PER_JSON_BYTE: usize = 2;
+
 pub struct CrateIndex {
     crate_data: Crate,
     pub index: HashMap<Id, Item>,
     _external_crates: HashMap<u32, String>,
+    memory_bytes: usize,
 }
 
 impl CrateIndex {
@@ -144,9 +186,15 @@ impl CrateIndex {
             crate_data,
             index,
             _external_crates: external_crates,
+            memory_bytes: content.len().saturating_mul(MEMORY_PER_JSON_BYTE),
         })
     }
 
+    /// Approximate memory held by this index, estimated from its JSON size.
+    pub const fn memory_bytes(&self) -> usize {
+        self.memory_bytes
+    }
+
     /// Async wrapper around [`Self::load`] that offloads blocking I/O to a thread pool.
     pub async fn load_async<P: AsRef<Path> + Send + 'static>(path: P) -> Result<Self> {
         tokio::task::spawn_blocking(move || Self::load(path))
@@ -1,6 +1,126 @@
 //! Search relevance and ranking algorithms.
 //!
-//! This module provides utilities for canonicality scoring used in search and query resolution.
+//! This module provides BM25F term scoring for the inverted index, and utilities
+//! for canonicality scoring used in search and query resolution.
+
+// BM25F divides term counts and field lengths, which are far below the f32
+// mantissa limit.
+#![allow(clippy::cast_precision_loss)]
+
+use serde::{Deserialize, Serialize};
+
+/// Number of [`Field`]s.
+pub(crate) const FIELD_COUNT: usize = 5;
+
+/// BM25 term frequency saturation: the higher, the longer repeated occurrences
+/// of a term keep adding to its score.
+const K1: f32 = 1.2;
+
+/// A part of an item indexed separately, with its own weight and length
+/// normalization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Field {
+    /// The item's name.
+    Name,
+    /// Names of the modules and types the item is nested in.
+    Path,
+    /// Parameter names and types of a function, and its return type.
+    Signature,
+    /// First paragraph of the doc comment.
+    Summary,
+    /// The rest of the doc comment.
+    Body,
+}
+
+impl Field {
+    /// Every field, in the order [`FieldCounts`] stores them.
+    pub(crate) const ALL: [Self; FIELD_COUNT] = [
+        Self::Name,
+        Self::Path,
+        Self::Signature,
+        Self::Summary,
+        Self::Body,
+    ];
+
+    /// How much an occurrence in this field counts relative to the others.
+    const fn weight(self) -> f32 {
+        match self {
+            Self::Name => 5.0,
+            Self::Path | Self::Summary => 1.0,
+            Self::Signature | Self::Body => 0.5,
+        }
+    }
+
+    /// BM25's `b`: how far term counts in a field longer than average are
+    /// scaled down, from 0 (not at all) to 1 (in proportion to its length).
+    const fn length_normalization(self) -> f32 {
+        match self {
+            Self::Name | Self::Path | Self::Signature => 0.5,
+            Self::Summary | Self::Body => 0.75,
+        }
+    }
+}
+
+/// A count per [`Field`], indexed by `Field as usize`: how often a term occurs
+/// in each field of a document, or how many terms each field holds.
+pub(crate) type FieldCounts = [u16; FIELD_COUNT];
+
+/// Corpus-wide field statistics BM25F normalizes field lengths against.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct FieldStats {
+    documents: u64,
+    /// Terms in each field, summed over every document.
+    total_lengths: [u64; FIELD_COUNT],
+}
+
+impl FieldStats {
+    /// Count a document with the given field lengths.
+    pub(crate) fn add(&mut self, lengths: &FieldCounts) {
+        self.documents += 1;
+        for (total, &length) in self.total_lengths.iter_mut().zip(lengths) {
+            *total += u64::from(length);
+        }
+    }
+
+    /// Average number of terms in `field` per document.
+    fn average_length(&self, field: Field) -> f32 {
+        if self.documents == 0 {
+            return 0.0;
+        }
+        self.total_lengths[field as usize] as f32 / self.documents as f32
+    }
+
+    /// Inverse document frequency of a term found in `doc_freq` documents.
+    /// Unlike the classic `ln(N / df)`, it stays positive for terms found in
+    /// most documents.
+    pub(crate) fn idf(&self, doc_freq: usize) -> f32 {
+        let documents = self.documents as f32;
+        let doc_freq = doc_freq as f32;
+        ((documents - doc_freq + 0.5) / (doc_freq + 0.5)).ln_1p()
+    }
+
+    /// BM25F score of a term with inverse document frequency `idf`, occurring
+    /// `counts` times in the fields of a document whose fields hold `lengths`
+    /// terms.
+    ///
+    /// Each field's count is weighted and normalized by the field's length
+    /// relative to its average before the counts are summed and saturated, so
+    /// a term repeated across fields doesn't score as if each field were a
+    /// separate match.
+    pub(crate) fn score(&self, idf: f32, counts: &FieldCounts, lengths: &FieldCounts) -> f32 {
+        let tf: f32 = Field::ALL
+            .iter()
+            .filter(|&&field| counts[field as usize] > 0)
+            .map(|&field| {
+                let average = self.average_length(field).max(1.0);
+                let b = field.length_normalization();
+                let norm = f32::from(lengths[field as usize]) / average;
+                field.weight() * f32::from(counts[field as usize]) / (b.mul_add(norm, 1.0 - b))
+            })
+            .sum();
+        idf * tf * (K1 + 1.0) / (K1 + tf)
+    }
+}
 
 /// Convert a relevance score in `[0.0, 1.0]` to an integer percent in `[0, 100]`.
 ///
@@ -0,0 +1,911 @@
+//! Type-signature search over function and method signatures.
+//!
+//! Queries look like Rust signatures with the function name left out:
+//!
+//! - `&str -> Result<_, io::Error>`: one `&str` parameter, returning a `Result`
+//!   whose error type is `io::Error`
+//! - `(&str, usize) -> bool` or `fn(&str, usize) -> bool`: several parameters
+//! - `-> impl Iterator`: return type only, any parameters
+//! - `Vec<T>`: parameter types only, any return type
+//!
+//! Both the query and each candidate's [`FunctionSignature`] are normalized into
+//! [`SigType`] trees. Paths on the candidate side are resolved through the same
+//! `paths` table lookup [`TypeFormatter`] uses for display, so `io::Error` in a
+//! query matches `std::io::error::Error` in the docs. Generic parameters, `_`,
+//! and single-letter uppercase names in the query act as wildcards.
+
+// Unification scores are averaged over small argument counts.
+#![allow(clippy::cast_precision_loss)]
+
+use crate::error::QueryError;
+use crate::format::TypeFormatter;
+use crate::search::{CrateIndex, ItemFilter};
+use rustdoc_types::{
+    AssocItemConstraintKind, FunctionSignature, GenericArg, GenericArgs, GenericBound, Id, Item,
+    ItemEnum, Path, Term, Type, Visibility,
+};
+
+/// Penalty applied when a query type only matches after adding or removing a reference.
+const AUTOREF_PENALTY: f32 = 0.6;
+
+/// Score for a concrete query type matched against a generic candidate parameter.
+const GENERIC_CANDIDATE_SCORE: f32 = 0.75;
+
+/// Candidates scoring below this are not returned.
+const MIN_SCORE: f32 = 0.3;
+
+/// A normalized type used for signature unification.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SigType {
+    /// Wildcard: generic parameters, `_`, associated-type projections.
+    Any,
+    /// A nominal type or trait, identified by its path segments.
+    Named {
+        path: Vec<String>,
+        args: Vec<Self>,
+    },
+    Ref {
+        mutable: bool,
+        inner: Box<Self>,
+    },
+    Ptr {
+        mutable: bool,
+        inner: Box<Self>,
+    },
+    /// Slices and arrays (array lengths are ignored).
+    Slice(Box<Self>),
+    /// Tuples; `()` is the empty tuple.
+    Tuple(Vec<Self>),
+    /// Function pointers.
+    Fn,
+}
+
+impl SigType {
+    const fn named(path: Vec<String>, args: Vec<Self>) -> Self {
+        Self::Named { path, args }
+    }
+}
+
+/// A parsed signature query.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SignatureQuery {
+    /// Parameter types, or `None` if the query only constrains the return type.
+    pub inputs: Option<Vec<SigType>>,
+    /// Return type, or `None` if the query only constrains the parameters.
+    pub output: Option<SigType>,
+}
+
+/// A function or method whose signature unified with the query.
+#[derive(Debug, Clone)]
+pub(crate) struct SignatureMatch {
+    pub id: Id,
+    /// Fully-qualified path, e.g. `std::string::String::from_utf8`.
+    pub path: String,
+    /// Unification score in `(0.0, 1.0]`.
+    pub score: f32,
+}
+
+/// Parse a signature query such as `&str -> Result<_, io::Error>`.
+pub(crate) fn parse_signature_query(query: &str) -> Result<SignatureQuery, QueryError> {
+    let invalid = |reason: String| QueryError::InvalidSignature {
+        query: query.to_string(),
+        reason,
+    };
+
+    let tokens = lex(query).map_err(invalid)?;
+    if tokens.is_empty() {
+        return Err(QueryError::EmptyQuery);
+    }
+
+    let mut parser = Parser { tokens, pos: 0 };
+    let parsed = parser.parse_query().map_err(invalid)?;
+    if let Some(token) = parser.peek() {
+        return Err(invalid(format!("unexpected '{token}'")));
+    }
+    Ok(parsed)
+}
+
+/// Scan every public function and method in a crate and rank them against the query.
+///
+/// Covers free functions, inherent methods, and trait method declarations.
+/// Trait impl methods are skipped: they repeat the trait's declaration once per
+/// implementing type. Functions failing `filter` are dropped before `limit` applies.
+pub(crate) fn search_signatures(
+    index: &CrateIndex,
+    query: &SignatureQuery,
+    filter: &ItemFilter,
+    limit: usize,
+) -> Vec<SignatureMatch> {
+    let fmt = TypeFormatter::new(index);
+    let mut matches = Vec::new();
+
+    let mut consider = |item: &Item, path: String, self_ty: Option<&SigType>| {
+        let ItemEnum::Function(func) = &item.inner else {
+            return;
+        };
+        if !filter.is_empty() && !filter.matches(item, &path.split("::").collect::<Vec<_>>()) {
+            return;
+        }
+        let candidate = normalize_signature(&fmt, &func.sig, self_ty);
+        if let Some(score) = score_signature(query, &candidate) {
+            matches.push(SignatureMatch {
+                id: item.id,
+                path,
+                score,
+            });
+        }
+    };
+
+    for item in index.index.values() {
+        match &item.inner {
+            ItemEnum::Function(_) if matches!(item.visibility, Visibility::Public) => {
+                // Methods have no `paths` entry; only free functions do.
+                if let Some(summary) = index.paths().get(&item.id) {
+                    consider(item, summary.path.join("::"), None);
+                }
+            }
+            ItemEnum::Impl(impl_block)
+                if impl_block.trait_.is_none()
+                    && !impl_block.is_synthetic
+                    && impl_block.blanket_impl.is_none() =>
+            {
+                let self_ty = normalize_type(&fmt, &impl_block.for_, None);
+                let owner = owner_path(&fmt, &impl_block.for_);
+                for method in impl_block.items.iter().filter_map(|id| index.get_item(*id)) {
+                    if matches!(method.visibility, Visibility::Public)
+                        && let Some(name) = &method.name
+                    {
+                        consider(method, format!("{owner}::{name}"), Some(&self_ty));
+                    }
+                }
+            }
+            ItemEnum::Trait(trait_) if matches!(item.visibility, Visibility::Public) => {
+                let Some(summary) = index.paths().get(&item.id) else {
+                    continue;
+                };
+                let owner = summary.path.join("::");
+                for method in trait_.items.iter().filter_map(|id| index.get_item(*id)) {
+                    if let Some(name) = &method.name {
+                        consider(method, format!("{owner}::{name}"), None);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    matches.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.path.len().cmp(&b.path.len()))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+/// Display path for the type an inherent impl is attached to.
+fn owner_path(fmt: &TypeFormatter<'_>, ty: &Type) -> String {
+    if let Type::ResolvedPath(path) = ty
+        && let Some(segments) = fmt.path_segments(path)
+    {
+        return segments.join("::");
+    }
+    let mut out = String::new();
+    let _ = fmt.write_type(&mut out, ty);
+    out
+}
+
+/// Normalized parameter and return types of a candidate function.
+#[derive(Debug)]
+struct CandidateSignature {
+    inputs: Vec<SigType>,
+    output: SigType,
+}
+
+fn normalize_signature(
+    fmt: &TypeFormatter<'_>,
+    sig: &FunctionSignature,
+    self_ty: Option<&SigType>,
+) -> CandidateSignature {
+    CandidateSignature {
+        inputs: sig
+            .inputs
+            .iter()
+            .map(|(_, ty)| normalize_type(fmt, ty, self_ty))
+            .collect(),
+        output: sig
+            .output
+            .as_ref()
+            .map_or(SigType::Tuple(Vec::new()), |ty| {
+                normalize_type(fmt, ty, self_ty)
+            }),
+    }
+}
+
+/// Normalize a rustdoc [`Type`] into a [`SigType`].
+///
+/// `Self` is replaced by `self_ty` when the function lives in an inherent impl.
+pub(crate) fn normalize_type(
+    fmt: &TypeFormatter<'_>,
+    ty: &Type,
+    self_ty: Option<&SigType>,
+) -> SigType {
+    match ty {
+        Type::ResolvedPath(path) => normalize_path(fmt, path, self_ty),
+        Type::DynTrait(dyn_trait) => dyn_trait.traits.first().map_or(SigType::Any, |poly| {
+            normalize_path(fmt, &poly.trait_, self_ty)
+        }),
+        Type::ImplTrait(bounds) => bounds
+            .iter()
+            .find_map(|bound| match bound {
+                GenericBound::TraitBound { trait_, .. } => {
+                    Some(normalize_path(fmt, trait_, self_ty))
+                }
+                _ => None,
+            })
+            .unwrap_or(SigType::Any),
+        Type::Generic(name) if name == "Self" => self_ty.cloned().unwrap_or(SigType::Any),
+        Type::Generic(_) | Type::Infer | Type::QualifiedPath { .. } => SigType::Any,
+        Type::Primitive(name) => SigType::named(vec![name.clone()], Vec::new()),
+        Type::FunctionPointer(_) => SigType::Fn,
+        Type::Tuple(types) => SigType::Tuple(
+            types
+                .iter()
+                .map(|t| normalize_type(fmt, t, self_ty))
+                .collect(),
+        ),
+        Type::Slice(inner) | Type::Array { type_: inner, .. } => {
+            SigType::Slice(Box::new(normalize_type(fmt, inner, self_ty)))
+        }
+        Type::Pat { type_, .. } => normalize_type(fmt, type_, self_ty),
+        Type::RawPointer { is_mutable, type_ } => SigType::Ptr {
+            mutable: *is_mutable,
+            inner: Box::new(normalize_type(fmt, type_, self_ty)),
+        },
+        Type::BorrowedRef {
+            is_mutable, type_, ..
+        } => SigType::Ref {
+            mutable: *is_mutable,
+            inner: Box::new(normalize_type(fmt, type_, self_ty)),
+        },
+    }
+}
+
+fn normalize_path(fmt: &TypeFormatter<'_>, path: &Path, self_ty: Option<&SigType>) -> SigType {
+    let segments = fmt.path_segments(path).map_or_else(
+        || path.path.split("::").map(str::to_string).collect(),
+        <[String]>::to_vec,
+    );
+
+    let args = match path.args.as_deref() {
+        Some(GenericArgs::AngleBracketed { args, constraints }) => args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArg::Type(t) => Some(normalize_type(fmt, t, self_ty)),
+                GenericArg::Infer => Some(SigType::Any),
+                GenericArg::Lifetime(_) | GenericArg::Const(_) => None,
+            })
+            .chain(constraints.iter().filter_map(|c| match &c.binding {
+                AssocItemConstraintKind::Equality(Term::Type(t)) => {
+                    Some(normalize_type(fmt, t, self_ty))
+                }
+                _ => None,
+            }))
+            .collect(),
+        Some(GenericArgs::Parenthesized { inputs, output }) => inputs
+            .iter()
+            .map(|t| normalize_type(fmt, t, self_ty))
+            .chain(std::iter::once(
+                output.as_ref().map_or(SigType::Tuple(Vec::new()), |t| {
+                    normalize_type(fmt, t, self_ty)
+                }),
+            ))
+            .collect(),
+        Some(GenericArgs::ReturnTypeNotation) | None => Vec::new(),
+    };
+
+    SigType::named(segments, args)
+}
+
+/// Score a candidate signature against the query, or `None` if it doesn't match.
+fn score_signature(query: &SignatureQuery, candidate: &CandidateSignature) -> Option<f32> {
+    let input_score = match &query.inputs {
+        Some(inputs) => Some(score_inputs(inputs, &candidate.inputs)?),
+        None => None,
+    };
+    let output_score = query
+        .output
+        .as_ref()
+        .map(|output| unify(output, &candidate.output));
+
+    let score = match (input_score, output_score) {
+        (Some(i), Some(o)) if o > 0.0 => f32::midpoint(i, o),
+        (Some(i), None) => i,
+        (None, Some(o)) => o,
+        (Some(_), Some(_)) | (None, None) => return None,
+    };
+
+    (score >= MIN_SCORE).then_some(score)
+}
+
+/// Match query parameters to candidate parameters in any order.
+///
+/// Every query parameter must unify with a distinct candidate parameter.
+/// Extra candidate parameters cost half a parameter each.
+fn score_inputs(query: &[SigType], candidate: &[SigType]) -> Option<f32> {
+    if query.len() > candidate.len() {
+        return None;
+    }
+
+    let mut used = vec![false; candidate.len()];
+    let mut matched = 0.0;
+    for q in query {
+        let (best_idx, best_score) = candidate
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !used[*i])
+            .map(|(i, c)| (i, unify(q, c)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+        if best_score <= 0.0 {
+            return None;
+        }
+        used[best_idx] = true;
+        matched += best_score;
+    }
+
+    let extra = (candidate.len() - query.len()) as f32;
+    if query.is_empty() {
+        return Some(1.0 / 0.5f32.mul_add(extra, 1.0));
+    }
+    Some(matched / 0.5f32.mul_add(extra, query.len() as f32))
+}
+
+/// Unify a query type with a candidate type, returning a score in `[0.0, 1.0]`.
+fn unify(query: &SigType, candidate: &SigType) -> f32 {
+    match (query, candidate) {
+        (SigType::Any, _) | (SigType::Fn, SigType::Fn) => 1.0,
+        (_, SigType::Any) => GENERIC_CANDIDATE_SCORE,
+        (SigType::Named { path: qp, args: qa }, SigType::Named { path: cp, args: ca }) => {
+            unify_named(qp, qa, cp, ca)
+        }
+        (
+            SigType::Ref {
+                mutable: qm,
+                inner: qi,
+            },
+            SigType::Ref {
+                mutable: cm,
+                inner: ci,
+            },
+        )
+        | (
+            SigType::Ptr {
+                mutable: qm,
+                inner: qi,
+            },
+            SigType::Ptr {
+                mutable: cm,
+                inner: ci,
+            },
+        ) => {
+            let mutability = if qm == cm { 1.0 } else { 0.7 };
+            unify(qi, ci) * mutability
+        }
+        (SigType::Ref { inner, .. }, c) => unify(inner, c) * AUTOREF_PENALTY,
+        (q, SigType::Ref { inner, .. }) => unify(q, inner) * AUTOREF_PENALTY,
+        (SigType::Slice(q), SigType::Slice(c)) => unify(q, c),
+        (SigType::Tuple(q), SigType::Tuple(c)) if q.len() == c.len() => {
+            if q.is_empty() {
+                1.0
+            } else {
+                q.iter().zip(c).map(|(q, c)| unify(q, c)).sum::<f32>() / q.len() as f32
+            }
+        }
+        _ => 0.0,
+    }
+}
+
+fn unify_named(
+    q_path: &[String],
+    q_args: &[SigType],
+    c_path: &[String],
+    c_args: &[SigType],
+) -> f32 {
+    let (Some(q_name), Some(c_name)) = (q_path.last(), c_path.last()) else {
+        return 0.0;
+    };
+    if !q_name.eq_ignore_ascii_case(c_name) {
+        return 0.0;
+    }
+
+    // Query qualifiers (`io` in `io::Error`) must appear, in order, in the
+    // candidate's canonical path (`std::io::error::Error`).
+    let mut remaining = c_path[..c_path.len() - 1].iter();
+    for qualifier in &q_path[..q_path.len() - 1] {
+        if !remaining.any(|segment| segment == qualifier) {
+            return 0.0;
+        }
+    }
+
+    if q_args.is_empty() {
+        return 1.0;
+    }
+
+    // Missing candidate args (e.g. `io::Result<T>` against `Result<T, E>`)
+    // count as half-matches rather than failures.
+    let args_score = q_args
+        .iter()
+        .enumerate()
+        .map(|(i, q)| c_args.get(i).map_or(0.5, |c| unify(q, c)))
+        .sum::<f32>()
+        / q_args.len() as f32;
+    0.5f32.mul_add(args_score, 0.5)
+}
+
+/// Lexical tokens of the signature query language.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Lifetime,
+    PathSep,
+    Arrow,
+    Punct(char),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ident(name) => f.write_str(name),
+            Self::Lifetime => f.write_str("'lifetime"),
+            Self::PathSep => f.write_str("::"),
+            Self::Arrow => f.write_str("->"),
+            Self::Punct(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+fn lex(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(i, next)) = chars.peek() {
+                    if next.is_alphanumeric() || next == '_' {
+                        end = i + next.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(input[start..end].to_string()));
+            }
+            '\'' => {
+                while chars
+                    .peek()
+                    .is_some_and(|(_, next)| next.is_alphanumeric() || *next == '_')
+                {
+                    chars.next();
+                }
+                tokens.push(Token::Lifetime);
+            }
+            ':' if chars.peek().is_some_and(|(_, next)| *next == ':') => {
+                chars.next();
+                tokens.push(Token::PathSep);
+            }
+            '-' if chars.peek().is_some_and(|(_, next)| *next == '>') => {
+                chars.next();
+                tokens.push(Token::Arrow);
+            }
+            '&' | '*' | '<' | '>' | '(' | ')' | '[' | ']' | ',' | ';' | '=' | '+' | '!' => {
+                tokens.push(Token::Punct(c));
+            }
+            other => return Err(format!("unexpected character '{other}'")),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over [`Token`]s.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, expected: &Token) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_ident(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(name)) if name == keyword) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Punct(c)) if c == expected => Ok(()),
+            Some(other) => Err(format!("expected '{expected}', found '{other}'")),
+            None => Err(format!("expected '{expected}', found end of query")),
+        }
+    }
+
+    /// `query := [params] ["->" type]`
+    fn parse_query(&mut self) -> Result<SignatureQuery, String> {
+        let inputs = if self.peek() == Some(&Token::Arrow) {
+            None
+        } else {
+            Some(self.parse_params()?)
+        };
+
+        let output = if self.eat(&Token::Arrow) {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+
+        Ok(SignatureQuery { inputs, output })
+    }
+
+    /// `params := "fn"? "(" types ")" | types`
+    ///
+    /// A leading parenthesized list that spans the whole parameter side is a
+    /// parameter list, not a tuple: `(&str, usize) -> bool`.
+    fn parse_params(&mut self) -> Result<Vec<SigType>, String> {
+        let explicit_fn = self.eat_ident("fn");
+        if (explicit_fn || self.peek() == Some(&Token::Punct('(')))
+            && self.closing_paren_ends_params()
+        {
+            self.expect('(')?;
+            let params = self.parse_type_list(')')?;
+            self.expect(')')?;
+            return Ok(params);
+        }
+        if explicit_fn {
+            return Err("expected '(' after 'fn'".to_string());
+        }
+
+        let mut params = vec![self.parse_type()?];
+        while self.eat(&Token::Punct(',')) {
+            params.push(self.parse_type()?);
+        }
+        Ok(params)
+    }
+
+    /// True if the paren at the cursor closes right before `->` or the end of input.
+    fn closing_paren_ends_params(&self) -> bool {
+        let mut depth = 0usize;
+        for (offset, token) in self.tokens[self.pos..].iter().enumerate() {
+            match token {
+                Token::Punct('(' | '<' | '[') => depth += 1,
+                Token::Punct(')' | '>' | ']') => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        let after = self.tokens.get(self.pos + offset + 1);
+                        return matches!(after, None | Some(Token::Arrow));
+                    }
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+
+    fn parse_type_list(&mut self, close: char) -> Result<Vec<SigType>, String> {
+        let mut types = Vec::new();
+        while self.peek() != Some(&Token::Punct(close)) {
+            types.push(self.parse_type()?);
+            if !self.eat(&Token::Punct(',')) {
+                break;
+            }
+        }
+        Ok(types)
+    }
+
+    fn parse_type(&mut self) -> Result<SigType, String> {
+        match self.next() {
+            Some(Token::Punct('&')) => {
+                self.eat(&Token::Lifetime);
+                let mutable = self.eat_ident("mut");
+                Ok(SigType::Ref {
+                    mutable,
+                    inner: Box::new(self.parse_type()?),
+                })
+            }
+            Some(Token::Punct('*')) => {
+                let mutable = if self.eat_ident("mut") {
+                    true
+                } else if self.eat_ident("const") {
+                    false
+                } else {
+                    return Err("expected 'const' or 'mut' after '*'".to_string());
+                };
+                Ok(SigType::Ptr {
+                    mutable,
+                    inner: Box::new(self.parse_type()?),
+                })
+            }
+            Some(Token::Punct('[')) => {
+                let inner = self.parse_type()?;
+                if self.eat(&Token::Punct(';')) {
+                    // Array length: skip to the closing bracket.
+                    while !matches!(self.peek(), None | Some(Token::Punct(']'))) {
+                        self.pos += 1;
+                    }
+                }
+                self.expect(']')?;
+                Ok(SigType::Slice(Box::new(inner)))
+            }
+            Some(Token::Punct('(')) => {
+                let mut types = Vec::new();
+                let mut trailing_comma = false;
+                while self.peek() != Some(&Token::Punct(')')) {
+                    types.push(self.parse_type()?);
+                    trailing_comma = self.eat(&Token::Punct(','));
+                    if !trailing_comma {
+                        break;
+                    }
+                }
+                self.expect(')')?;
+                if types.len() == 1 && !trailing_comma {
+                    Ok(types.pop().unwrap_or(SigType::Any))
+                } else {
+                    Ok(SigType::Tuple(types))
+                }
+            }
+            Some(Token::Punct('!')) => Ok(SigType::named(vec!["!".to_string()], Vec::new())),
+            Some(Token::Ident(name)) => match name.as_str() {
+                "_" => Ok(SigType::Any),
+                "impl" | "dyn" => {
+                    let bound = self.parse_path()?;
+                    // Only the first bound is significant for matching.
+                    while self.eat(&Token::Punct('+')) {
+                        if !self.eat(&Token::Lifetime) {
+                            self.parse_path()?;
+                        }
+                    }
+                    Ok(bound)
+                }
+                "fn" => {
+                    self.expect('(')?;
+                    self.parse_type_list(')')?;
+                    self.expect(')')?;
+                    if self.eat(&Token::Arrow) {
+                        self.parse_type()?;
+                    }
+                    Ok(SigType::Fn)
+                }
+                _ => {
+                    self.pos -= 1;
+                    self.parse_path()
+                }
+            },
+            Some(other) => Err(format!("unexpected '{other}'")),
+            None => Err("expected a type, found end of query".to_string()),
+        }
+    }
+
+    /// `path := ident ("::" ident)* ["<" args ">" | "(" types ")" ["->" type]]`
+    fn parse_path(&mut self) -> Result<SigType, String> {
+        let mut segments = Vec::new();
+        loop {
+            match self.next() {
+                Some(Token::Ident(name)) => segments.push(name),
+                Some(other) => return Err(format!("expected a type name, found '{other}'")),
+                None => return Err("expected a type name, found end of query".to_string()),
+            }
+            if !self.eat(&Token::PathSep) {
+                break;
+            }
+        }
+
+        if let [single] = segments.as_slice()
+            && is_type_parameter(single)
+        {
+            return Ok(SigType::Any);
+        }
+
+        let mut args = Vec::new();
+        if self.eat(&Token::Punct('<')) {
+            while self.peek() != Some(&Token::Punct('>')) {
+                if !self.eat(&Token::Lifetime) {
+                    // Associated type bindings (`Item = u8`) contribute their type.
+                    if matches!(self.tokens.get(self.pos + 1), Some(Token::Punct('='))) {
+                        self.pos += 2;
+                    }
+                    args.push(self.parse_type()?);
+                }
+                if !self.eat(&Token::Punct(',')) {
+                    break;
+                }
+            }
+            self.expect('>')?;
+        } else if matches!(
+            segments.last().map(String::as_str),
+            Some("Fn" | "FnMut" | "FnOnce")
+        ) && self.eat(&Token::Punct('('))
+        {
+            args = self.parse_type_list(')')?;
+            self.expect(')')?;
+            args.push(if self.eat(&Token::Arrow) {
+                self.parse_type()?
+            } else {
+                SigType::Tuple(Vec::new())
+            });
+        }
+
+        Ok(SigType::named(segments, args))
+    }
+}
+
+/// Single uppercase letters (`T`, `E`, `K`) and `Self` are treated as type parameters.
+fn is_type_parameter(name: &str) -> bool {
+    name == "Self" || (name.len() == 1 && name.chars().all(|c| c.is_ascii_uppercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use rstest::rstest;
+
+    fn named(path: &str, args: Vec<SigType>) -> SigType {
+        SigType::named(path.split("::").map(str::to_string).collect(), args)
+    }
+
+    fn reference(inner: SigType) -> SigType {
+        SigType::Ref {
+            mutable: false,
+            inner: Box::new(inner),
+        }
+    }
+
+    #[test]
+    fn parses_params_and_return_type() {
+        let_assert!(Ok(query) = parse_signature_query("&str -> Result<_, io::Error>"));
+        check!(query.inputs == Some(vec![reference(named("str", vec![]))]));
+        check!(
+            query.output
+                == Some(named(
+                    "Result",
+                    vec![SigType::Any, named("io::Error", vec![])]
+                ))
+        );
+    }
+
+    #[rstest]
+    #[case("(&str, usize) -> bool")]
+    #[case("fn(&str, usize) -> bool")]
+    #[case("&str, usize -> bool")]
+    fn parameter_list_forms_are_equivalent(#[case] input: &str) {
+        let_assert!(Ok(query) = parse_signature_query(input));
+        check!(
+            query.inputs
+                == Some(vec![
+                    reference(named("str", vec![])),
+                    named("usize", vec![])
+                ])
+        );
+        check!(query.output == Some(named("bool", vec![])));
+    }
+
+    #[test]
+    fn return_only_query_leaves_inputs_unconstrained() {
+        let_assert!(Ok(query) = parse_signature_query("-> impl Iterator<Item = u8>"));
+        check!(query.inputs.is_none());
+        check!(query.output == Some(named("Iterator", vec![named("u8", vec![])])));
+    }
+
+    #[test]
+    fn tuple_return_type_is_not_a_parameter_list() {
+        let_assert!(Ok(query) = parse_signature_query("&str -> (String, usize)"));
+        check!(
+            query.output
+                == Some(SigType::Tuple(vec![
+                    named("String", vec![]),
+                    named("usize", vec![])
+                ]))
+        );
+    }
+
+    #[test]
+    fn single_letter_names_are_wildcards() {
+        let_assert!(Ok(query) = parse_signature_query("Vec<T> -> Option<&'a T>"));
+        check!(query.inputs == Some(vec![named("Vec", vec![SigType::Any])]));
+        check!(query.output == Some(named("Option", vec![reference(SigType::Any)])));
+    }
+
+    #[rstest]
+    #[case("&str ->")]
+    #[case("Vec<u8")]
+    #[case("&str $ usize")]
+    #[case("fn &str")]
+    fn malformed_queries_are_rejected(#[case] input: &str) {
+        let_assert!(Err(QueryError::InvalidSignature { .. }) = parse_signature_query(input));
+    }
+
+    #[test]
+    fn empty_query_is_rejected() {
+        let_assert!(Err(QueryError::EmptyQuery) = parse_signature_query("   "));
+    }
+
+    #[test]
+    fn qualifiers_must_appear_in_candidate_path() {
+        let candidate = named("std::io::error::Error", vec![]);
+        check!(unify(&named("io::Error", vec![]), &candidate) > 0.99);
+        check!(unify(&named("fmt::Error", vec![]), &candidate) < 0.01);
+        check!(unify(&named("Error", vec![]), &candidate) > 0.99);
+    }
+
+    #[test]
+    fn generic_candidates_match_with_reduced_score() {
+        let score = unify(&named("String", vec![]), &SigType::Any);
+        check!(score > 0.0);
+        check!(score < 1.0);
+    }
+
+    #[test]
+    fn exact_match_outranks_autoref_match() {
+        let query = reference(named("str", vec![]));
+        let exact = unify(&query, &reference(named("str", vec![])));
+        let autoref = unify(&query, &named("str", vec![]));
+        check!(exact > autoref);
+        check!(autoref > 0.0);
+    }
+
+    #[test]
+    fn extra_candidate_params_lower_the_score() {
+        let query = SignatureQuery {
+            inputs: Some(vec![named("usize", vec![])]),
+            output: None,
+        };
+        let exact = CandidateSignature {
+            inputs: vec![named("usize", vec![])],
+            output: SigType::Tuple(vec![]),
+        };
+        let extra = CandidateSignature {
+            inputs: vec![named("usize", vec![]), named("bool", vec![])],
+            output: SigType::Tuple(vec![]),
+        };
+        let_assert!(Some(exact_score) = score_signature(&query, &exact));
+        let_assert!(Some(extra_score) = score_signature(&query, &extra));
+        check!(exact_score > extra_score);
+    }
+
+    #[test]
+    fn missing_parameter_type_rejects_candidate() {
+        let query = SignatureQuery {
+            inputs: Some(vec![named("PathBuf", vec![])]),
+            output: None,
+        };
+        let candidate = CandidateSignature {
+            inputs: vec![named("usize", vec![])],
+            output: SigType::Tuple(vec![]),
+        };
+        check!(score_signature(&query, &candidate).is_none());
+    }
+}
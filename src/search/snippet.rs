@@ -0,0 +1,247 @@
+//! Doc snippets showing where a text query matched an item.
+
+use super::index::{MIN_EXPANSION_LEN, fuzzy_distance, max_edits};
+use super::query::{Qualifier, SearchQuery};
+use super::tokenize::{Token, tokenize};
+use rust_stemmers::{Algorithm, Stemmer};
+use std::{collections::HashSet, ops::Range};
+
+/// Bytes of docs a snippet aims to show, before widening to whole words.
+const SNIPPET_LEN: usize = 160;
+
+/// Marks docs left out at either end of a snippet.
+const ELLIPSIS: &str = "...";
+
+/// A window of an item's docs, with the words a query matched marked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Snippet {
+    /// The docs in the window, with runs of whitespace collapsed to a space
+    pub text: String,
+    /// Byte ranges of matched words in `text`, sorted and non-overlapping
+    pub highlights: Vec<Range<usize>>,
+}
+
+/// Picks the window of an item's docs that best matches a query.
+///
+/// Docs are tokenized and stemmed the same way as indexed text. A word is
+/// highlighted when it shares a query word's stem. Like the index, a query
+/// word whose stem the docs lack instead matches the words it is a prefix of
+/// or within its typo budget of (see [`max_edits`]).
+pub(crate) struct Highlighter {
+    stemmer: Stemmer,
+    /// Tokens of the query terms that may match in docs
+    tokens: Vec<Token>,
+}
+
+impl Highlighter {
+    /// A highlighter for the words and phrases of `query`, leaving out
+    /// exclusions and terms qualified to match outside the docs.
+    pub(crate) fn new(query: &SearchQuery) -> Self {
+        let stemmer = Stemmer::create(Algorithm::English);
+        let tokens = query
+            .terms
+            .iter()
+            .filter(|term| term.qualifier.is_none_or(|q| q == Qualifier::Doc))
+            .flat_map(|term| tokenize(&term.text, &stemmer))
+            .collect();
+        Self { stemmer, tokens }
+    }
+
+    /// The window of `docs` holding the most distinct query words, or `None`
+    /// if no query word occurs in them.
+    pub(crate) fn snippet(&self, docs: &str) -> Option<Snippet> {
+        if self.tokens.is_empty() {
+            return None;
+        }
+        let text = docs.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        // Matched words with the index of the query token they match
+        let words = tokenize(&text, &self.stemmer);
+        let mut matches: Vec<(Range<usize>, usize)> = Vec::new();
+        for (idx, query) in self.tokens.iter().enumerate() {
+            let exact: Vec<&Token> = words.iter().filter(|w| w.stem == query.stem).collect();
+            let found = if exact.is_empty() {
+                words
+                    .iter()
+                    .filter(|word| expands_to(query, word))
+                    .collect()
+            } else {
+                exact
+            };
+            matches.extend(found.into_iter().map(|word| (word.span.clone(), idx)));
+        }
+        if matches.is_empty() {
+            return None;
+        }
+        matches.sort_by_key(|(span, _)| (span.start, span.end));
+
+        let (first, last) = best_window(&matches);
+        let window = &matches[first..=last];
+        let matched_end = window.iter().map(|(span, _)| span.end).max().unwrap_or(0);
+        let (start, end) = widen(&text, window[0].0.start, matched_end);
+
+        let prefix = if start > 0 { ELLIPSIS } else { "" };
+        let suffix = if end < text.len() { ELLIPSIS } else { "" };
+        let mut highlights: Vec<Range<usize>> = Vec::new();
+        for (span, _) in &matches {
+            if span.start < start || span.end > end {
+                continue;
+            }
+            let shifted = span.start - start + prefix.len()..span.end - start + prefix.len();
+            match highlights.last_mut() {
+                Some(previous) if shifted.start <= previous.end => {
+                    previous.end = previous.end.max(shifted.end);
+                }
+                _ => highlights.push(shifted),
+            }
+        }
+
+        Some(Snippet {
+            text: format!("{prefix}{}{suffix}", &text[start..end]),
+            highlights,
+        })
+    }
+}
+
+/// Whether the query word `query` expands to the docs word `word`, by prefix
+/// or within its typo budget.
+fn expands_to(query: &Token, word: &Token) -> bool {
+    let len = query.word.chars().count();
+    if len < MIN_EXPANSION_LEN {
+        return false;
+    }
+    if word.word.starts_with(&query.word) {
+        return true;
+    }
+    let max_edits = max_edits(len);
+    max_edits > 0 && fuzzy_distance(&query.word, len, &word.word, max_edits).is_some()
+}
+
+/// First and last of the `matches` spanning at most [`SNIPPET_LEN`] bytes
+/// that cover the most distinct query words, then the most matches. Earlier
+/// windows win ties.
+fn best_window(matches: &[(Range<usize>, usize)]) -> (usize, usize) {
+    let mut best = (0, 0);
+    let mut best_score = (0, 0);
+    for (first, (span, _)) in matches.iter().enumerate() {
+        let window: Vec<_> = matches[first..]
+            .iter()
+            .take_while(|(next, _)| next.end - span.start <= SNIPPET_LEN)
+            .collect();
+        let distinct: HashSet<usize> = window.iter().map(|(_, matched)| *matched).collect();
+        let score = (distinct.len(), window.len());
+        if score > best_score {
+            best_score = score;
+            best = (first, first + window.len() - 1);
+        }
+    }
+    best
+}
+
+/// Grow the byte range `start..end` of `text` to about [`SNIPPET_LEN`] bytes,
+/// centered on it, then out to whole words.
+fn widen(text: &str, start: usize, end: usize) -> (usize, usize) {
+    let slack = SNIPPET_LEN.saturating_sub(end - start);
+    let mut wide_start = start.saturating_sub(slack / 2);
+    let wide_end = (wide_start + SNIPPET_LEN).max(end).min(text.len());
+    wide_start = wide_start.min(wide_end.saturating_sub(SNIPPET_LEN));
+
+    let wide_start = floor_char_boundary(text, wide_start);
+    let wide_end = floor_char_boundary(text, wide_end);
+    (
+        text[..wide_start].rfind(' ').map_or(0, |space| space + 1),
+        text[wide_end..]
+            .find(' ')
+            .map_or(text.len(), |space| wide_end + space),
+    )
+}
+
+/// The closest char boundary of `text` at or before `idx`.
+const fn floor_char_boundary(text: &str, mut idx: usize) -> usize {
+    while !text.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::query::parse_search_query;
+    use assert2::{check, let_assert};
+
+    fn snippet_for(query: &str, docs: &str) -> Option<Snippet> {
+        let query = parse_search_query(query).expect("test query parses");
+        Highlighter::new(&query).snippet(docs)
+    }
+
+    /// The highlighted parts of `snippet`.
+    fn highlighted(snippet: &Snippet) -> Vec<&str> {
+        snippet
+            .highlights
+            .iter()
+            .map(|range| &snippet.text[range.clone()])
+            .collect()
+    }
+
+    #[test]
+    fn highlights_stemmed_matches() {
+        let_assert!(
+            Some(snippet) = snippet_for("parse", "Parses the input.\n\nParsing fails on EOF.")
+        );
+        check!(snippet.text == "Parses the input. Parsing fails on EOF.");
+        check!(highlighted(&snippet) == ["Parses", "Parsing"]);
+    }
+
+    #[test]
+    fn picks_the_window_with_the_most_query_words() {
+        let filler = "Unrelated words about other things. ".repeat(10);
+        let docs = format!(
+            "Mentions a pool once.\n\n{filler}A connection pool that reuses connections.\n\n{filler}"
+        );
+        let_assert!(Some(snippet) = snippet_for("connection pool", &docs));
+        check!(snippet.text.starts_with(ELLIPSIS));
+        check!(snippet.text.ends_with(ELLIPSIS));
+        check!(
+            snippet
+                .text
+                .contains("A connection pool that reuses connections.")
+        );
+        check!(highlighted(&snippet) == ["connection", "pool", "connections"]);
+    }
+
+    #[test]
+    fn adjacent_subword_matches_merge() {
+        let_assert!(Some(snippet) = snippet_for("connection pool", "Wraps a ConnectionPool."));
+        check!(highlighted(&snippet) == ["ConnectionPool"]);
+    }
+
+    #[test]
+    fn prefixes_and_typos_are_highlighted() {
+        let_assert!(Some(snippet) = snippet_for("deser", "Deserialize a value."));
+        check!(highlighted(&snippet) == ["Deserialize"]);
+        let_assert!(Some(snippet) = snippet_for("formater", "A type formatter."));
+        check!(highlighted(&snippet) == ["formatter"]);
+    }
+
+    #[test]
+    fn exact_matches_suppress_expansion() {
+        let_assert!(Some(snippet) = snippet_for("handle", "Calls handle_search to handle it."));
+        check!(highlighted(&snippet) == ["handle", "handle"]);
+    }
+
+    #[test]
+    fn terms_outside_the_docs_are_not_highlighted() {
+        check!(snippet_for("name:cache -disk", "A disk cache.").is_none());
+        check!(snippet_for("cache", "Holds nothing relevant.").is_none());
+        let_assert!(Some(snippet) = snippet_for("doc:cache -disk", "A disk cache."));
+        check!(highlighted(&snippet) == ["cache"]);
+    }
+
+    #[test]
+    fn multibyte_text_stays_on_char_boundaries() {
+        let docs = format!("{} needle {}", "é".repeat(120), "ü".repeat(120));
+        let_assert!(Some(snippet) = snippet_for("needle", &docs));
+        check!(highlighted(&snippet) == ["needle"]);
+    }
+}
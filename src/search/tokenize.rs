@@ -1,20 +1,19 @@
 //! Text tokenization and stemming utilities for search indexing.
 
-// TF-IDF scoring performs usize→f32 conversions for counts and lengths.
-// Precision loss for counts that fit in a 23-bit mantissa is acceptable
-// for relevance ranking.
-#![allow(clippy::cast_precision_loss)]
-
+use crate::format::TypeFormatter;
 use crate::item::ItemRef;
-use ahash::{AHashMap, AHasher};
+use ahash::AHasher;
 use rust_stemmers::{Algorithm, Stemmer};
 use rustdoc_types::{Item, ItemEnum};
 use std::{
     collections::HashMap,
+    fmt::Write,
     hash::{Hash, Hasher},
+    ops::Range,
 };
 
-use super::index::InvertedIndex;
+use super::index::{InvertedIndex, Posting};
+use super::scoring::{Field, FieldCounts, FieldStats};
 
 /// Minimum token length for indexing. Set to 1 to allow short Rust types like `u8`, `i32`, `io`.
 const MIN_TOKEN_LENGTH: usize = 1;
@@ -32,14 +31,25 @@ type DocId = (u64, u32);
 /// Term hash for fast lookup
 type TermHash = u64;
 
-/// Builder for accumulating term frequencies before TF-IDF finalization.
+/// A word of tokenized text, lowercased, along with its stem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Token {
+    pub word: String,
+    pub stem: String,
+    /// Byte range of the word in the tokenized text
+    pub span: Range<usize>,
+}
+
+/// Builder for accumulating per-field term frequencies before the index is finalized.
 pub(crate) struct TermBuilder {
-    /// Flat map from (term_hash, doc_id) → raw TF score
-    term_docs: HashMap<(TermHash, DocId), f32>,
+    /// Flat map from (term_hash, doc_id) → positions as (field, offset within field)
+    term_docs: HashMap<(TermHash, DocId), Vec<(u8, u16)>>,
+    /// Every word seen, unstemmed, with the hash of its stem
+    words: HashMap<String, TermHash>,
     /// Map from doc_id to id_path (sequence of u32 IDs from crate root to item)
     shortest_paths: HashMap<DocId, Vec<u32>>,
-    /// Map from doc_id to document length (total term count for normalization)
-    doc_lengths: HashMap<DocId, usize>,
+    /// Map from doc_id to the number of terms in each field (for length normalization)
+    doc_lengths: HashMap<DocId, FieldCounts>,
     /// Reusable stemmer instance for English language stemming
     stemmer: Stemmer,
 }
@@ -48,6 +58,7 @@ impl Default for TermBuilder {
     fn default() -> Self {
         Self {
             term_docs: HashMap::default(),
+            words: HashMap::default(),
             shortest_paths: HashMap::default(),
             doc_lengths: HashMap::default(),
             stemmer: Stemmer::create(Algorithm::English),
@@ -56,105 +67,86 @@ impl Default for TermBuilder {
 }
 
 impl TermBuilder {
-    /// Add a term with its TF score for a specific document.
-    fn add(&mut self, term: &str, tf_score: f32, doc_id: DocId) {
+    /// Record an occurrence of a term at `offset` in one field of a specific document.
+    fn add(&mut self, term: &str, field: Field, offset: u16, doc_id: DocId) {
         let term_hash = hash_term(term);
-        *self.term_docs.entry((term_hash, doc_id)).or_insert(0.0) += tf_score;
+        self.term_docs
+            .entry((term_hash, doc_id))
+            .or_default()
+            .push((field as u8, offset));
     }
 
-    /// Extracts and adds terms from text with frequency counting.
-    /// TF score = term_count * base_score, where base_score weights importance (e.g., 2.0 for names, 1.0 for docs).
-    fn add_terms(&mut self, text: &str, doc_id: DocId, base_score: f32) {
-        let words = tokenize_and_stem(text, &self.stemmer);
-
-        // Count word frequencies using AHashMap for O(1) operations
-        let mut word_counts: AHashMap<String, usize> = AHashMap::with_capacity(words.len());
-        for word in words {
-            *word_counts.entry(word).or_insert(0) += 1;
+    /// Extracts and adds terms from text to one field of a document, recording
+    /// where each occurs so phrases can be matched.
+    /// Offsets continue from earlier text added to the same field.
+    fn add_terms(&mut self, text: &str, doc_id: DocId, field: Field) {
+        let tokens = tokenize(text, &self.stemmer);
+        let start = self
+            .doc_lengths
+            .get(&doc_id)
+            .map_or(0, |lengths| lengths[field as usize]);
+
+        let mut field_len = 0u16;
+        for Token { word, stem, .. } in tokens {
+            let offset = start.saturating_add(field_len);
+            self.add(&stem, field, offset, doc_id);
+            self.words.entry(word).or_insert_with(|| hash_term(&stem));
+            field_len = field_len.saturating_add(1);
         }
 
-        // Track document length for normalization
-        let doc_len: usize = word_counts.values().sum();
-        *self.doc_lengths.entry(doc_id).or_insert(0) += doc_len;
-
-        // TF = count * base_score
-        for (word, count) in word_counts {
-            let tf_score = (count as f32) * base_score;
-            self.add(&word, tf_score, doc_id);
-        }
+        // Track field length for normalization
+        let lengths = self.doc_lengths.entry(doc_id).or_default();
+        lengths[field as usize] = lengths[field as usize].saturating_add(field_len);
     }
 
-    /// Calculates IDF scores and produces the final searchable index.
-    /// Uses formula: TF-IDF = (1 + ln(tf_normalized)) * ln(total_docs / doc_freq),
-    /// where tf_normalized = tf / doc_length for length normalization.
+    /// Collects per-field statistics and produces the final searchable index.
+    ///
+    /// Postings keep raw term positions per field; BM25F scores are computed at
+    /// query time from them and the persisted field statistics (see
+    /// [`FieldStats::score`]), so field weights can change without a rebuild.
     pub(crate) fn finalize(self) -> InvertedIndex {
-        type GroupedDocs = HashMap<TermHash, Vec<(DocId, f32)>>;
+        type GroupedDocs = HashMap<TermHash, Vec<Posting>>;
 
         let start = std::time::Instant::now();
-        let total_docs = self.shortest_paths.len() as f32;
-
-        // Calculate average document length for normalization
-        let total_length: usize = self.doc_lengths.values().sum();
-        let avg_doc_length = if self.doc_lengths.is_empty() {
-            1.0
-        } else {
-            total_length as f32 / self.doc_lengths.len() as f32
-        };
 
         // Sort shortest_paths by doc_id for deterministic output
         let mut sorted_paths: Vec<_> = self.shortest_paths.into_iter().collect();
         sorted_paths.sort_by_key(|(doc_id, _)| *doc_id);
 
-        // Build id_set mapping from doc_id to array index
+        // Build id_set mapping from doc_id to array index, with each document's field lengths
         let mut id_set: HashMap<DocId, usize> = HashMap::new();
         let mut ids: Vec<Vec<u32>> = Vec::new();
+        let mut lengths: Vec<FieldCounts> = Vec::new();
+        let mut stats = FieldStats::default();
 
         for (doc_id, path) in sorted_paths {
-            let index = ids.len();
+            let doc_lengths = self.doc_lengths.get(&doc_id).copied().unwrap_or_default();
+            stats.add(&doc_lengths);
+            id_set.insert(doc_id, ids.len());
             ids.push(path);
-            id_set.insert(doc_id, index);
+            lengths.push(doc_lengths);
         }
 
-        // Group flat term_docs by term_hash
-        let mut grouped: GroupedDocs = HashMap::new();
+        // Group flat term_docs by term_hash, dropping documents without a path
+        let mut terms: GroupedDocs = HashMap::new();
         let total_term_doc_pairs = self.term_docs.len(); // Capture before move
-        for ((term_hash, doc_id), tf_score) in self.term_docs {
-            grouped
-                .entry(term_hash)
-                .or_default()
-                .push((doc_id, tf_score));
+        for ((term_hash, doc_id), mut positions) in self.term_docs {
+            if let Some(&idx) = id_set.get(&doc_id) {
+                positions.sort_unstable();
+                terms.entry(term_hash).or_default().push(Posting {
+                    doc: idx,
+                    positions,
+                });
+            }
         }
-
-        // Calculate TF-IDF scores
-        let mut terms: HashMap<TermHash, Vec<(usize, f32)>> = HashMap::new();
-
-        for (term_hash, doc_scores) in grouped {
-            // IDF = ln(total_docs / doc_freq)
-            let doc_freq = doc_scores.len() as f32;
-            let idf = (total_docs / doc_freq).ln();
-
-            // TF-IDF with length normalization
-            let mut tf_idf_scores: Vec<_> = doc_scores
-                .into_iter()
-                .filter_map(|(doc_id, tf_score)| {
-                    let doc_length = self.doc_lengths.get(&doc_id).copied().unwrap_or(1) as f32;
-                    // Normalize TF by document length relative to average
-                    let length_norm = doc_length / avg_doc_length;
-                    let tf_normalized = tf_score / length_norm.max(0.5); // Clamp to prevent over-penalization
-
-                    id_set
-                        .get(&doc_id)
-                        .map(|&idx| (idx, (1.0 + tf_normalized.ln()) * idf))
-                })
-                .collect();
-
-            // Sort descending by score
-            tf_idf_scores.sort_by(|(_, a), (_, b)| b.total_cmp(a));
-
-            terms.insert(term_hash, tf_idf_scores);
+        for postings in terms.values_mut() {
+            postings.sort_unstable_by_key(|posting| posting.doc);
         }
 
-        let index = InvertedIndex::new(terms, ids);
+        let mut words: Vec<(String, TermHash)> = self.words.into_iter().collect();
+        words.sort_unstable();
+
+        let index = InvertedIndex::new(terms, ids, lengths, stats, words);
 
         tracing::info!(
             "Built search index: {} unique terms, {} documents, {} term-document pairs in {:?}",
@@ -168,12 +160,22 @@ impl TermBuilder {
     }
 
     /// Recursively index an item and its children.
-    pub(crate) fn recurse(&mut self, item: ItemRef<'_, Item>, path: &[u32], track_path: bool) {
+    ///
+    /// `names` holds the names of the modules and types the item is nested in.
+    pub(crate) fn recurse<'a>(
+        &mut self,
+        item: ItemRef<'a, Item>,
+        path: &[u32],
+        names: &[&'a str],
+        track_path: bool,
+    ) {
         let id_num = item.id.0;
 
         let mut new_path = path.to_vec();
+        let mut new_names = names.to_vec();
         if track_path {
             new_path.push(id_num);
+            new_names.extend(item.name());
         }
 
         // Create document ID (crate_id, item_id)
@@ -187,15 +189,11 @@ impl TermBuilder {
                 .or_insert_with(|| new_path.clone());
         }
 
-        // Index name with higher weight (base_score: 2.0)
         if let Some(name) = item.name() {
-            self.add_terms(name, doc_id, 2.0);
-        }
-
-        // Index documentation with lower weight (base_score: 1.0)
-        if let Some(docs) = item.comment() {
-            self.add_terms(docs, doc_id, 1.0);
+            self.add_terms(name, doc_id, Field::Name);
         }
+        self.add_terms(&names.join(" "), doc_id, Field::Path);
+        self.add_details(item, doc_id);
 
         // Recurse into children
         match item.inner() {
@@ -204,22 +202,45 @@ impl TermBuilder {
                 for child in item.children().include_use().build() {
                     if let ItemEnum::Use(use_item) = child.inner() {
                         // Index re-exports under their public name
-                        self.index_reexport(child, use_item, &new_path);
+                        self.index_reexport(child, use_item, &new_path, &new_names);
                     } else {
-                        self.recurse(child, &new_path, true);
+                        self.recurse(child, &new_path, &new_names, true);
                     }
                 }
             }
             ItemEnum::Struct(_) | ItemEnum::Union(_) | ItemEnum::Trait(_) => {
                 // Index methods but don't include in path
                 for method in item.methods() {
-                    self.recurse(method, &new_path, false);
+                    self.recurse(method, &new_path, &new_names, false);
                 }
             }
             _ => {}
         }
     }
 
+    /// Index the signature and documentation of `item` into `doc_id`.
+    fn add_details(&mut self, item: ItemRef<'_, Item>, doc_id: DocId) {
+        if let ItemEnum::Function(function) = item.inner() {
+            let formatter = TypeFormatter::new(item.crate_index());
+            let mut signature = String::new();
+            for (param, ty) in &function.sig.inputs {
+                let _ = write!(signature, "{param} ");
+                let _ = formatter.write_type(&mut signature, ty);
+                signature.push(' ');
+            }
+            if let Some(output) = &function.sig.output {
+                let _ = formatter.write_type(&mut signature, output);
+            }
+            self.add_terms(&signature, doc_id, Field::Signature);
+        }
+
+        if let Some(docs) = item.comment() {
+            let (summary, body) = docs.split_once("\n\n").unwrap_or((docs, ""));
+            self.add_terms(summary, doc_id, Field::Summary);
+            self.add_terms(body, doc_id, Field::Body);
+        }
+    }
+
     /// Index a re-export item under its public name.
     ///
     /// This ensures that `pub use other::Thing` makes `Thing` searchable
@@ -229,6 +250,7 @@ impl TermBuilder {
         use_ref: ItemRef<'_, Item>,
         use_item: &rustdoc_types::Use,
         path: &[u32],
+        names: &[&str],
     ) {
         // Skip glob imports - they're expanded by the iterator
         if use_item.is_glob {
@@ -249,7 +271,8 @@ impl TermBuilder {
             .or_insert_with(|| reexport_path);
 
         // Index the re-export name (e.g., "Serialize" from `pub use serde_core::Serialize`)
-        self.add_terms(&use_item.name, doc_id, 2.0);
+        self.add_terms(&use_item.name, doc_id, Field::Name);
+        self.add_terms(&names.join(" "), doc_id, Field::Path);
 
         // Try to resolve the target to get its documentation
         let target = use_item
@@ -258,10 +281,8 @@ impl TermBuilder {
             .or_else(|| use_ref.query().resolve_path(&use_item.source, &mut vec![]));
 
         if let Some(target_item) = target {
-            // Index target's documentation under the re-export's identity
-            if let Some(docs) = target_item.comment() {
-                self.add_terms(docs, doc_id, 1.0);
-            }
+            // Index target's signature and documentation under the re-export's identity
+            self.add_details(target_item, doc_id);
         }
     }
 }
@@ -278,7 +299,8 @@ impl TermBuilder {
 /// - `subword_start`: Start of the current sub-component (e.g., "Server")
 ///
 /// This allows extracting both individual components and the full compound term.
-pub(crate) fn tokenize_and_stem(text: &str, stemmer: &Stemmer) -> Vec<String> {
+/// Each token keeps its lowercased word alongside the stem.
+pub(crate) fn tokenize(text: &str, stemmer: &Stemmer) -> Vec<Token> {
     let mut tokens = vec![];
 
     // State machine variables
@@ -312,7 +334,7 @@ pub(crate) fn tokenize_and_stem(text: &str, stemmer: &Stemmer) -> Vec<String> {
             // **Snake_case / hyphen-case boundary**: "parse_json" or "multi-line"
             // Extract the current subword (e.g., "parse" from "parse_json")
             if i.saturating_sub(subword_start) >= MIN_TOKEN_LENGTH {
-                index_token(&text[subword_start..i], &mut tokens, stemmer);
+                index_token(text, subword_start..i, &mut tokens, stemmer);
             }
             // Start a new subword after the delimiter
             subword_start_next_char = true;
@@ -320,11 +342,11 @@ pub(crate) fn tokenize_and_stem(text: &str, stemmer: &Stemmer) -> Vec<String> {
             // **Non-alphabetic character**: End of complete word
             // Extract last subword if different from word start (e.g., "Server" from "HttpServer123")
             if i.saturating_sub(subword_start) >= MIN_TOKEN_LENGTH && subword_start != word_start {
-                index_token(&text[subword_start..i], &mut tokens, stemmer);
+                index_token(text, subword_start..i, &mut tokens, stemmer);
             }
             // Extract complete word (e.g., "HttpServer" from "HttpServer123")
             if i.saturating_sub(word_start) >= MIN_TOKEN_LENGTH {
-                index_token(&text[word_start..i], &mut tokens, stemmer);
+                index_token(text, word_start..i, &mut tokens, stemmer);
             }
             // Start a new word after this non-alphabetic character
             word_start_next_char = true;
@@ -332,7 +354,7 @@ pub(crate) fn tokenize_and_stem(text: &str, stemmer: &Stemmer) -> Vec<String> {
             // **CamelCase boundary**: lowercase → uppercase (e.g., "http" → "S" in "httpServer")
             // Extract the previous subword (e.g., "http" before "Server")
             if i.saturating_sub(subword_start) >= MIN_TOKEN_LENGTH {
-                index_token(&text[subword_start..i], &mut tokens, stemmer);
+                index_token(text, subword_start..i, &mut tokens, stemmer);
             }
             // Start new subword at the uppercase character
             subword_start = i;
@@ -342,31 +364,47 @@ pub(crate) fn tokenize_and_stem(text: &str, stemmer: &Stemmer) -> Vec<String> {
     // **Handle final tokens** at end of string
     if !word_start_next_char {
         // Extract last subword if it's different from word start
-        let last_subword = &text[subword_start..];
-        if word_start != subword_start && last_subword.len() >= MIN_TOKEN_LENGTH {
-            index_token(last_subword, &mut tokens, stemmer);
+        if word_start != subword_start && text.len() - subword_start >= MIN_TOKEN_LENGTH {
+            index_token(text, subword_start..text.len(), &mut tokens, stemmer);
         }
         // Extract complete final word
-        let last_word = &text[word_start..];
-        if last_word.len() >= MIN_TOKEN_LENGTH {
-            index_token(last_word, &mut tokens, stemmer);
+        if text.len() - word_start >= MIN_TOKEN_LENGTH {
+            index_token(text, word_start..text.len(), &mut tokens, stemmer);
         }
     }
 
     tokens
 }
 
-/// Add a token using proper stemming algorithm, filtering out stop words.
-pub(crate) fn index_token(token: &str, tokens: &mut Vec<String>, stemmer: &Stemmer) {
-    let lowercase = token.to_lowercase();
+/// Add the token at `span` of `text` using proper stemming algorithm, filtering out stop words.
+pub(crate) fn index_token(
+    text: &str,
+    span: Range<usize>,
+    tokens: &mut Vec<Token>,
+    stemmer: &Stemmer,
+) {
+    let lowercase = text[span.clone()].to_lowercase();
 
     // Skip stop words
     if STOP_WORDS.contains(&lowercase.as_str()) {
         return;
     }
 
-    let stem = stemmer.stem(&lowercase);
-    tokens.push(stem.into_owned());
+    let stem = stemmer.stem(&lowercase).into_owned();
+    tokens.push(Token {
+        word: lowercase,
+        stem,
+        span,
+    });
+}
+
+/// Stems of [`tokenize`]d text.
+#[cfg(test)]
+pub(crate) fn tokenize_and_stem(text: &str, stemmer: &Stemmer) -> Vec<String> {
+    tokenize(text, stemmer)
+        .into_iter()
+        .map(|token| token.stem)
+        .collect()
 }
 
 /// Hashes a term for fast lookup (case-insensitive).
@@ -470,6 +508,18 @@ mod tests {
         let _tokens = tokenize_and_stem(input, &stemmer);
     }
 
+    #[rstest]
+    #[case("parse HttpServer", &["parse", "Http", "Server", "HttpServer"])]
+    #[case("snake_case, été", &["snake", "case", "snake_case", "été"])]
+    fn test_token_spans(#[case] input: &str, #[case] expected: &[&str]) {
+        let stemmer = Stemmer::create(Algorithm::English);
+        let words: Vec<&str> = tokenize(input, &stemmer)
+            .into_iter()
+            .map(|token| &input[token.span])
+            .collect();
+        check!(words == expected);
+    }
+
     #[test]
     fn test_empty_and_whitespace() {
         let stemmer = Stemmer::create(Algorithm::English);
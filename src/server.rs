@@ -1,19 +1,53 @@
 //! MCP server implementation and session state management.
 
+use crate::progress::{self, Progress, Reporter};
+use crate::resources::{MIME_TYPE, list_resources, read_resource, resource_templates};
 use crate::stdlib::StdlibDocs;
-use crate::tools::inspect_crate::{InspectCrateRequest, handle_inspect_crate};
-use crate::tools::inspect_item::{InspectItemRequest, handle_inspect_item};
-use crate::tools::search::{SearchRequest, handle_search};
-use crate::tools::set_workspace::{format_response, handle_set_workspace};
-use crate::worker::DocState;
+use crate::tools::cache::{
+    CacheRequest, StructuredCacheResult, handle_cache_structured, render_cache_result,
+};
+use crate::tools::find_implementors::{
+    FindImplementorsRequest, StructuredImplementorsResult, handle_find_implementors_structured,
+    render_implementors_result,
+};
+use crate::tools::inspect_crate::{
+    InspectCrateRequest, StructuredCrateResult, handle_inspect_crate_structured,
+    render_crate_result,
+};
+use crate::tools::inspect_item::{
+    InspectItemRequest, StructuredInspectResult, handle_inspect_item_structured,
+    render_inspect_result,
+};
+use crate::tools::search::{
+    SearchRequest, StructuredSearchResult, handle_search_structured, render_search_result,
+};
+use crate::tools::set_workspace::{
+    StructuredWorkspaceResult, handle_set_workspace, render_workspace_result,
+};
+use crate::tools::view_source::{ViewSourceRequest, handle_view_source_structured};
+use crate::worker::{DocEvent, DocState};
+use crate::workspace::root_uri_to_path;
 use rmcp::{
-    ServerHandler,
+    ErrorData as McpError, RoleServer, ServerHandler,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
-    model::{JsonObject, ServerCapabilities, ServerInfo},
+    model::{
+        CallToolResult, Content, JsonObject, ListResourceTemplatesResult, ListResourcesResult,
+        PaginatedRequestParams, ProgressNotificationParam, ReadResourceRequestParams,
+        ReadResourceResult, ResourceContents, ServerCapabilities, ServerInfo,
+    },
     schemars::{self, JsonSchema, generate::SchemaSettings},
+    service::{NotificationContext, Peer, RequestContext},
     tool, tool_handler, tool_router,
 };
+use serde::Serialize;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+/// How long documentation changes are gathered into one `resources/list_changed`.
+const LIST_CHANGED_SETTLE: Duration = Duration::from_millis(500);
 
 /// Parameters for set_workspace tool
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
@@ -56,20 +90,22 @@ impl ItemServer {
     }
 
     #[tool(
-        description = "Configure the workspace path for a Rust project. Automatically discovers workspace members and resolves all dependencies with their versions using cargo metadata."
+        description = "Configure the workspace path for a Rust project and make it the default for other tools. Automatically discovers workspace members and resolves all dependencies with their versions using cargo metadata. Previously configured workspaces stay loaded and can still be queried through the tools' `workspace` argument.",
+        output_schema = output_schema_for_type::<StructuredWorkspaceResult>()
     )]
     async fn set_workspace(
         &self,
         Parameters(SetWorkspaceRequest { path }): Parameters<SetWorkspaceRequest>,
-    ) -> std::result::Result<String, String> {
+    ) -> Result<CallToolResult, McpError> {
         // Get current workspace before changing it
         let old_workspace = self.state.working_directory().await;
 
         // Execute the logic, passing current workspace for change detection
         let (canonical_path, workspace_info, changed) =
-            handle_set_workspace(path, old_workspace.as_deref())
-                .await
-                .map_err(|e| e.user_message())?;
+            match handle_set_workspace(path, old_workspace.as_deref()).await {
+                Ok(result) => result,
+                Err(e) => return structured_result(Err(e.user_message()), render_workspace_result),
+            };
 
         // Update state
         let cargo_lock = canonical_path.join("Cargo.lock");
@@ -79,60 +115,115 @@ impl ItemServer {
             None
         };
 
-        // Clear cache when workspace changes
-        if changed {
-            tracing::info!("Workspace changed, clearing documentation cache");
-            self.state.clear_cache().await;
-        }
-
         self.state
             .set_workspace(canonical_path.clone(), workspace_info.clone(), cargo_lock)
             .await;
 
-        // Format response with old workspace and changed flag
-        let response = format_response(
+        // Summarize with old workspace and changed flag
+        let result = StructuredWorkspaceResult::new(
             &canonical_path,
             &workspace_info,
             old_workspace.as_deref(),
             changed,
         );
 
-        Ok(response)
+        structured_result(Ok(result), render_workspace_result)
     }
 
     #[tool(
         description = "Inspect crate-level information. Without a crate name, lists all crates with descriptions and stats. With a crate name, shows detailed structure including modules, exports, and item counts.",
-        input_schema = inline_schema_for_type::<InspectCrateRequest>()
+        input_schema = inline_schema_for_type::<InspectCrateRequest>(),
+        output_schema = output_schema_for_type::<StructuredCrateResult>()
     )]
     async fn inspect_crate(
         &self,
         Parameters(request): Parameters<InspectCrateRequest>,
-    ) -> std::result::Result<String, String> {
-        handle_inspect_crate(&self.state, request)
-            .await
-            .map_err(|e| e.to_string())
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let result = with_progress(
+            &context,
+            handle_inspect_crate_structured(&self.state, request),
+        )
+        .await
+        .map_err(|e| e.to_string());
+        structured_result(result, render_crate_result)
     }
 
     #[tool(
-        description = "Inspect a Rust item (struct, enum, function, trait, module, etc.) from the workspace or dependencies. Supports path queries like 'Vec', 'std::vec::Vec', or 'HashMap'. Returns formatted documentation with configurable detail levels.",
-        input_schema = inline_schema_for_type::<InspectItemRequest>()
+        description = "Inspect a Rust item (struct, enum, function, trait, module, etc.) from the workspace or dependencies. Supports path queries like 'Vec', 'std::vec::Vec', or 'HashMap', and associated items like 'Vec::push' or 'Iterator::Item', which show the owning impl or trait. Returns formatted documentation with configurable detail levels. Set features to document the queried crate with exactly those features, e.g. to see feature-gated items.",
+        input_schema = inline_schema_for_type::<InspectItemRequest>(),
+        output_schema = output_schema_for_type::<StructuredInspectResult>()
     )]
     async fn inspect_item(
         &self,
         Parameters(request): Parameters<InspectItemRequest>,
-    ) -> std::result::Result<String, String> {
-        handle_inspect_item(&self.state, request).await
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let result = with_progress(
+            &context,
+            handle_inspect_item_structured(&self.state, request),
+        )
+        .await;
+        structured_result(result, render_inspect_result)
     }
 
     #[tool(
-        description = "Search for Rust items within a crate using TF-IDF full-text search. Searches item names and documentation, returning ranked results by relevance.",
-        input_schema = inline_schema_for_type::<SearchRequest>()
+        description = "Search for Rust items within a crate using BM25F full-text search. Searches item names, module paths, function signatures and documentation, weighting name matches highest, and returns results ranked by relevance, each with the part of its docs best matching the query and the matched words in bold. Queries support \"quoted phrases\", -word exclusions, name:/doc:/path: qualifiers restricting a word or phrase to that part of an item, and kind:struct (e.g. '\"connection pool\" -deprecated kind:struct name:Pool'). Omit crate_name to search every crate with generated docs at once, optionally narrowed with scope ('members' or 'direct' dependencies) or a crates allowlist; set include_stdlib to add the standard library. Narrow results by kinds, path_prefix (e.g. 'tokio::sync'), deprecated, unsafe or async. Set features to search crate_name documented with exactly those features. Set mode to 'signature' to find functions and methods by type signature instead (e.g. '&str -> Result<_, io::Error>', '(&str, usize) -> bool', '-> impl Iterator'); generic parameters and '_' match any type.",
+        input_schema = inline_schema_for_type::<SearchRequest>(),
+        output_schema = output_schema_for_type::<StructuredSearchResult>()
     )]
     async fn search(
         &self,
         Parameters(request): Parameters<SearchRequest>,
-    ) -> std::result::Result<String, String> {
-        handle_search(&self.state, request).await
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let result = with_progress(&context, handle_search_structured(&self.state, request)).await;
+        structured_result(result, render_search_result)
+    }
+
+    #[tool(
+        description = "Find all implementations of a trait across the workspace, its dependencies, and the standard library. Accepts paths like 'serde::Serialize', 'std::fmt::Display', or a bare trait name. Lists implementing types with generics and where-clauses, grouping blanket impls and auto-trait impls separately.",
+        input_schema = inline_schema_for_type::<FindImplementorsRequest>(),
+        output_schema = output_schema_for_type::<StructuredImplementorsResult>()
+    )]
+    async fn find_implementors(
+        &self,
+        Parameters(request): Parameters<FindImplementorsRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let result = handle_find_implementors_structured(&self.state, request).await;
+        structured_result(result, render_implementors_result)
+    }
+
+    #[tool(
+        description = "Show the source code of a Rust item's definition, with a few lines of surrounding context. Accepts the same queries as inspect_item ('serde_json::from_str', 'Vec', 'HashMap'). Reads from the workspace, ~/.cargo/registry/src, or the rust-src component for standard library items.",
+        input_schema = inline_schema_for_type::<ViewSourceRequest>(),
+        output_schema = output_schema_for_type::<StructuredInspectResult>()
+    )]
+    async fn view_source(
+        &self,
+        Parameters(request): Parameters<ViewSourceRequest>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let result = with_progress(
+            &context,
+            handle_view_source_structured(&self.state, request),
+        )
+        .await;
+        structured_result(result, render_inspect_result)
+    }
+
+    #[tool(
+        description = "Inspect and manage the documentation cache. 'stats' (default) reports memory and disk usage, hit/miss counters and whether each crate's docs are fresh. 'clear' deletes a crate's cached docs (all crates without crate_name), 'regenerate' deletes and rebuilds them, and 'prune' deletes old stdlib toolchain indices and shared dependency docs by max_age_days or max_size_mb.",
+        input_schema = inline_schema_for_type::<CacheRequest>(),
+        output_schema = output_schema_for_type::<StructuredCacheResult>()
+    )]
+    async fn cache(
+        &self,
+        Parameters(request): Parameters<CacheRequest>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let result = with_progress(&context, handle_cache_structured(&self.state, request)).await;
+        structured_result(result, render_cache_result)
     }
 }
 
@@ -142,15 +233,186 @@ impl ServerHandler for ItemServer {
         ServerInfo::new(
             ServerCapabilities::builder()
                 .enable_tools()
+                .enable_resources()
+                .enable_resources_list_changed()
                 .build(),
         )
         .with_instructions(
             "rustdoc-mcp: A focused Rust documentation server with beautiful syntax formatting. \
              Automatically detects workspace and generates documentation on startup. \
              Standard library (std, core, alloc) is always available if rust-docs-json is installed. \
-             Use set_workspace to override automatic detection if needed.",
+             Use set_workspace to override automatic detection if needed. \
+             Documentation is also available as resources: rustdoc://{crate} for a crate \
+             and rustdoc://{crate}/{+path} for an item (e.g. rustdoc://serde/de/Deserialize).",
         )
     }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        Ok(ListResourcesResult::with_all_items(
+            list_resources(&self.state).await,
+        ))
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        Ok(ListResourceTemplatesResult::with_all_items(
+            resource_templates(),
+        ))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let text = read_resource(&self.state, &request.uri)
+            .await
+            .map_err(|e| McpError::resource_not_found(e, None))?;
+        Ok(ReadResourceResult::new(vec![
+            ResourceContents::text(text, request.uri).with_mime_type(MIME_TYPE),
+        ]))
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        if client_supports_roots(&context.peer) {
+            tokio::spawn(sync_roots(self.state.clone(), context.peer.clone()));
+        }
+
+        // Forward documentation changes as resources/list_changed until the client goes away.
+        let mut events = self.state.subscribe();
+        let peer = context.peer;
+        tokio::spawn(async move {
+            while next_resource_changes(&mut events).await {
+                if let Err(e) = peer.notify_resource_list_changed().await {
+                    tracing::debug!(error = %e, "Stopped forwarding resource list changes");
+                    break;
+                }
+            }
+        });
+    }
+
+    async fn on_roots_list_changed(&self, context: NotificationContext<RoleServer>) {
+        tokio::spawn(sync_roots(self.state.clone(), context.peer));
+    }
+}
+
+/// Wait for a documentation change, then take the changes that follow within
+/// [`LIST_CHANGED_SETTLE`], so a burst of them (such as a cold start generating
+/// every crate's docs) is forwarded as one notification.
+///
+/// Returns `false` once no more changes can arrive.
+async fn next_resource_changes(events: &mut broadcast::Receiver<DocEvent>) -> bool {
+    match events.recv().await {
+        Ok(DocEvent::WorkspaceChanged | DocEvent::DocsReady(_)) | Err(RecvError::Lagged(_)) => {}
+        Err(RecvError::Closed) => return false,
+    }
+    tokio::time::sleep(LIST_CHANGED_SETTLE).await;
+    loop {
+        match events.try_recv() {
+            Ok(_) | Err(TryRecvError::Lagged(_)) => {}
+            Err(TryRecvError::Empty | TryRecvError::Closed) => return true,
+        }
+    }
+}
+
+/// Whether the client exposes its roots (`roots/list`).
+fn client_supports_roots(peer: &Peer<RoleServer>) -> bool {
+    peer.peer_info()
+        .is_some_and(|info| info.capabilities.roots.is_some())
+}
+
+/// Ask the client for its roots and load the workspaces they contain.
+///
+/// Roots take precedence over the server's working directory, which is often the
+/// client's install directory rather than the user's project.
+async fn sync_roots(state: Arc<DocState>, peer: Peer<RoleServer>) {
+    let roots = match peer.list_roots().await {
+        Ok(result) => result.roots,
+        Err(e) => {
+            tracing::debug!(error = %e, "Client did not list its roots");
+            return;
+        }
+    };
+
+    let paths: Vec<PathBuf> = roots
+        .iter()
+        .filter_map(|root| {
+            let path = root_uri_to_path(&root.uri);
+            if path.is_none() {
+                tracing::debug!(uri = %root.uri, "Skipping non-file root");
+            }
+            path
+        })
+        .collect();
+
+    let workspaces = state.set_roots(&paths).await;
+    tracing::info!(
+        roots = paths.len(),
+        workspaces = workspaces.len(),
+        "Synchronized client roots"
+    );
+}
+
+/// Run a tool call, reporting its progress as `notifications/progress` if the
+/// client sent a progress token.
+async fn with_progress<F: Future>(context: &RequestContext<RoleServer>, call: F) -> F::Output {
+    let Some(token) = context.meta.get_progress_token() else {
+        return call.await;
+    };
+
+    // Reports arrive synchronously, so a task sends the notifications in order
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Progress>();
+    let peer = context.peer.clone();
+    tokio::spawn(async move {
+        // Progress must increase with every notification; the total is an estimate
+        // from the units left in the current step
+        let mut step = 0.0;
+        while let Some(progress) = rx.recv().await {
+            step += 1.0;
+            let mut param = ProgressNotificationParam::new(token.clone(), step)
+                .with_message(progress.to_string());
+            if let Some(remaining) = progress.remaining().and_then(|n| u32::try_from(n).ok()) {
+                param = param.with_total(step + f64::from(remaining));
+            }
+            if let Err(e) = peer.notify_progress(param).await {
+                tracing::debug!(error = %e, "Stopped reporting progress");
+                break;
+            }
+        }
+    });
+
+    let reporter: Reporter = Arc::new(move |progress| {
+        let _ = tx.send(progress.clone());
+    });
+    progress::scope(reporter, call).await
+}
+
+/// Build a tool result carrying both the rendered text and the structured value.
+///
+/// Errors are reported as tool errors (`isError: true`) with the message as text,
+/// matching how string-returning tools surface failures.
+fn structured_result<T: Serialize>(
+    result: std::result::Result<T, String>,
+    render: impl FnOnce(&T) -> String,
+) -> Result<CallToolResult, McpError> {
+    let value = match result {
+        Ok(value) => value,
+        Err(message) => return Ok(CallToolResult::error(vec![Content::text(message)])),
+    };
+
+    let json = serde_json::to_value(&value).map_err(|e| {
+        McpError::internal_error(format!("Failed to serialize tool result: {e}"), None)
+    })?;
+    let mut tool_result = CallToolResult::structured(json);
+    tool_result.content = vec![Content::text(render(&value))];
+    Ok(tool_result)
 }
 
 /// Creates a minimal fallback schema when generation fails.
@@ -201,3 +463,38 @@ pub fn inline_schema_for_type<T: JsonSchema>() -> Arc<JsonObject> {
         }
     }
 }
+
+/// Generate an output schema for a tool's structured content.
+///
+/// Uses the same settings as [`inline_schema_for_type`]. MCP requires the root of
+/// an output schema to be an object, which schemars leaves implicit for tagged
+/// enums (a bare `oneOf`), so `"type": "object"` is added when missing.
+pub fn output_schema_for_type<T: JsonSchema>() -> Arc<JsonObject> {
+    let mut schema = inline_schema_for_type::<T>();
+    if !schema.contains_key("type") {
+        Arc::make_mut(&mut schema).insert("type".to_string(), serde_json::json!("object"));
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CrateName;
+    use assert2::check;
+
+    #[tokio::test]
+    async fn bursts_of_doc_events_coalesce() {
+        let (tx, mut events) = broadcast::channel(16);
+        for name in ["serde", "tokio", "anyhow"] {
+            tx.send(DocEvent::DocsReady(CrateName::new_unchecked(name)))
+                .unwrap();
+        }
+
+        check!(next_resource_changes(&mut events).await);
+        check!(matches!(events.try_recv(), Err(TryRecvError::Empty)));
+
+        drop(tx);
+        check!(!next_resource_changes(&mut events).await);
+    }
+}
@@ -3,6 +3,8 @@
 //! Provides access to pre-generated rustdoc JSON for std, core, alloc, and other
 //! standard library crates from the `rust-docs-json` nightly component.
 
+use crate::cache::default_cache_root;
+use crate::memory::{CachedDocs, MemoryCache, MemoryKey};
 use crate::search::{CrateIndex, PreloadedCrate, QueryContext};
 use crate::types::CrateName;
 use crate::workspace::{CrateMetadata, CrateOrigin, WorkspaceContext};
@@ -11,7 +13,6 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Command;
 use std::sync::Arc;
-use tokio::sync::RwLock;
 
 /// Standard library crates available when `rust-docs-json` component is installed.
 pub const STDLIB_CRATES: &[&str] = &["std", "core", "alloc", "proc_macro", "test"];
@@ -23,8 +24,11 @@ pub const STDLIB_CRATES: &[&str] = &["std", "core", "alloc", "proc_macro", "test
 pub struct StdlibDocs {
     /// Path to the nightly sysroot
     sysroot: PathBuf,
-    /// Lazily loaded crate indices
-    loaded: RwLock<HashMap<CrateName, Arc<CrateIndex>>>,
+    /// Memory cache holding loaded crate indices, shared with the [`DocState`]
+    /// these docs are handed to so both count against one budget.
+    ///
+    /// [`DocState`]: crate::DocState
+    memory: Arc<MemoryCache>,
     /// Rustc version (for display purposes)
     rustc_version: String,
     /// Root directory for compiled search-index caches.
@@ -41,21 +45,11 @@ impl std::fmt::Debug for StdlibDocs {
             .field("sysroot", &self.sysroot)
             .field("rustc_version", &self.rustc_version)
             .field("cache_root", &self.cache_root)
-            .field("loaded_count", &self.loaded.blocking_read().len())
+            .field("memory", &self.memory)
             .finish()
     }
 }
 
-/// Default cache root: `<dirs::cache_dir()>/rustdoc-mcp`, falling back to
-/// `std::env::temp_dir().join("rustdoc-mcp")` when the platform has no known
-/// cache location. Never falls back to the current working directory — that
-/// would pollute user project directories.
-fn default_cache_root() -> PathBuf {
-    dirs::cache_dir()
-        .unwrap_or_else(std::env::temp_dir)
-        .join("rustdoc-mcp")
-}
-
 /// Maximum length for the version-token prefix in a cache directory name.
 ///
 /// Caps the on-disk path length to stay well under Windows `MAX_PATH` (260)
@@ -152,7 +146,7 @@ impl StdlibDocs {
 
         Ok(Self {
             sysroot,
-            loaded: RwLock::new(HashMap::new()),
+            memory: Arc::new(MemoryCache::from_env()),
             rustc_version,
             cache_root: default_cache_root(),
         })
@@ -169,6 +163,18 @@ impl StdlibDocs {
         self
     }
 
+    /// Hold loaded crates in `memory` instead of a cache of their own.
+    #[must_use]
+    pub fn with_memory(mut self, memory: Arc<MemoryCache>) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// The memory cache holding loaded crates.
+    pub const fn memory(&self) -> &Arc<MemoryCache> {
+        &self.memory
+    }
+
     /// Get the path to a stdlib crate's JSON documentation.
     pub fn doc_path(&self, crate_name: &str) -> PathBuf {
         self.sysroot
@@ -198,15 +204,12 @@ impl StdlibDocs {
 
     /// Load a stdlib crate's documentation (lazy, cached).
     ///
-    /// Returns a cached `Arc<CrateIndex>` if already loaded, otherwise
+    /// Returns a cached `Arc<CrateIndex>` if still in memory, otherwise
     /// loads and parses the JSON documentation.
     pub async fn load(&self, crate_name: &str) -> Result<Arc<CrateIndex>> {
-        // Check if already loaded
-        {
-            let loaded = self.loaded.read().await;
-            if let Some(index) = loaded.get(crate_name) {
-                return Ok(index.clone());
-            }
+        let key = MemoryKey::Stdlib(CrateName::new_unchecked(crate_name));
+        if let Some(cached) = self.memory.get(&key) {
+            return Ok(cached.index);
         }
 
         // Not loaded, need to load it
@@ -228,14 +231,7 @@ impl StdlibDocs {
 
         let index = Arc::new(index);
 
-        // Cache it
-        {
-            let mut loaded = self.loaded.write().await;
-            loaded.insert(
-                CrateName::new_unchecked(crate_name.to_string()),
-                index.clone(),
-            );
-        }
+        self.memory.insert(key, CachedDocs::resolved(index.clone()));
 
         tracing::debug!("Loaded stdlib docs for {}", crate_name);
 
@@ -300,19 +296,18 @@ impl StdlibDocs {
 
         let crate_name_key = CrateName::new_unchecked(crate_name);
 
-        let mut crate_info = HashMap::new();
-        crate_info.insert(
-            crate_name_key.clone(),
-            CrateMetadata {
-                origin: CrateOrigin::Standard,
-                name: crate_name_key.clone(),
-                version: Some("nightly".to_string()),
-                description: None,
-                dev_dep: false,
-                is_root_crate: false,
-                used_by: vec![],
-            },
-        );
+        let metadata = CrateMetadata {
+            origin: CrateOrigin::Standard,
+            name: crate_name_key.clone(),
+            version: Some("nightly".to_string()),
+            description: None,
+            dev_dep: false,
+            is_root_crate: false,
+            used_by: vec![],
+            features: None,
+            sources: None,
+        };
+        let crate_info = HashMap::from([(metadata.key(), metadata)]);
 
         // Sentinel workspace root: never touched, because `load_crate` in
         // QueryContext short-circuits on the preloaded entry below. A
@@ -322,6 +317,7 @@ impl StdlibDocs {
             members: vec![],
             crate_info,
             root_crate: None,
+            target_dir: PathBuf::from("/target"),
         };
 
         let mut preloaded = HashMap::new();
@@ -334,10 +330,10 @@ impl StdlibDocs {
             },
         );
 
-        Ok(QueryContext::with_preloaded(
-            Arc::new(stdlib_ctx),
-            preloaded,
-        ))
+        Ok(
+            QueryContext::with_preloaded(Arc::new(stdlib_ctx), preloaded)
+                .with_memory(self.memory.clone()),
+        )
     }
 }
 
@@ -426,7 +422,7 @@ mod tests {
         // a real toolchain. We only care about the path composition logic here.
         let stdlib = StdlibDocs {
             sysroot: PathBuf::from("/unused"),
-            loaded: RwLock::new(HashMap::new()),
+            memory: Arc::default(),
             rustc_version: "rustc 1.88.0-nightly (abc1234567 2025-01-15)".to_string(),
             cache_root: PathBuf::from("/tmp/test-cache"),
         };
@@ -446,7 +442,7 @@ mod tests {
         // cache_root values must produce distinct index paths for the same crate.
         let make = |root: &str| StdlibDocs {
             sysroot: PathBuf::from("/unused"),
-            loaded: RwLock::new(HashMap::new()),
+            memory: Arc::default(),
             rustc_version: "rustc 1.88.0-nightly (abc1234567 2025-01-15)".to_string(),
             cache_root: PathBuf::from(root),
         };
@@ -0,0 +1,832 @@
+//! Cache management tool handler.
+//!
+//! Documentation is cached at several levels: parsed crates in [`DocState`]'s
+//! memory cache, rustdoc JSON with its `.digests` and `.index` files in the
+//! workspace's doc directory, dependency docs in the user-level [`SharedCache`],
+//! and stdlib search indices in one directory per nightly toolchain. The `cache`
+//! tool (and `rustdoc-mcp cache` on the command line) reports on all of them and
+//! can clear or regenerate a crate's docs and prune old cache directories.
+
+use crate::cache::{CacheDir, SharedCache, cache_dirs, default_cache_root, load_digest};
+use crate::memory::MemoryUsage;
+use crate::search::index_metrics::{self, Counters};
+use crate::stdlib::StdlibDocs;
+use crate::worker::DocState;
+use crate::workspace::{
+    CrateMetadata, CrateOrigin, DocTarget, WorkspaceContext, auto_detect_workspace, current_digest,
+    remove_docs,
+};
+use futures::StreamExt;
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Digests computed concurrently when reporting crate freshness.
+const FRESHNESS_CONCURRENCY: usize = 8;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheAction {
+    /// Report cache sizes, hit/miss counters and per-crate freshness
+    #[default]
+    Stats,
+    /// Delete a crate's cached docs (every crate's, without `crate_name`)
+    Clear,
+    /// Delete a crate's cached docs and generate them again
+    Regenerate,
+    /// Delete stdlib toolchain and shared cache directories by age or size budget
+    Prune,
+}
+
+#[derive(Debug, Default, Deserialize, schemars::JsonSchema)]
+pub struct CacheRequest {
+    /// What to do: stats (default), clear, regenerate or prune
+    #[serde(default)]
+    pub action: CacheAction,
+    /// Crate to clear or regenerate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crate_name: Option<String>,
+    /// Workspace to report on, clear or regenerate: its path or any directory inside
+    /// it. Defaults to the workspace configured with set_workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    /// For prune: delete directories not modified for this many days
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<u64>,
+    /// For prune: delete the least recently modified directories until the rest fit
+    /// in this many megabytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
+}
+
+/// Structured outcome of a `cache` call, tagged by `action`.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum StructuredCacheResult {
+    Stats(Box<CacheStats>),
+    /// Crates whose cached docs were deleted.
+    Cleared {
+        crates: Vec<String>,
+        freed_bytes: u64,
+    },
+    /// A crate's docs were deleted and generated again.
+    Regenerated {
+        crate_name: String,
+        freed_bytes: u64,
+    },
+    /// Cache directories deleted by `prune`.
+    Pruned {
+        removed: Vec<CacheDirEntry>,
+        freed_bytes: u64,
+        /// Size of the prunable directories left.
+        remaining_bytes: u64,
+    },
+}
+
+/// Everything the server has cached.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct CacheStats {
+    /// Root of the workspace reported on, if any.
+    pub workspace: Option<String>,
+    /// Hit/miss counters since the server started.
+    pub counters: Counters,
+    /// Configured workspaces.
+    pub workspaces: usize,
+    /// Memory held by parsed docs of every workspace and the stdlib.
+    pub memory: MemoryUsage,
+    /// Crates of the workspace whose docs are held in memory.
+    pub memory_crates: usize,
+    /// Crates whose docs are being generated.
+    pub generating: usize,
+    /// Size of the docs and search indices of the workspace's crates.
+    pub workspace_bytes: u64,
+    /// Dependency docs shared by every workspace.
+    pub shared: CacheArea,
+    /// Stdlib search indices, one directory per nightly toolchain.
+    pub stdlib: CacheArea,
+    /// The workspace's crates, in generation priority order.
+    pub crates: Vec<CrateCacheEntry>,
+}
+
+/// A directory of cache entries.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct CacheArea {
+    pub path: String,
+    pub bytes: u64,
+    pub entries: Vec<CacheDirEntry>,
+}
+
+/// One cache directory.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct CacheDirEntry {
+    pub name: String,
+    pub bytes: u64,
+    /// Days since anything in the directory was modified.
+    pub age_days: u64,
+    /// Whether the directory is in use (the current toolchain's stdlib indices);
+    /// such directories are never pruned.
+    pub current: bool,
+}
+
+/// Cache state of one workspace crate.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct CrateCacheEntry {
+    pub name: String,
+    pub version: Option<String>,
+    pub origin: CrateOrigin,
+    pub freshness: Freshness,
+    pub doc_bytes: u64,
+    /// Size of the search index, if one was built.
+    pub index_bytes: Option<u64>,
+    pub in_memory: bool,
+}
+
+/// Whether a crate's docs on disk match what would be generated now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Freshness {
+    /// Generated from the current sources, version, features and toolchain.
+    Fresh,
+    /// Generated from something else; regenerated on the next request.
+    Stale,
+    /// Not generated yet.
+    Missing,
+    /// The current digest couldn't be computed.
+    Unknown,
+}
+
+/// Handles `cache` requests.
+///
+/// This is the string-returning wrapper. For programmatic access or tests,
+/// call [`handle_cache_structured`] directly.
+#[tracing::instrument(skip_all, fields(action = ?request.action))]
+pub async fn handle_cache(state: &Arc<DocState>, request: CacheRequest) -> Result<String, String> {
+    let result = handle_cache_structured(state, request).await?;
+    Ok(render_cache_result(&result))
+}
+
+/// Structured variant of [`handle_cache`].
+#[tracing::instrument(skip_all, fields(action = ?request.action))]
+pub async fn handle_cache_structured(
+    state: &Arc<DocState>,
+    request: CacheRequest,
+) -> Result<StructuredCacheResult, String> {
+    let workspace = request.workspace.as_deref();
+    match request.action {
+        CacheAction::Stats => {
+            let workspace = state.select_workspace(workspace).await?;
+            Ok(StructuredCacheResult::Stats(Box::new(
+                cache_stats(state, workspace).await,
+            )))
+        }
+        CacheAction::Clear => {
+            let workspace = required_workspace(state, workspace).await?;
+            clear(state, &workspace, request.crate_name.as_deref()).await
+        }
+        CacheAction::Regenerate => {
+            let crate_name = request.crate_name.ok_or("regenerate needs a crate_name")?;
+            let workspace = required_workspace(state, workspace).await?;
+            regenerate(state, &workspace, &crate_name).await
+        }
+        CacheAction::Prune => {
+            if request.max_age_days.is_none() && request.max_size_mb.is_none() {
+                return Err("prune needs max_age_days or max_size_mb".to_string());
+            }
+            let max_age = request
+                .max_age_days
+                .map(|days| Duration::from_secs(days.saturating_mul(SECONDS_PER_DAY)));
+            let max_bytes = request.max_size_mb.map(|mb| mb.saturating_mul(1024 * 1024));
+            Ok(prune(state, max_age, max_bytes).await)
+        }
+    }
+}
+
+async fn cache_stats(state: &Arc<DocState>, workspace: Option<WorkspaceContext>) -> CacheStats {
+    let crates = match &workspace {
+        Some(workspace) => crate_entries(state, workspace).await,
+        None => Vec::new(),
+    };
+    let workspace_bytes = crates
+        .iter()
+        .map(|entry| entry.doc_bytes + entry.index_bytes.unwrap_or(0))
+        .sum();
+
+    let shared = SharedCache::global();
+    let shared_dirs = shared.entries().await;
+    let (stdlib_root, current_toolchain) = stdlib_dirs(state);
+    let stdlib_dirs = cache_dirs(&stdlib_root).await;
+
+    CacheStats {
+        memory_crates: workspace
+            .as_ref()
+            .map_or(0, |ws| state.cached_crates(&ws.root).len()),
+        workspace: workspace.map(|ws| ws.root.display().to_string()),
+        counters: index_metrics::counters(),
+        workspaces: state.workspace_roots().await.len(),
+        memory: state.memory_usage(),
+        generating: state.generating_count().await,
+        workspace_bytes,
+        shared: cache_area(&shared.crates_dir(), &shared_dirs, None),
+        stdlib: cache_area(&stdlib_root, &stdlib_dirs, current_toolchain.as_deref()),
+        crates,
+    }
+}
+
+/// Cache state of every crate of `workspace`.
+async fn crate_entries(state: &DocState, workspace: &WorkspaceContext) -> Vec<CrateCacheEntry> {
+    let cargo_lock = state.cargo_lock_path(&workspace.root).await;
+    let in_memory = state.cached_crates(&workspace.root);
+
+    let entries: Vec<_> = workspace
+        .prioritized_crates()
+        .iter()
+        .filter_map(|name| workspace.get_crate(name.as_str()))
+        .map(|meta| {
+            let target = DocTarget::new(meta, workspace);
+            let in_memory = in_memory.contains(&target.doc_name());
+            crate_entry(meta, target, workspace, cargo_lock.as_deref(), in_memory)
+        })
+        .collect();
+
+    futures::stream::iter(entries)
+        .buffered(FRESHNESS_CONCURRENCY)
+        .collect()
+        .await
+}
+
+async fn crate_entry(
+    meta: &CrateMetadata,
+    target: DocTarget,
+    workspace: &WorkspaceContext,
+    cargo_lock: Option<&Path>,
+    in_memory: bool,
+) -> CrateCacheEntry {
+    let doc_dir = workspace.doc_dir();
+    let doc_name = target.doc_name();
+    let doc_bytes = file_size(&doc_name.doc_json_path(&doc_dir)).await;
+    let freshness = match doc_bytes {
+        None => Freshness::Missing,
+        Some(_) => freshness(&target, &workspace.root, &doc_dir, cargo_lock).await,
+    };
+    CrateCacheEntry {
+        name: meta.name.to_string(),
+        version: meta.version.clone(),
+        origin: meta.origin,
+        freshness,
+        doc_bytes: doc_bytes.unwrap_or(0),
+        index_bytes: file_size(&doc_name.index_path(&doc_dir)).await,
+        in_memory,
+    }
+}
+
+/// Compare the digest `target`'s docs were generated with to the current one.
+async fn freshness(
+    target: &DocTarget,
+    workspace_root: &Path,
+    doc_dir: &Path,
+    cargo_lock: Option<&Path>,
+) -> Freshness {
+    let Some(saved) = load_digest(&target.doc_name().digest_path(doc_dir)).await else {
+        return Freshness::Stale;
+    };
+    match current_digest(target, workspace_root, cargo_lock).await {
+        Ok(current) if current == saved => Freshness::Fresh,
+        Ok(_) => Freshness::Stale,
+        Err(e) => {
+            tracing::debug!(crate_name = %target.crate_name, error = ?e, "Failed to compute digest");
+            Freshness::Unknown
+        }
+    }
+}
+
+async fn file_size(path: &Path) -> Option<u64> {
+    tokio::fs::metadata(path).await.ok().map(|m| m.len())
+}
+
+/// Directory of the stdlib index caches, and the current toolchain's directory in it.
+fn stdlib_dirs(state: &DocState) -> (PathBuf, Option<PathBuf>) {
+    match state.stdlib() {
+        Some(stdlib) => {
+            let current = stdlib.index_cache_dir();
+            let root = current
+                .parent()
+                .map_or_else(|| current.clone(), Path::to_path_buf);
+            (root, Some(current))
+        }
+        None => (default_cache_root().join("stdlib"), None),
+    }
+}
+
+fn cache_area(path: &Path, dirs: &[CacheDir], current: Option<&Path>) -> CacheArea {
+    let now = SystemTime::now();
+    CacheArea {
+        path: path.display().to_string(),
+        bytes: dirs.iter().map(|dir| dir.bytes).sum(),
+        entries: dirs
+            .iter()
+            .map(|dir| dir_entry(dir, now, current == Some(dir.path.as_path())))
+            .collect(),
+    }
+}
+
+fn dir_entry(dir: &CacheDir, now: SystemTime, current: bool) -> CacheDirEntry {
+    let age = now.duration_since(dir.modified).unwrap_or_default();
+    CacheDirEntry {
+        name: dir.name(),
+        bytes: dir.bytes,
+        age_days: age.as_secs() / SECONDS_PER_DAY,
+        current,
+    }
+}
+
+/// Delete the cached docs of `crate_name`, or of every crate of `workspace`.
+async fn clear(
+    state: &DocState,
+    workspace: &WorkspaceContext,
+    crate_name: Option<&str>,
+) -> Result<StructuredCacheResult, String> {
+    let targets = match crate_name {
+        Some(crate_name) => vec![doc_target(workspace, crate_name)?],
+        None => workspace
+            .prioritized_crates()
+            .iter()
+            .filter_map(|name| workspace.get_crate(name.as_str()))
+            .map(|meta| DocTarget::new(meta, workspace))
+            .collect(),
+    };
+
+    let mut crates = Vec::new();
+    let mut freed_bytes = 0;
+    for target in &targets {
+        freed_bytes += remove_cached(state, workspace, target).await;
+        crates.push(target.doc_name().to_string());
+    }
+    tracing::info!(
+        crates = crates.len(),
+        freed_bytes,
+        "Cleared cached documentation"
+    );
+
+    Ok(StructuredCacheResult::Cleared {
+        crates,
+        freed_bytes,
+    })
+}
+
+/// Delete a crate's cached docs and generate them again.
+async fn regenerate(
+    state: &Arc<DocState>,
+    workspace: &WorkspaceContext,
+    crate_name: &str,
+) -> Result<StructuredCacheResult, String> {
+    let target = doc_target(workspace, crate_name)?;
+    let freed_bytes = remove_cached(state, workspace, &target).await;
+
+    let crate_name = target.doc_name().to_string();
+    state.get_docs(&workspace.root, &crate_name).await?;
+    tracing::info!(crate_name, "Regenerated documentation");
+
+    Ok(StructuredCacheResult::Regenerated {
+        crate_name,
+        freed_bytes,
+    })
+}
+
+/// The workspace a request names, or the active one; an error without either.
+async fn required_workspace(
+    state: &DocState,
+    path: Option<&str>,
+) -> Result<WorkspaceContext, String> {
+    state
+        .select_workspace(path)
+        .await?
+        .ok_or_else(|| "No workspace configured. Use set_workspace first.".to_string())
+}
+
+/// Drop a crate's docs from memory and disk. Returns the number of bytes freed.
+async fn remove_cached(state: &DocState, workspace: &WorkspaceContext, target: &DocTarget) -> u64 {
+    state
+        .invalidate(&workspace.root, target.doc_name().as_str())
+        .await;
+    remove_docs(target, &workspace.target_dir).await
+}
+
+/// Resolve a crate of `workspace` for clearing.
+fn doc_target(workspace: &WorkspaceContext, crate_name: &str) -> Result<DocTarget, String> {
+    if StdlibDocs::is_stdlib_crate(crate_name) {
+        return Err(format!(
+            "'{crate_name}' docs come from the rust-docs-json component; \
+             prune removes search indices of old toolchains"
+        ));
+    }
+    let meta = workspace
+        .get_crate(crate_name)
+        .ok_or_else(|| format!("Crate '{crate_name}' not found in workspace"))?;
+    Ok(DocTarget::new(meta, workspace))
+}
+
+/// Delete stdlib toolchain and shared cache directories older than `max_age`, then
+/// the least recently modified ones until the rest fit in `max_bytes`. The current
+/// toolchain's stdlib indices are kept.
+async fn prune(
+    state: &DocState,
+    max_age: Option<Duration>,
+    max_bytes: Option<u64>,
+) -> StructuredCacheResult {
+    let (stdlib_root, current_toolchain) = stdlib_dirs(state);
+    let mut dirs = cache_dirs(&stdlib_root).await;
+    dirs.retain(|dir| Some(&dir.path) != current_toolchain.as_ref());
+    dirs.extend(SharedCache::global().entries().await);
+
+    let now = SystemTime::now();
+    let selected = select_prunable(&dirs, now, max_age, max_bytes);
+
+    let mut removed = Vec::new();
+    let mut freed_bytes = 0;
+    for (index, dir) in dirs.iter().enumerate() {
+        if !selected.contains(&index) {
+            continue;
+        }
+        match tokio::fs::remove_dir_all(&dir.path).await {
+            Ok(()) => {
+                freed_bytes += dir.bytes;
+                removed.push(dir_entry(dir, now, false));
+            }
+            Err(e) => tracing::warn!(path = %dir.path.display(), error = %e, "Failed to prune"),
+        }
+    }
+    let total: u64 = dirs.iter().map(|dir| dir.bytes).sum();
+    tracing::info!(
+        removed = removed.len(),
+        freed_bytes,
+        "Pruned cache directories"
+    );
+
+    StructuredCacheResult::Pruned {
+        removed,
+        freed_bytes,
+        remaining_bytes: total - freed_bytes,
+    }
+}
+
+/// Indices of the `dirs` to delete: those older than `max_age`, then the oldest
+/// of the rest until the remainder fits in `max_bytes`.
+fn select_prunable(
+    dirs: &[CacheDir],
+    now: SystemTime,
+    max_age: Option<Duration>,
+    max_bytes: Option<u64>,
+) -> Vec<usize> {
+    let mut by_age: Vec<usize> = (0..dirs.len()).collect();
+    by_age.sort_by_key(|&index| dirs[index].modified);
+
+    let mut remaining: u64 = dirs.iter().map(|dir| dir.bytes).sum();
+    let mut selected = Vec::new();
+    for index in by_age {
+        let dir = &dirs[index];
+        let expired = max_age
+            .is_some_and(|max_age| now.duration_since(dir.modified).unwrap_or_default() > max_age);
+        let over_budget = max_bytes.is_some_and(|max_bytes| remaining > max_bytes);
+        if expired || over_budget {
+            remaining -= dir.bytes;
+            selected.push(index);
+        }
+    }
+    selected
+}
+
+/// Render a [`StructuredCacheResult`] into the human-readable MCP output format.
+pub fn render_cache_result(result: &StructuredCacheResult) -> String {
+    let mut output = String::new();
+    let _ = match result {
+        StructuredCacheResult::Stats(stats) => render_stats(&mut output, stats),
+        StructuredCacheResult::Cleared {
+            crates,
+            freed_bytes,
+        } => writeln!(
+            output,
+            "Cleared cached documentation for {} crate(s), freeing {}.{}",
+            crates.len(),
+            format_bytes(*freed_bytes),
+            if crates.len() == 1 {
+                format!(" ({})", crates[0])
+            } else {
+                String::new()
+            }
+        ),
+        StructuredCacheResult::Regenerated {
+            crate_name,
+            freed_bytes,
+        } => writeln!(
+            output,
+            "Regenerated documentation for {crate_name} (replaced {}).",
+            format_bytes(*freed_bytes)
+        ),
+        StructuredCacheResult::Pruned {
+            removed,
+            freed_bytes,
+            remaining_bytes,
+        } => render_pruned(&mut output, removed, *freed_bytes, *remaining_bytes),
+    };
+    output
+}
+
+fn render_stats(output: &mut String, stats: &CacheStats) -> std::fmt::Result {
+    let counters = &stats.counters;
+    let memory = &stats.memory;
+    writeln!(output, "Memory:")?;
+    writeln!(
+        output,
+        "  {} of {} budget in {} crates ({} stdlib)",
+        format_bytes(memory.used_bytes),
+        format_bytes(memory.budget_bytes),
+        memory.entries,
+        format_bytes(memory.stdlib_bytes)
+    )?;
+    writeln!(
+        output,
+        "  {} crates cached for the workspace ({} workspaces configured), {} generating",
+        stats.memory_crates, stats.workspaces, stats.generating
+    )?;
+    writeln!(
+        output,
+        "  {} hits, {} misses; {} docs generated, {} reused from the shared cache",
+        counters.memory_hits, counters.memory_misses, counters.generations, counters.shared_hits
+    )?;
+    writeln!(
+        output,
+        "  Search indices: {} built, {} loaded from disk, {} reused from memory",
+        counters.index_builds, counters.index_loads, counters.index_memory_loads
+    )?;
+    writeln!(output)?;
+
+    writeln!(output, "Disk:")?;
+    if let Some(workspace) = &stats.workspace {
+        writeln!(
+            output,
+            "  Workspace docs: {} ({workspace})",
+            format_bytes(stats.workspace_bytes)
+        )?;
+    }
+    render_area(output, "Shared dependency docs", &stats.shared)?;
+    render_area(output, "Stdlib search indices", &stats.stdlib)?;
+
+    if !stats.crates.is_empty() {
+        writeln!(output)?;
+        writeln!(output, "Crates ({}):", stats.crates.len())?;
+        for entry in &stats.crates {
+            let version = entry.version.as_deref().unwrap_or("unknown");
+            write!(
+                output,
+                "  • {} v{version}: {}",
+                entry.name,
+                freshness_label(entry.freshness)
+            )?;
+            if entry.freshness != Freshness::Missing {
+                write!(output, ", {} docs", format_bytes(entry.doc_bytes))?;
+            }
+            if let Some(index_bytes) = entry.index_bytes {
+                write!(output, ", {} index", format_bytes(index_bytes))?;
+            }
+            if entry.in_memory {
+                write!(output, ", in memory")?;
+            }
+            writeln!(output)?;
+        }
+    }
+    Ok(())
+}
+
+fn render_area(output: &mut String, label: &str, area: &CacheArea) -> std::fmt::Result {
+    writeln!(
+        output,
+        "  {label}: {} in {} directories ({})",
+        format_bytes(area.bytes),
+        area.entries.len(),
+        area.path
+    )?;
+    for entry in area.entries.iter().filter(|entry| entry.current) {
+        writeln!(
+            output,
+            "    current: {} ({})",
+            entry.name,
+            format_bytes(entry.bytes)
+        )?;
+    }
+    Ok(())
+}
+
+fn render_pruned(
+    output: &mut String,
+    removed: &[CacheDirEntry],
+    freed_bytes: u64,
+    remaining_bytes: u64,
+) -> std::fmt::Result {
+    if removed.is_empty() {
+        return writeln!(
+            output,
+            "Nothing to prune ({} in cache directories).",
+            format_bytes(remaining_bytes)
+        );
+    }
+    writeln!(
+        output,
+        "Pruned {} directories, freeing {} ({} left):",
+        removed.len(),
+        format_bytes(freed_bytes),
+        format_bytes(remaining_bytes)
+    )?;
+    for entry in removed {
+        writeln!(
+            output,
+            "  • {} ({}, {} days old)",
+            entry.name,
+            format_bytes(entry.bytes),
+            entry.age_days
+        )?;
+    }
+    Ok(())
+}
+
+const fn freshness_label(freshness: Freshness) -> &'static str {
+    match freshness {
+        Freshness::Fresh => "fresh",
+        Freshness::Stale => "stale",
+        Freshness::Missing => "not generated",
+        Freshness::Unknown => "unknown",
+    }
+}
+
+/// Format a byte count with a binary unit, e.g. `1.5 MB`.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut unit = 0;
+    let mut scaled = bytes;
+    let mut tenths = 0;
+    while scaled >= 1024 && unit < UNITS.len() - 1 {
+        tenths = (scaled % 1024) * 10 / 1024;
+        scaled /= 1024;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{scaled}.{tenths} {}", UNITS[unit])
+    }
+}
+
+/// Usage of the `rustdoc-mcp cache` subcommand.
+pub const CLI_USAGE: &str = "\
+Usage: rustdoc-mcp cache [ACTION]
+
+Actions:
+  stats                       Show cache sizes, counters and crate freshness (default)
+  clear [CRATE]               Delete a crate's cached docs, or every crate's
+  regenerate CRATE            Delete a crate's cached docs and generate them again
+  prune [--max-age-days N] [--max-size-mb N]
+                              Delete old stdlib toolchain and shared cache directories
+
+Crates are looked up in the workspace containing the current directory.";
+
+/// Parse the arguments following `rustdoc-mcp cache`.
+pub fn parse_cli_args(args: &[String]) -> Result<CacheRequest, String> {
+    let mut args = args.iter().map(String::as_str);
+    let action = match args.next() {
+        None | Some("stats") => CacheAction::Stats,
+        Some("clear") => CacheAction::Clear,
+        Some("regenerate") => CacheAction::Regenerate,
+        Some("prune") => CacheAction::Prune,
+        Some(other) => return Err(format!("Unknown cache action '{other}'")),
+    };
+    let mut request = CacheRequest {
+        action,
+        ..CacheRequest::default()
+    };
+
+    while let Some(arg) = args.next() {
+        match (request.action, arg) {
+            (CacheAction::Prune, "--max-age-days" | "--max-size-mb") => {
+                let value = args
+                    .next()
+                    .and_then(|value| value.parse::<u64>().ok())
+                    .ok_or_else(|| format!("{arg} needs a number"))?;
+                if arg == "--max-age-days" {
+                    request.max_age_days = Some(value);
+                } else {
+                    request.max_size_mb = Some(value);
+                }
+            }
+            (CacheAction::Clear | CacheAction::Regenerate, name)
+                if request.crate_name.is_none() && !name.starts_with('-') =>
+            {
+                request.crate_name = Some(name.to_string());
+            }
+            _ => return Err(format!("Unexpected argument '{arg}'")),
+        }
+    }
+    Ok(request)
+}
+
+/// Run `rustdoc-mcp cache` against the workspace containing the current directory.
+pub async fn run_cli(args: &[String]) -> Result<String, String> {
+    let request = parse_cli_args(args)?;
+
+    let stdlib = StdlibDocs::discover().ok().map(Arc::new);
+    let state = Arc::new(DocState::new(stdlib));
+    if let Some(path) = auto_detect_workspace().await {
+        let (working_dir, workspace, _changed) =
+            crate::tools::set_workspace::handle_set_workspace(path.display().to_string(), None)
+                .await
+                .map_err(|e| e.user_message())?;
+        let cargo_lock = workspace.root.join("Cargo.lock");
+        let cargo_lock = cargo_lock.exists().then_some(cargo_lock);
+        state
+            .set_workspace(working_dir, workspace, cargo_lock)
+            .await;
+    }
+
+    handle_cache(&state, request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use rstest::rstest;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_cli_actions() {
+        let_assert!(Ok(request) = parse_cli_args(&[]));
+        check!(request.action == CacheAction::Stats);
+
+        let_assert!(Ok(request) = parse_cli_args(&args(&["regenerate", "serde"])));
+        check!(request.action == CacheAction::Regenerate);
+        check!(request.crate_name.as_deref() == Some("serde"));
+
+        let_assert!(
+            Ok(request) = parse_cli_args(&args(&[
+                "prune",
+                "--max-age-days",
+                "30",
+                "--max-size-mb",
+                "512"
+            ]))
+        );
+        check!(request.max_age_days == Some(30));
+        check!(request.max_size_mb == Some(512));
+    }
+
+    #[rstest]
+    #[case::unknown_action(&["purge"])]
+    #[case::missing_number(&["prune", "--max-age-days"])]
+    #[case::bad_number(&["prune", "--max-size-mb", "lots"])]
+    #[case::two_crates(&["clear", "serde", "tokio"])]
+    #[case::crate_for_stats(&["stats", "serde"])]
+    fn rejects_bad_cli_args(#[case] input: &[&str]) {
+        check!(parse_cli_args(&args(input)).is_err());
+    }
+
+    fn dir(name: &str, mb: u64, days_old: u64, now: SystemTime) -> CacheDir {
+        CacheDir {
+            path: PathBuf::from(name),
+            bytes: mb * 1024 * 1024,
+            modified: now - Duration::from_secs(days_old * SECONDS_PER_DAY),
+        }
+    }
+
+    #[test]
+    fn prunes_by_age_and_size() {
+        let now = SystemTime::now();
+        let dirs = [
+            dir("recent", 10, 1, now),
+            dir("old", 10, 90, now),
+            dir("middle", 10, 20, now),
+        ];
+        let day = Duration::from_secs(SECONDS_PER_DAY);
+
+        check!(select_prunable(&dirs, now, Some(day * 30), None) == [1]);
+        check!(select_prunable(&dirs, now, None, Some(15 * 1024 * 1024)) == [1, 2]);
+        check!(select_prunable(&dirs, now, Some(day * 30), Some(100 * 1024 * 1024)) == [1]);
+        check!(select_prunable(&dirs, now, None, Some(100 * 1024 * 1024)).is_empty());
+    }
+
+    #[rstest]
+    #[case(0, "0 B")]
+    #[case(1023, "1023 B")]
+    #[case(1536, "1.5 KB")]
+    #[case(5 * 1024 * 1024, "5.0 MB")]
+    fn formats_bytes(#[case] bytes: u64, #[case] expected: &str) {
+        check!(format_bytes(bytes) == expected);
+    }
+}
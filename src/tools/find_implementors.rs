@@ -0,0 +1,456 @@
+//! Trait implementor lookup across workspace, dependency, and stdlib crates.
+//!
+//! # Structured and rendered APIs
+//!
+//! Follows the same two-layer pattern as [`crate::tools::search`]:
+//!
+//! - [`handle_find_implementors_structured`] returns a typed
+//!   [`StructuredImplementorsResult`] with every matching impl block grouped
+//!   into concrete, blanket, and synthetic auto-trait implementations.
+//! - [`handle_find_implementors`] wraps the structured variant and renders it
+//!   into the human-readable MCP output.
+//!
+//! # Trait matching
+//!
+//! Qualified queries (`serde::Serialize`) are first resolved through
+//! [`QueryContext::resolve_path`], which follows re-exports to the trait's
+//! definition path (`serde_core::ser::Serialize`). Impl blocks are then matched
+//! by comparing the `paths` entry of their `trait_` against that definition
+//! path. Bare names (`Display`) and stdlib paths that cannot be resolved fall
+//! back to matching on the trait's final path segment.
+
+use crate::format::TypeFormatter;
+use crate::search::{CrateIndex, QueryContext, parse_item_path};
+use crate::stdlib::StdlibDocs;
+use crate::types::CrateName;
+use crate::worker::DocState;
+use rmcp::schemars;
+use rustdoc_types::{Impl, ItemEnum, Type};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct FindImplementorsRequest {
+    /// Trait to look up (e.g., "serde::Serialize", "std::fmt::Display", "Iterator")
+    pub trait_path: String,
+    /// Maximum number of implementations to list per group (default: 50)
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    /// Workspace to query: its path or any directory inside it. Defaults to the
+    /// workspace configured with set_workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+const fn default_limit() -> usize {
+    50
+}
+
+/// Structured outcome of a `find_implementors` call.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct StructuredImplementorsResult {
+    /// The trait path as given by the caller.
+    pub query: String,
+    /// Definition paths of the traits that matched the query (e.g.
+    /// `serde_core::ser::Serialize`). More than one entry means a bare name
+    /// matched several distinct traits.
+    pub matched_traits: Vec<String>,
+    /// Names of the crates whose impl blocks were scanned.
+    pub crates_scanned: Vec<String>,
+    /// Impls for concrete types (`impl Serialize for MyType`).
+    pub implementations: ImplementorGroup,
+    /// Impls over a bare type parameter (`impl<T: Display> ToString for T`).
+    pub blanket: ImplementorGroup,
+    /// Compiler-synthesized auto-trait impls (`Send`, `Sync`, `Unpin`, ...).
+    pub auto: ImplementorGroup,
+}
+
+/// One group of implementors, truncated to the requested limit.
+#[derive(Debug, Clone, Default, Serialize, schemars::JsonSchema)]
+pub struct ImplementorGroup {
+    /// Entries kept after truncation, sorted by crate then type.
+    pub entries: Vec<Implementor>,
+    /// Total number of matching impls before truncation.
+    pub total: usize,
+}
+
+/// A single impl block implementing the requested trait.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct Implementor {
+    /// Crate that contains the impl block.
+    pub crate_name: String,
+    /// Definition path of the implemented trait.
+    pub trait_path: String,
+    /// The implementing type (e.g. `Vec<T>`, `&T`).
+    pub for_type: String,
+    /// Generic parameters of the impl block (e.g. `<T: Clone>`), empty when none.
+    pub generics: String,
+    /// Where clause of the impl block, if any.
+    pub where_clause: Option<String>,
+    /// True for negative impls (`impl !Send for Rc<T>`).
+    pub is_negative: bool,
+    /// Full impl header, e.g. `impl<T> Serialize for Vec<T> where T: Serialize`.
+    pub declaration: String,
+}
+
+/// How impl blocks are matched against the requested trait.
+#[derive(Debug)]
+enum TraitMatcher {
+    /// Match a fully-resolved definition path exactly.
+    Exact(Vec<String>),
+    /// Match on the trait's final segment, optionally restricted to a crate.
+    Name {
+        name: String,
+        crate_hint: Option<String>,
+    },
+}
+
+impl TraitMatcher {
+    fn matches(&self, segments: &[String]) -> bool {
+        match self {
+            Self::Exact(path) => segments == path.as_slice(),
+            Self::Name { name, crate_hint } => {
+                if segments.last() != Some(name) {
+                    return false;
+                }
+                let Some(hint) = crate_hint else {
+                    return true;
+                };
+                let Some(first) = segments.first() else {
+                    return false;
+                };
+                // std re-exports most of core and alloc, so any stdlib crate
+                // satisfies a stdlib hint.
+                first == hint
+                    || (StdlibDocs::is_stdlib_crate(hint) && StdlibDocs::is_stdlib_crate(first))
+            }
+        }
+    }
+}
+
+/// Handles find_implementors requests and renders the grouped implementor list.
+///
+/// This is the string-returning wrapper. For programmatic access or tests,
+/// call [`handle_find_implementors_structured`] directly.
+#[tracing::instrument(skip_all, fields(trait_path = %request.trait_path))]
+pub async fn handle_find_implementors(
+    state: &Arc<DocState>,
+    request: FindImplementorsRequest,
+) -> Result<String, String> {
+    let structured = handle_find_implementors_structured(state, request).await?;
+    Ok(render_implementors_result(&structured))
+}
+
+/// Structured variant of [`handle_find_implementors`].
+///
+/// Only crates whose rustdoc JSON is already on disk are scanned; this tool
+/// never triggers doc generation. Stdlib crates are included when the query
+/// names a stdlib trait or is a bare trait name.
+#[tracing::instrument(skip_all, fields(trait_path = %request.trait_path))]
+pub async fn handle_find_implementors_structured(
+    state: &Arc<DocState>,
+    request: FindImplementorsRequest,
+) -> Result<StructuredImplementorsResult, String> {
+    let path = parse_item_path(&request.trait_path);
+    let Some(name) = path
+        .path_components
+        .last()
+        .filter(|s| !s.is_empty())
+        .cloned()
+    else {
+        return Err("Empty trait path".to_string());
+    };
+    let first = path.path_components[0].clone();
+    let is_bare = path.path_components.len() == 1;
+    let targets_stdlib = !is_bare && StdlibDocs::is_stdlib_crate(&first);
+
+    let workspace = state.select_workspace(request.workspace.as_deref()).await?;
+    let stdlib = state.stdlib();
+
+    if workspace.is_none() && stdlib.is_none() {
+        return Err(
+            "No workspace configured and standard library docs not available.\n\n\
+             To configure a workspace:\n\
+             • Use set_workspace with a path to a Rust project\n\n\
+             To enable standard library docs:\n\
+             • Run: rustup component add rust-docs-json --toolchain nightly"
+                .to_string(),
+        );
+    }
+
+    // All async loading happens up front: QueryContext is not Send, so it must
+    // not be held across an await point.
+    let mut resolved: Option<Vec<String>> = None;
+    let mut stdlib_indexes: Vec<Arc<CrateIndex>> = Vec::new();
+    if let Some(stdlib) = stdlib
+        && (targets_stdlib || is_bare)
+    {
+        if targets_stdlib && let Ok(query_ctx) = stdlib.build_query_context(&first).await {
+            resolved = resolve_trait(&query_ctx, &request.trait_path)?;
+        }
+
+        for crate_name in stdlib.available_crates() {
+            match stdlib.load(crate_name).await {
+                Ok(index) => stdlib_indexes.push(index),
+                Err(e) => {
+                    tracing::warn!(crate_name, error = ?e, "Failed to load stdlib docs");
+                }
+            }
+        }
+    }
+
+    let query_ctx = workspace.map(|ws| state.query_context(ws));
+
+    if resolved.is_none()
+        && !is_bare
+        && !targets_stdlib
+        && let Some(query_ctx) = &query_ctx
+    {
+        resolved = resolve_trait(query_ctx, &request.trait_path)?;
+    }
+
+    let matcher = match resolved {
+        Some(segments) => TraitMatcher::Exact(segments),
+        None => TraitMatcher::Name {
+            name,
+            crate_hint: (!is_bare).then(|| CrateName::normalize(&first).into_owned()),
+        },
+    };
+    tracing::debug!(?matcher, "Scanning impl blocks");
+
+    let mut collector = Collector::default();
+
+    if let Some(query_ctx) = &query_ctx {
+        let workspace = query_ctx.workspace();
+        let crate_names = workspace
+            .members
+            .iter()
+            .map(|member| member.as_str().to_string())
+            .chain(workspace.dependency_names());
+
+        for crate_name in crate_names {
+            let crate_name = crate_name.as_str();
+            if StdlibDocs::is_stdlib_crate(crate_name)
+                || !query_ctx.doc_source_path(crate_name).exists()
+            {
+                continue;
+            }
+            match query_ctx.load_crate(crate_name) {
+                Ok(index) => collector.scan(index, &matcher),
+                Err(e) => {
+                    tracing::debug!(crate_name, error = %e, "Skipping crate without docs");
+                }
+            }
+        }
+    }
+
+    for index in &stdlib_indexes {
+        collector.scan(index, &matcher);
+    }
+
+    Ok(collector.finish(request.trait_path, request.limit))
+}
+
+/// Resolve a qualified trait path to its definition path.
+///
+/// Returns `Ok(None)` when the path cannot be resolved (callers fall back to
+/// name matching) and `Err` when it resolves to something that isn't a trait.
+fn resolve_trait(
+    query_ctx: &QueryContext,
+    trait_path: &str,
+) -> Result<Option<Vec<String>>, String> {
+    let Some(item) = query_ctx.resolve_path(trait_path, &mut Vec::new()) else {
+        return Ok(None);
+    };
+
+    if !matches!(item.inner(), ItemEnum::Trait(_)) {
+        return Err(format!(
+            "'{}' is a {}, not a trait",
+            trait_path,
+            crate::search::item_kind_str(item.inner())
+        ));
+    }
+
+    Ok(item.path_segments().map(<[String]>::to_vec))
+}
+
+/// Accumulates matching impl blocks across crates.
+#[derive(Default)]
+struct Collector {
+    crates_scanned: Vec<String>,
+    matched_traits: Vec<String>,
+    implementations: Vec<Implementor>,
+    blanket: Vec<Implementor>,
+    auto: Vec<Implementor>,
+}
+
+impl Collector {
+    fn scan(&mut self, index: &CrateIndex, matcher: &TraitMatcher) {
+        let crate_name = index.name().to_string();
+        let fmt = TypeFormatter::new(index);
+
+        for item in index.index.values() {
+            let ItemEnum::Impl(impl_block) = &item.inner else {
+                continue;
+            };
+            let Some(trait_) = &impl_block.trait_ else {
+                continue;
+            };
+            // Rustdoc materializes every applicable blanket impl onto each type;
+            // the generic impl itself is reported once under `blanket` instead.
+            if impl_block.blanket_impl.is_some() {
+                continue;
+            }
+
+            let segments = index.paths().get(&trait_.id).map_or_else(
+                || trait_.path.split("::").map(str::to_string).collect(),
+                |summary| summary.path.clone(),
+            );
+            if !matcher.matches(&segments) {
+                continue;
+            }
+
+            let trait_path = segments.join("::");
+            if !self.matched_traits.contains(&trait_path) {
+                self.matched_traits.push(trait_path.clone());
+            }
+
+            let implementor = describe_impl(&fmt, impl_block, &crate_name, trait_path);
+            if impl_block.is_synthetic {
+                self.auto.push(implementor);
+            } else if is_blanket_target(&impl_block.for_) {
+                self.blanket.push(implementor);
+            } else {
+                self.implementations.push(implementor);
+            }
+        }
+
+        self.crates_scanned.push(crate_name);
+    }
+
+    fn finish(self, query: String, limit: usize) -> StructuredImplementorsResult {
+        StructuredImplementorsResult {
+            query,
+            matched_traits: self.matched_traits,
+            crates_scanned: self.crates_scanned,
+            implementations: into_group(self.implementations, limit),
+            blanket: into_group(self.blanket, limit),
+            auto: into_group(self.auto, limit),
+        }
+    }
+}
+
+/// Sort, de-duplicate, and truncate a list of implementors.
+fn into_group(mut entries: Vec<Implementor>, limit: usize) -> ImplementorGroup {
+    entries.sort_by(|a, b| {
+        a.crate_name
+            .cmp(&b.crate_name)
+            .then_with(|| a.for_type.cmp(&b.for_type))
+            .then_with(|| a.declaration.cmp(&b.declaration))
+    });
+
+    let mut seen = HashSet::new();
+    entries.retain(|e| seen.insert((e.crate_name.clone(), e.declaration.clone())));
+
+    let total = entries.len();
+    entries.truncate(limit);
+    ImplementorGroup { entries, total }
+}
+
+/// True if the impl's self type is a bare type parameter, possibly behind a
+/// reference or pointer (`T`, `&T`, `*const T`).
+fn is_blanket_target(ty: &Type) -> bool {
+    match ty {
+        Type::Generic(_) => true,
+        Type::BorrowedRef { type_, .. } | Type::RawPointer { type_, .. } => {
+            is_blanket_target(type_)
+        }
+        _ => false,
+    }
+}
+
+/// Format an impl block's header into an [`Implementor`].
+fn describe_impl(
+    fmt: &TypeFormatter<'_>,
+    impl_block: &Impl,
+    crate_name: &str,
+    trait_path: String,
+) -> Implementor {
+    let mut generics = String::new();
+    let _ = fmt.write_generics(&mut generics, &impl_block.generics);
+
+    let mut for_type = String::new();
+    let _ = fmt.write_type(&mut for_type, &impl_block.for_);
+
+    let mut declaration = String::new();
+    let _ = fmt.write_impl_header(&mut declaration, impl_block);
+
+    let mut where_clause = String::new();
+    let _ = fmt.write_where_clause(&mut where_clause, &impl_block.generics.where_predicates, 0);
+
+    let where_clause = where_clause.trim();
+    Implementor {
+        crate_name: crate_name.to_string(),
+        trait_path,
+        for_type,
+        generics,
+        where_clause: (!where_clause.is_empty()).then(|| where_clause.to_string()),
+        is_negative: impl_block.is_negative,
+        declaration,
+    }
+}
+
+/// Render a [`StructuredImplementorsResult`] into the human-readable MCP output format.
+pub fn render_implementors_result(result: &StructuredImplementorsResult) -> String {
+    let crate_count = result.crates_scanned.len();
+
+    if result.implementations.total + result.blanket.total + result.auto.total == 0 {
+        let mut msg = format!(
+            "No implementations of '{}' found in {} crate(s).\n\n",
+            result.query, crate_count
+        );
+        msg.push_str("Tips:\n");
+        msg.push_str("• Use the trait's path, e.g. 'serde::Serialize' or 'std::fmt::Display'\n");
+        msg.push_str("• Only crates with generated documentation are scanned\n");
+        return msg;
+    }
+
+    let mut output = format!("Implementations of '{}'", result.query);
+    match result.matched_traits.as_slice() {
+        [single] if *single != result.query => {
+            let _ = write!(&mut output, " ({single})");
+        }
+        [_] => {}
+        many => {
+            let _ = write!(&mut output, " (matched {})", many.join(", "));
+        }
+    }
+    let _ = writeln!(&mut output, " across {crate_count} crate(s):");
+
+    render_group(&mut output, "Implementations", &result.implementations);
+    render_group(&mut output, "Blanket implementations", &result.blanket);
+    render_group(&mut output, "Auto-trait implementations", &result.auto);
+
+    output
+}
+
+fn render_group(output: &mut String, title: &str, group: &ImplementorGroup) {
+    if group.total == 0 {
+        return;
+    }
+
+    let _ = writeln!(output, "\n{title} ({}):", group.total);
+    for entry in &group.entries {
+        let declaration = entry.declaration.replace('\n', "\n    ");
+        let _ = writeln!(output, "  [{}] {}", entry.crate_name, declaration);
+    }
+    if group.total > group.entries.len() {
+        let _ = writeln!(
+            output,
+            "  ... and {} more",
+            group.total - group.entries.len()
+        );
+    }
+}
@@ -1,14 +1,19 @@
+//! Crate-level overview handler.
+//!
+//! Like the other tools, this module has a structured layer,
+//! [`handle_inspect_crate_structured`], returning a [`StructuredCrateResult`],
+//! and [`handle_inspect_crate`], which renders it for the MCP text output.
+
 use crate::error::Result;
 use crate::format::DetailLevel;
 use crate::search::CrateIndex;
 use crate::stdlib::StdlibDocs;
-use crate::types::CrateName;
 use crate::worker::DocState;
-use crate::workspace::{CrateOrigin, get_docs};
+use crate::workspace::{CrateMetadata, CrateOrigin};
 use anyhow::anyhow;
 use rmcp::schemars;
-use rustdoc_types::ItemEnum;
-use serde::Deserialize;
+use rustdoc_types::{Item, ItemEnum, MacroKind, ProcMacro};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::Write as _;
 use std::sync::Arc;
@@ -22,6 +27,92 @@ pub struct InspectCrateRequest {
     /// Detail level: low (counts only), medium (+ modules), high (+ top exports)
     #[serde(default)]
     pub detail_level: DetailLevel,
+
+    /// Workspace to query: its path or any directory inside it. Defaults to the
+    /// workspace configured with set_workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+/// Number of entries shown per export group (types, traits, functions).
+const EXPORTS_SHOWN: usize = 5;
+
+/// Structured outcome of an `inspect_crate` call, tagged by `mode`.
+///
+/// Lists are complete; truncation for the `detail_level` happens when rendering.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum StructuredCrateResult {
+    /// Every crate in the configured workspace.
+    Summary {
+        detail_level: DetailLevel,
+        members: Vec<CrateEntry>,
+        /// External dependencies, most-used first.
+        dependencies: Vec<CrateEntry>,
+        stdlib: Vec<CrateEntry>,
+    },
+    /// No workspace is configured; only the standard library is available.
+    StdlibSummary {
+        rustc_version: String,
+        crates: Vec<CrateEntry>,
+    },
+    /// A single crate in depth.
+    Detail(CrateDetail),
+}
+
+/// One crate in a summary listing.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct CrateEntry {
+    pub name: String,
+    pub version: Option<String>,
+    pub origin: CrateOrigin,
+    pub description: Option<String>,
+    /// True for the workspace's root package.
+    pub is_root: bool,
+    /// Workspace members that depend on this crate.
+    pub used_by: Vec<String>,
+}
+
+/// Detailed information about one crate.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct CrateDetail {
+    pub detail_level: DetailLevel,
+    pub name: String,
+    pub version: Option<String>,
+    pub origin: CrateOrigin,
+    pub description: Option<String>,
+    pub used_by: Vec<String>,
+    /// Why documentation couldn't be loaded; when set, the fields below are empty.
+    pub documentation_error: Option<String>,
+    /// Item counts by kind, largest first.
+    pub item_counts: Vec<ItemCount>,
+    /// Top-level module names, sorted. Omitted at low detail.
+    pub modules: Option<Vec<String>>,
+    /// Commonly used public items. Only included at high detail.
+    pub exports: Option<CommonExports>,
+}
+
+/// Number of items of one kind.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct ItemCount {
+    pub kind: String,
+    pub count: usize,
+}
+
+/// Public items grouped by kind.
+#[derive(Debug, Clone, Default, Serialize, schemars::JsonSchema)]
+pub struct CommonExports {
+    pub types: ExportGroup,
+    pub traits: ExportGroup,
+    pub functions: ExportGroup,
+}
+
+/// The first few public items of a kind, alphabetically.
+#[derive(Debug, Clone, Default, Serialize, schemars::JsonSchema)]
+pub struct ExportGroup {
+    pub paths: Vec<String>,
+    /// Total number of public items of this kind.
+    pub total: usize,
 }
 
 /// Handles inspect_crate requests by showing crate-level information.
@@ -38,26 +129,42 @@ pub struct InspectCrateRequest {
 /// - Item counts by kind
 /// - Common exports
 /// - Usage information
+///
+/// This is the string-returning wrapper. For programmatic access or tests,
+/// call [`handle_inspect_crate_structured`] directly.
 #[tracing::instrument(skip_all, fields(crate_name = ?request.crate_name))]
 pub async fn handle_inspect_crate(
     state: &Arc<DocState>,
     request: InspectCrateRequest,
 ) -> Result<String> {
+    let structured = handle_inspect_crate_structured(state, request).await?;
+    Ok(render_crate_result(&structured))
+}
+
+/// Structured variant of [`handle_inspect_crate`].
+#[tracing::instrument(skip_all, fields(crate_name = ?request.crate_name))]
+pub async fn handle_inspect_crate_structured(
+    state: &Arc<DocState>,
+    request: InspectCrateRequest,
+) -> Result<StructuredCrateResult> {
     // Try workspace first
-    if let Some(workspace_ctx) = state.workspace().await {
+    let workspace = state
+        .select_workspace(request.workspace.as_deref())
+        .await
+        .map_err(|e| anyhow!(e))?;
+    if let Some(workspace_ctx) = workspace {
         tracing::debug!("Using workspace context");
         return match request.crate_name {
-            None => render_summary_mode(&workspace_ctx, request.detail_level),
+            None => Ok(summarize_workspace(&workspace_ctx, request.detail_level)),
             Some(crate_name) => {
                 // Check if it's a stdlib crate that we should handle specially
                 if StdlibDocs::is_stdlib_crate(&crate_name)
                     && let Some(stdlib) = state.stdlib()
                 {
                     tracing::debug!(crate_name = %crate_name, "Routing stdlib crate to stdlib handler");
-                    return render_stdlib_detail_mode(&crate_name, stdlib, request.detail_level)
-                        .await;
+                    return stdlib_detail(&crate_name, stdlib, request.detail_level).await;
                 }
-                render_detail_mode(&crate_name, &workspace_ctx, request.detail_level, state).await
+                crate_detail(state, &crate_name, workspace_ctx, request.detail_level)
             }
         };
     }
@@ -78,7 +185,7 @@ pub async fn handle_inspect_crate(
     })?;
 
     match request.crate_name {
-        None => render_stdlib_summary_mode(stdlib, request.detail_level),
+        None => Ok(summarize_stdlib(stdlib)),
         Some(crate_name) => {
             if !StdlibDocs::is_stdlib_crate(&crate_name) {
                 return Err(anyhow!(
@@ -91,291 +198,156 @@ pub async fn handle_inspect_crate(
                     stdlib.available_crates().join(", ")
                 ));
             }
-            render_stdlib_detail_mode(&crate_name, stdlib, request.detail_level).await
+            stdlib_detail(&crate_name, stdlib, request.detail_level).await
+        }
+    }
+}
+
+impl CrateEntry {
+    fn from_metadata(meta: &CrateMetadata) -> Self {
+        Self {
+            name: meta.name.as_str().to_string(),
+            version: meta.version.clone(),
+            origin: meta.origin,
+            description: meta.description.clone(),
+            is_root: meta.is_root_crate,
+            used_by: meta
+                .used_by
+                .iter()
+                .map(|name| name.as_str().to_string())
+                .collect(),
         }
     }
 }
 
 /// Summary mode: list all crates with descriptions and stats
-fn render_summary_mode(
+fn summarize_workspace(
     workspace_ctx: &crate::workspace::WorkspaceContext,
     detail_level: DetailLevel,
-) -> Result<String> {
-    let mut output = String::new();
+) -> StructuredCrateResult {
+    let mut members = Vec::new();
+    let mut dependencies = Vec::new();
+    let mut stdlib = Vec::new();
 
-    // Categorize crates
-    let mut workspace_members = Vec::new();
-    let mut external_deps = Vec::new();
-    let mut std_crates = Vec::new();
-
-    for (name, metadata) in &workspace_ctx.crate_info {
+    for metadata in workspace_ctx.crate_info.values() {
+        let entry = CrateEntry::from_metadata(metadata);
         match metadata.origin {
-            CrateOrigin::Local => workspace_members.push((name, metadata)),
-            CrateOrigin::External => external_deps.push((name, metadata)),
-            CrateOrigin::Standard => std_crates.push((name, metadata)),
+            CrateOrigin::Local => members.push(entry),
+            CrateOrigin::External => dependencies.push(entry),
+            CrateOrigin::Standard => stdlib.push(entry),
         }
     }
 
+    members.sort_by(|a, b| a.name.cmp(&b.name));
     // Sort by usage (most used first), then alphabetically
-    external_deps.sort_by(|(name_a, meta_a), (name_b, meta_b)| {
-        meta_b
-            .used_by
+    dependencies.sort_by(|a, b| {
+        b.used_by
             .len()
-            .cmp(&meta_a.used_by.len())
-            .then_with(|| name_a.cmp(name_b))
+            .cmp(&a.used_by.len())
+            .then_with(|| a.name.cmp(&b.name))
     });
+    stdlib.sort_by(|a, b| a.name.cmp(&b.name));
 
-    // Workspace Members
-    if !workspace_members.is_empty() {
-        writeln!(output, "Workspace Members ({}):", workspace_members.len())?;
-        for (name, meta) in &workspace_members {
-            let version = meta.version.as_deref().unwrap_or("unknown");
-            write!(output, "  • {} v{}", name, version)?;
-            if meta.is_root_crate {
-                write!(output, " (root)")?;
-            }
-            writeln!(output)?;
-
-            if detail_level != DetailLevel::Low
-                && let Some(desc) = &meta.description
-            {
-                writeln!(output, "    {}", truncate_description(desc, 80))?;
-            }
-        }
-        writeln!(output)?;
+    StructuredCrateResult::Summary {
+        detail_level,
+        members,
+        dependencies,
+        stdlib,
     }
-
-    // External Dependencies
-    if !external_deps.is_empty() {
-        writeln!(output, "External Dependencies ({}):", external_deps.len())?;
-
-        let limit = match detail_level {
-            DetailLevel::Low => 10,
-            DetailLevel::Medium => 20,
-            DetailLevel::High => external_deps.len(),
-        };
-
-        for (name, meta) in external_deps.iter().take(limit) {
-            let version = meta.version.as_deref().unwrap_or("unknown");
-            write!(output, "  • {} v{}", name, version)?;
-
-            if detail_level != DetailLevel::Low && !meta.used_by.is_empty() {
-                let used_by: Vec<_> = meta
-                    .used_by
-                    .iter()
-                    .map(super::super::types::CrateName::as_str)
-                    .collect();
-                write!(output, " (used by {})", used_by.join(", "))?;
-            }
-            writeln!(output)?;
-
-            if detail_level == DetailLevel::High
-                && let Some(desc) = &meta.description
-            {
-                writeln!(output, "    {}", truncate_description(desc, 80))?;
-            }
-        }
-
-        if external_deps.len() > limit {
-            writeln!(
-                output,
-                "  ... and {} more dependencies",
-                external_deps.len() - limit
-            )?;
-        }
-        writeln!(output)?;
-    }
-
-    // Standard Library
-    if !std_crates.is_empty() && detail_level != DetailLevel::Low {
-        writeln!(output, "Standard Library ({}):", std_crates.len())?;
-        std_crates.sort_by_key(|(name, _)| *name);
-        for (name, _) in std_crates.iter().take(5) {
-            writeln!(output, "  • {}", name)?;
-        }
-        if std_crates.len() > 5 {
-            writeln!(output, "  ... and {} more", std_crates.len() - 5)?;
-        }
-    }
-
-    Ok(output)
 }
 
 /// Detail mode: deep dive into a specific crate
-#[allow(clippy::too_many_lines)]
-async fn render_detail_mode(
+fn crate_detail(
+    state: &Arc<DocState>,
     crate_name: &str,
-    workspace_ctx: &crate::workspace::WorkspaceContext,
+    workspace_ctx: crate::workspace::WorkspaceContext,
     detail_level: DetailLevel,
-    state: &Arc<DocState>,
-) -> Result<String> {
-    let mut output = String::new();
-
+) -> Result<StructuredCrateResult> {
     // Get crate metadata
     let meta = workspace_ctx
         .get_crate(crate_name)
         .ok_or_else(|| anyhow!("Crate '{}' not found in workspace", crate_name))?;
 
-    // Header
-    let version = meta.version.as_deref().unwrap_or("unknown");
-    writeln!(output, "Crate: {} v{}", crate_name, version)?;
-    writeln!(output, "Origin: {:?}", meta.origin)?;
+    let entry = CrateEntry::from_metadata(meta);
+    let mut detail = CrateDetail {
+        detail_level,
+        name: crate_name.to_string(),
+        version: entry.version,
+        origin: entry.origin,
+        description: entry.description,
+        used_by: entry.used_by,
+        documentation_error: None,
+        item_counts: Vec::new(),
+        modules: None,
+        exports: None,
+    };
 
-    if let Some(desc) = &meta.description {
-        writeln!(output, "\n{}", desc)?;
-    }
+    // Crates present in several versions keep their docs under `name@version`
+    let doc_name = workspace_ctx.qualified_name(meta);
 
-    // Usage information
-    if !meta.used_by.is_empty() {
-        let used_by: Vec<_> = meta
-            .used_by
-            .iter()
-            .map(super::super::types::CrateName::as_str)
-            .collect();
-        writeln!(output, "\nUsed by: {}", used_by.join(", "))?;
+    // Load through the shared cache, generating the docs if they're missing
+    match state.query_context(workspace_ctx).load_shared(&doc_name) {
+        Ok(crate_index) => fill_from_index(&mut detail, &crate_index, true),
+        Err(e) => detail.documentation_error = Some(e.to_string()),
     }
 
-    // Try to load documentation
-    let workspace_root = state
-        .working_directory()
-        .await
-        .ok_or_else(|| anyhow!("No working directory configured"))?;
-
-    // Create CrateName for consistent normalization
-    let crate_name = CrateName::new_unchecked(crate_name);
-    let doc_path = crate_name.doc_json_path(&workspace_root.join("target/doc"));
-
-    let doc_result = if doc_path.exists() {
-        // JSON exists, load directly without regeneration
-        CrateIndex::load(&doc_path)
-    } else {
-        // JSON doesn't exist, try to generate
-        let cargo_lock_path = state.cargo_lock_path().await;
-        let is_workspace_member = meta.origin == CrateOrigin::Local;
-        let version = meta.version.as_deref();
-
-        get_docs(
-            &crate_name,
-            version,
-            &workspace_root,
-            is_workspace_member,
-            cargo_lock_path.as_deref(),
-        )
-        .await
-    };
-
-    match doc_result {
-        Ok(crate_index) => {
-            writeln!(output, "\nDocumentation: Available")?;
+    Ok(StructuredCrateResult::Detail(detail))
+}
 
-            // Item counts
-            let counts = count_items_by_kind(&crate_index);
-            writeln!(output, "\nItem Counts:")?;
-            for (kind, count) in &counts {
-                writeln!(output, "  {}: {}", kind, count)?;
-            }
+/// Populate item counts, modules and exports of `detail` from loaded docs.
+fn fill_from_index(detail: &mut CrateDetail, crate_index: &CrateIndex, include_functions: bool) {
+    detail.item_counts = count_items_by_kind(crate_index);
 
-            // Module hierarchy (medium and high detail)
-            if detail_level != DetailLevel::Low
-                && let Some(root) = crate_index.root_module()
-                && let ItemEnum::Module(module) = &root.inner
-            {
-                writeln!(output, "\nTop-level Modules:")?;
-                let mut module_names: Vec<_> = module
-                    .items
-                    .iter()
-                    .filter_map(|&id| {
-                        let item = crate_index.get_item(id)?;
-                        if matches!(item.inner, ItemEnum::Module(_)) {
-                            item.name.as_ref()
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-                module_names.sort();
-
-                let limit = if detail_level == DetailLevel::High {
-                    module_names.len()
+    // Module hierarchy (medium and high detail)
+    if detail.detail_level != DetailLevel::Low
+        && let Some(root) = crate_index.root_module()
+        && let ItemEnum::Module(module) = &root.inner
+    {
+        let mut module_names: Vec<String> = module
+            .items
+            .iter()
+            .filter_map(|&id| {
+                let item = crate_index.get_item(id)?;
+                if matches!(item.inner, ItemEnum::Module(_)) {
+                    item.name.clone()
                 } else {
-                    10
-                };
-
-                for name in module_names.iter().take(limit) {
-                    writeln!(output, "  • {}", name)?;
-                }
-
-                if module_names.len() > limit {
-                    writeln!(
-                        output,
-                        "  ... and {} more modules",
-                        module_names.len() - limit
-                    )?;
-                }
-            }
-
-            // Top exports (high detail only)
-            if detail_level == DetailLevel::High {
-                writeln!(output, "\nCommon Exports:")?;
-
-                // Show top types
-                let types = crate_index.public_types();
-                if !types.is_empty() {
-                    writeln!(output, "  Types:")?;
-                    for item in types.iter().take(5) {
-                        if item.name.is_some() {
-                            let path = crate_index.get_item_path(item);
-                            writeln!(output, "    • {}", path)?;
-                        }
-                    }
-                    if types.len() > 5 {
-                        writeln!(output, "    ... and {} more types", types.len() - 5)?;
-                    }
-                }
-
-                // Show top traits
-                let traits = crate_index.public_traits();
-                if !traits.is_empty() {
-                    writeln!(output, "  Traits:")?;
-                    for item in traits.iter().take(5) {
-                        if item.name.is_some() {
-                            let path = crate_index.get_item_path(item);
-                            writeln!(output, "    • {}", path)?;
-                        }
-                    }
-                    if traits.len() > 5 {
-                        writeln!(output, "    ... and {} more traits", traits.len() - 5)?;
-                    }
+                    None
                 }
+            })
+            .collect();
+        module_names.sort();
+        detail.modules = Some(module_names);
+    }
 
-                // Show top functions
-                let functions = crate_index.public_functions();
-                if !functions.is_empty() {
-                    writeln!(output, "  Functions:")?;
-                    for item in functions.iter().take(5) {
-                        if item.name.is_some() {
-                            let path = crate_index.get_item_path(item);
-                            writeln!(output, "    • {}", path)?;
-                        }
-                    }
-                    if functions.len() > 5 {
-                        writeln!(output, "    ... and {} more functions", functions.len() - 5)?;
-                    }
-                }
-            }
-        }
-        Err(e) => {
-            writeln!(output, "\nDocumentation: Not available")?;
-            writeln!(output, "  Error: {}", e)?;
-        }
+    // Top exports (high detail only)
+    if detail.detail_level == DetailLevel::High {
+        detail.exports = Some(CommonExports {
+            types: export_group(crate_index, &crate_index.public_types()),
+            traits: export_group(crate_index, &crate_index.public_traits()),
+            functions: if include_functions {
+                export_group(crate_index, &crate_index.public_functions())
+            } else {
+                ExportGroup::default()
+            },
+        });
     }
+}
 
-    Ok(output)
+fn export_group(crate_index: &CrateIndex, items: &[&Item]) -> ExportGroup {
+    ExportGroup {
+        paths: items
+            .iter()
+            .take(EXPORTS_SHOWN)
+            .filter(|item| item.name.is_some())
+            .map(|item| crate_index.get_item_path(item))
+            .collect(),
+        total: items.len(),
+    }
 }
 
-/// Count items by kind in a crate
-fn count_items_by_kind(crate_index: &crate::search::CrateIndex) -> HashMap<String, usize> {
-    let mut counts: HashMap<String, usize> = HashMap::new();
+/// Count items by kind in a crate, largest count first.
+fn count_items_by_kind(crate_index: &crate::search::CrateIndex) -> Vec<ItemCount> {
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
 
     for item in crate_index.index.values() {
         let kind = match &item.inner {
@@ -387,68 +359,208 @@ fn count_items_by_kind(crate_index: &crate::search::CrateIndex) -> HashMap<Strin
             ItemEnum::TypeAlias(_) => "Type Aliases",
             ItemEnum::Constant { .. } => "Constants",
             ItemEnum::Static(_) => "Statics",
-            ItemEnum::Macro(_) => "Macros",
+            ItemEnum::Macro(_)
+            | ItemEnum::ProcMacro(ProcMacro {
+                kind: MacroKind::Bang,
+                ..
+            }) => "Macros",
+            ItemEnum::ProcMacro(ProcMacro {
+                kind: MacroKind::Derive,
+                ..
+            }) => "Derive Macros",
+            ItemEnum::ProcMacro(ProcMacro {
+                kind: MacroKind::Attr,
+                ..
+            }) => "Attribute Macros",
             _ => continue,
         };
 
-        *counts.entry(kind.to_string()).or_insert(0) += 1;
+        *counts.entry(kind).or_insert(0) += 1;
     }
 
     // Sort by count descending
-    let mut sorted: Vec<_> = counts.into_iter().collect();
-    sorted.sort_by(|(_, count_a), (_, count_b)| count_b.cmp(count_a));
-
-    sorted.into_iter().collect()
+    let mut sorted: Vec<ItemCount> = counts
+        .into_iter()
+        .map(|(kind, count)| ItemCount {
+            kind: kind.to_string(),
+            count,
+        })
+        .collect();
+    sorted.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.kind.cmp(&b.kind)));
+    sorted
 }
 
-/// Truncate description to a maximum length, breaking at word boundaries
-fn truncate_description(desc: &str, max_len: usize) -> String {
-    let first_line = desc.lines().next().unwrap_or(desc);
-
-    if first_line.len() <= max_len {
-        return first_line.to_string();
+/// Summary mode for stdlib-only (no workspace configured)
+fn summarize_stdlib(stdlib: &Arc<StdlibDocs>) -> StructuredCrateResult {
+    let crates = stdlib
+        .available_crates()
+        .into_iter()
+        .map(|crate_name| CrateEntry {
+            name: crate_name.to_string(),
+            version: None,
+            origin: CrateOrigin::Standard,
+            description: stdlib_description(crate_name).map(str::to_string),
+            is_root: false,
+            used_by: Vec::new(),
+        })
+        .collect();
+
+    StructuredCrateResult::StdlibSummary {
+        rustc_version: stdlib.rustc_version().to_string(),
+        crates,
     }
+}
 
-    // Find last space before max_len
-    if let Some(pos) = first_line[..max_len].rfind(' ') {
-        format!("{}...", &first_line[..pos])
-    } else {
-        format!("{}...", &first_line[..max_len])
+const fn stdlib_description(crate_name: &str) -> Option<&'static str> {
+    match crate_name.as_bytes() {
+        b"std" => Some("The Rust Standard Library"),
+        b"core" => Some("The Rust Core Library (no_std compatible)"),
+        b"alloc" => Some("Memory allocation APIs (no_std compatible)"),
+        b"proc_macro" => Some("Procedural macro support"),
+        b"test" => Some("Testing framework internals"),
+        _ => None,
     }
 }
 
-/// Summary mode for stdlib-only (no workspace configured)
-fn render_stdlib_summary_mode(
-    stdlib: &std::sync::Arc<StdlibDocs>,
-    _detail_level: DetailLevel,
-) -> Result<String> {
+/// Detail mode for a stdlib crate
+async fn stdlib_detail(
+    crate_name: &str,
+    stdlib: &Arc<StdlibDocs>,
+    detail_level: DetailLevel,
+) -> Result<StructuredCrateResult> {
+    // Load the crate documentation
+    let crate_index = stdlib.load(crate_name).await?;
+
+    let (name, version) = crate_index.crate_info();
+    let mut detail = CrateDetail {
+        detail_level,
+        name: name.unwrap_or(crate_name).to_string(),
+        version: Some(version.unwrap_or("nightly").to_string()),
+        origin: CrateOrigin::Standard,
+        description: None,
+        used_by: Vec::new(),
+        documentation_error: None,
+        item_counts: Vec::new(),
+        modules: None,
+        exports: None,
+    };
+    fill_from_index(&mut detail, &crate_index, false);
+
+    Ok(StructuredCrateResult::Detail(detail))
+}
+
+/// Render a [`StructuredCrateResult`] into the human-readable MCP output format.
+pub fn render_crate_result(result: &StructuredCrateResult) -> String {
     let mut output = String::new();
+    let _ = match result {
+        StructuredCrateResult::Summary {
+            detail_level,
+            members,
+            dependencies,
+            stdlib,
+        } => render_summary(&mut output, *detail_level, members, dependencies, stdlib),
+        StructuredCrateResult::StdlibSummary {
+            rustc_version,
+            crates,
+        } => render_stdlib_summary(&mut output, rustc_version, crates),
+        StructuredCrateResult::Detail(detail) => render_detail(&mut output, detail),
+    };
+    output
+}
 
+fn render_summary(
+    output: &mut String,
+    detail_level: DetailLevel,
+    members: &[CrateEntry],
+    dependencies: &[CrateEntry],
+    stdlib: &[CrateEntry],
+) -> std::fmt::Result {
+    // Workspace Members
+    if !members.is_empty() {
+        writeln!(output, "Workspace Members ({}):", members.len())?;
+        for member in members {
+            let version = member.version.as_deref().unwrap_or("unknown");
+            write!(output, "  • {} v{}", member.name, version)?;
+            if member.is_root {
+                write!(output, " (root)")?;
+            }
+            writeln!(output)?;
+
+            if detail_level != DetailLevel::Low
+                && let Some(desc) = &member.description
+            {
+                writeln!(output, "    {}", truncate_description(desc, 80))?;
+            }
+        }
+        writeln!(output)?;
+    }
+
+    // External Dependencies
+    if !dependencies.is_empty() {
+        writeln!(output, "External Dependencies ({}):", dependencies.len())?;
+
+        let limit = match detail_level {
+            DetailLevel::Low => 10,
+            DetailLevel::Medium => 20,
+            DetailLevel::High => dependencies.len(),
+        };
+
+        for dep in dependencies.iter().take(limit) {
+            let version = dep.version.as_deref().unwrap_or("unknown");
+            write!(output, "  • {} v{}", dep.name, version)?;
+
+            if detail_level != DetailLevel::Low && !dep.used_by.is_empty() {
+                write!(output, " (used by {})", dep.used_by.join(", "))?;
+            }
+            writeln!(output)?;
+
+            if detail_level == DetailLevel::High
+                && let Some(desc) = &dep.description
+            {
+                writeln!(output, "    {}", truncate_description(desc, 80))?;
+            }
+        }
+
+        if dependencies.len() > limit {
+            writeln!(
+                output,
+                "  ... and {} more dependencies",
+                dependencies.len() - limit
+            )?;
+        }
+        writeln!(output)?;
+    }
+
+    // Standard Library
+    if !stdlib.is_empty() && detail_level != DetailLevel::Low {
+        writeln!(output, "Standard Library ({}):", stdlib.len())?;
+        for entry in stdlib.iter().take(5) {
+            writeln!(output, "  • {}", entry.name)?;
+        }
+        if stdlib.len() > 5 {
+            writeln!(output, "  ... and {} more", stdlib.len() - 5)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn render_stdlib_summary(
+    output: &mut String,
+    rustc_version: &str,
+    crates: &[CrateEntry],
+) -> std::fmt::Result {
     writeln!(
         output,
         "No workspace configured. Showing standard library only."
     )?;
     writeln!(output)?;
-    writeln!(
-        output,
-        "Standard Library Crates ({}):",
-        stdlib.rustc_version()
-    )?;
+    writeln!(output, "Standard Library Crates ({rustc_version}):")?;
 
-    for crate_name in stdlib.available_crates() {
-        let description = match crate_name {
-            "std" => "The Rust Standard Library",
-            "core" => "The Rust Core Library (no_std compatible)",
-            "alloc" => "Memory allocation APIs (no_std compatible)",
-            "proc_macro" => "Procedural macro support",
-            "test" => "Testing framework internals",
-            _ => "",
-        };
-
-        if description.is_empty() {
-            writeln!(output, "  • {}", crate_name)?;
-        } else {
-            writeln!(output, "  • {} - {}", crate_name, description)?;
+    for entry in crates {
+        match &entry.description {
+            Some(description) => writeln!(output, "  • {} - {}", entry.name, description)?,
+            None => writeln!(output, "  • {}", entry.name)?,
         }
     }
 
@@ -456,115 +568,110 @@ fn render_stdlib_summary_mode(
     writeln!(
         output,
         "Hint: Use set_workspace to configure a Rust project for additional crates."
-    )?;
-
-    Ok(output)
+    )
 }
 
-/// Detail mode for a stdlib crate
-async fn render_stdlib_detail_mode(
-    crate_name: &str,
-    stdlib: &std::sync::Arc<StdlibDocs>,
-    detail_level: DetailLevel,
-) -> Result<String> {
-    let mut output = String::new();
+fn render_detail(output: &mut String, detail: &CrateDetail) -> std::fmt::Result {
+    // Header
+    let version = detail.version.as_deref().unwrap_or("unknown");
+    writeln!(output, "Crate: {} v{}", detail.name, version)?;
+    match detail.origin {
+        CrateOrigin::Standard => writeln!(output, "Origin: Standard Library")?,
+        origin => writeln!(output, "Origin: {:?}", origin)?,
+    }
 
-    // Load the crate documentation
-    let crate_index = stdlib.load(crate_name).await?;
+    if let Some(desc) = &detail.description {
+        writeln!(output, "\n{}", desc)?;
+    }
 
-    // Header
-    let (name, version) = crate_index.crate_info();
-    let name = name.unwrap_or(crate_name);
-    let version = version.unwrap_or("nightly");
+    // Usage information
+    if !detail.used_by.is_empty() {
+        writeln!(output, "\nUsed by: {}", detail.used_by.join(", "))?;
+    }
 
-    writeln!(output, "Crate: {} v{}", name, version)?;
-    writeln!(output, "Origin: Standard Library")?;
+    if let Some(error) = &detail.documentation_error {
+        writeln!(output, "\nDocumentation: Not available")?;
+        return writeln!(output, "  Error: {}", error);
+    }
+    if detail.origin != CrateOrigin::Standard {
+        writeln!(output, "\nDocumentation: Available")?;
+    }
 
-    // Item counts
-    let counts = count_items_by_kind(&crate_index);
     writeln!(output, "\nItem Counts:")?;
-    for (kind, count) in &counts {
-        writeln!(output, "  {}: {}", kind, count)?;
+    for count in &detail.item_counts {
+        writeln!(output, "  {}: {}", count.kind, count.count)?;
     }
 
-    // Module hierarchy (medium and high detail)
-    if detail_level != DetailLevel::Low
-        && let Some(root) = crate_index.root_module()
-        && let ItemEnum::Module(module) = &root.inner
-    {
+    if let Some(modules) = &detail.modules {
         writeln!(output, "\nTop-level Modules:")?;
-        let mut module_names: Vec<_> = module
-            .items
-            .iter()
-            .filter_map(|&id| {
-                let item = crate_index.get_item(id)?;
-                if matches!(item.inner, ItemEnum::Module(_)) {
-                    item.name.as_ref()
-                } else {
-                    None
-                }
-            })
-            .collect();
-        module_names.sort();
-
-        let limit = if detail_level == DetailLevel::High {
-            module_names.len()
+        let limit = if detail.detail_level == DetailLevel::High {
+            modules.len()
         } else {
             10
         };
 
-        for name in module_names.iter().take(limit) {
+        for name in modules.iter().take(limit) {
             writeln!(output, "  • {}", name)?;
         }
 
-        if module_names.len() > limit {
-            writeln!(
-                output,
-                "  ... and {} more modules",
-                module_names.len() - limit
-            )?;
+        if modules.len() > limit {
+            writeln!(output, "  ... and {} more modules", modules.len() - limit)?;
         }
     }
 
-    // Top exports (high detail only)
-    if detail_level == DetailLevel::High {
+    if let Some(exports) = &detail.exports {
         writeln!(output, "\nCommon Exports:")?;
+        render_export_group(output, "Types", "types", &exports.types)?;
+        render_export_group(output, "Traits", "traits", &exports.traits)?;
+        render_export_group(output, "Functions", "functions", &exports.functions)?;
+    }
 
-        let types = crate_index.public_types();
-        if !types.is_empty() {
-            writeln!(output, "  Types:")?;
-            for item in types.iter().take(5) {
-                if item.name.is_some() {
-                    let path = crate_index.get_item_path(item);
-                    writeln!(output, "    • {}", path)?;
-                }
-            }
-            if types.len() > 5 {
-                writeln!(output, "    ... and {} more types", types.len() - 5)?;
-            }
-        }
+    Ok(())
+}
 
-        let traits = crate_index.public_traits();
-        if !traits.is_empty() {
-            writeln!(output, "  Traits:")?;
-            for item in traits.iter().take(5) {
-                if item.name.is_some() {
-                    let path = crate_index.get_item_path(item);
-                    writeln!(output, "    • {}", path)?;
-                }
-            }
-            if traits.len() > 5 {
-                writeln!(output, "    ... and {} more traits", traits.len() - 5)?;
-            }
-        }
+fn render_export_group(
+    output: &mut String,
+    title: &str,
+    noun: &str,
+    group: &ExportGroup,
+) -> std::fmt::Result {
+    if group.total == 0 {
+        return Ok(());
     }
+    writeln!(output, "  {title}:")?;
+    for path in &group.paths {
+        writeln!(output, "    • {}", path)?;
+    }
+    if group.total > EXPORTS_SHOWN {
+        writeln!(
+            output,
+            "    ... and {} more {noun}",
+            group.total - EXPORTS_SHOWN
+        )?;
+    }
+    Ok(())
+}
 
-    Ok(output)
+/// Truncate description to a maximum length, breaking at word boundaries
+fn truncate_description(desc: &str, max_len: usize) -> String {
+    let first_line = desc.lines().next().unwrap_or(desc);
+
+    if first_line.len() <= max_len {
+        return first_line.to_string();
+    }
+
+    // Find last space before max_len
+    if let Some(pos) = first_line[..max_len].rfind(' ') {
+        format!("{}...", &first_line[..pos])
+    } else {
+        format!("{}...", &first_line[..max_len])
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{CrateKey, CrateName};
     use crate::workspace::{CrateMetadata, WorkspaceContext};
     use assert2::{check, let_assert};
     use std::collections::HashMap;
@@ -590,6 +697,7 @@ mod tests {
         let request = InspectCrateRequest {
             crate_name: None,
             detail_level: DetailLevel::Medium,
+            workspace: None,
         };
 
         let result = handle_inspect_crate(&state, request).await;
@@ -602,7 +710,7 @@ mod tests {
     async fn test_inspect_crate_summary_mode() {
         let mut crate_info = HashMap::new();
         crate_info.insert(
-            CrateName::new_unchecked("my-crate"),
+            CrateKey::new("my-crate", Some("0.1.0")),
             CrateMetadata {
                 origin: CrateOrigin::Local,
                 version: Some("0.1.0".to_string()),
@@ -611,10 +719,12 @@ mod tests {
                 name: CrateName::new_unchecked("my-crate"),
                 is_root_crate: true,
                 used_by: vec![],
+                features: None,
+                sources: None,
             },
         );
         crate_info.insert(
-            CrateName::new_unchecked("serde"),
+            CrateKey::new("serde", Some("1.0.0")),
             CrateMetadata {
                 origin: CrateOrigin::External,
                 version: Some("1.0.0".to_string()),
@@ -623,10 +733,12 @@ mod tests {
                 name: CrateName::new_unchecked("serde"),
                 is_root_crate: false,
                 used_by: vec![CrateName::new_unchecked("my-crate")],
+                features: None,
+                sources: None,
             },
         );
         crate_info.insert(
-            CrateName::new_unchecked("tokio"),
+            CrateKey::new("tokio", Some("1.0.0")),
             CrateMetadata {
                 origin: CrateOrigin::External,
                 version: Some("1.0.0".to_string()),
@@ -635,6 +747,8 @@ mod tests {
                 name: CrateName::new_unchecked("tokio"),
                 is_root_crate: false,
                 used_by: vec![CrateName::new_unchecked("my-crate")],
+                features: None,
+                sources: None,
             },
         );
 
@@ -643,6 +757,7 @@ mod tests {
             members: vec![CrateName::new_unchecked("my-crate")],
             crate_info,
             root_crate: Some(CrateName::new_unchecked("my-crate")),
+            target_dir: PathBuf::from("/test/project/target"),
         };
 
         let state = test_state_with_workspace(workspace_ctx).await;
@@ -650,6 +765,7 @@ mod tests {
         let request = InspectCrateRequest {
             crate_name: None,
             detail_level: DetailLevel::High,
+            workspace: None,
         };
 
         let result = handle_inspect_crate(&state, request).await.unwrap();
@@ -669,6 +785,7 @@ mod tests {
             members: vec![CrateName::new_unchecked("my-crate")],
             crate_info: HashMap::new(),
             root_crate: Some(CrateName::new_unchecked("my-crate")),
+            target_dir: PathBuf::from("/test/project/target"),
         };
 
         let state = test_state_with_workspace(workspace_ctx).await;
@@ -676,6 +793,7 @@ mod tests {
         let request = InspectCrateRequest {
             crate_name: Some("nonexistent".to_string()),
             detail_level: DetailLevel::Medium,
+            workspace: None,
         };
 
         let result = handle_inspect_crate(&state, request).await;
@@ -12,9 +12,11 @@
 
 use crate::format::DetailLevel;
 use crate::format::renderers::{
-    render_constant, render_enum, render_function, render_module, render_static, render_struct,
-    render_trait, render_type_alias,
+    render_assoc_const, render_assoc_type, render_constant, render_enum, render_function,
+    render_macro, render_module, render_proc_macro, render_static, render_struct, render_trait,
+    render_type_alias,
 };
+use crate::format::source::{SourceLocator, render_source};
 use crate::item::ItemRef;
 use crate::search::{
     DetailedSearchResult, ItemKind, QueryContext, TermIndex, item_kind_str, matches_kind,
@@ -23,17 +25,19 @@ use crate::search::{
 use crate::stdlib::StdlibDocs;
 use crate::types::CrateName;
 use crate::worker::DocState;
+use crate::workspace::WorkspaceContext;
 
 use rmcp::schemars;
 use rustdoc_types::{Item, ItemEnum};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fmt::Write as _;
 use std::sync::Arc;
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct InspectItemRequest {
-    /// Item to inspect (e.g., "Vec", "std::vec::Vec", "HashMap")
+    /// Item to inspect (e.g., "Vec", "std::vec::Vec", "HashMap", "Vec::push", "Iterator::Item",
+    /// or "syn@1::Ident" to pick one of several versions of a crate)
     pub query: String,
     /// Optional filter by item kind (struct, enum, function, trait, module, etc.)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -41,17 +45,36 @@ pub struct InspectItemRequest {
     /// Detail level: low (signature only), medium (+docs), high (+members+impls)
     #[serde(default = "default_detail_level")]
     pub detail_level: DetailLevel,
+    /// Workspace to query: its path or any directory inside it. Defaults to the
+    /// workspace configured with set_workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    /// Document the crate named in the query with exactly these features instead of
+    /// those the workspace enables, e.g. to see feature-gated items
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub features: Option<Vec<String>>,
 }
 
 const fn default_detail_level() -> DetailLevel {
     DetailLevel::Medium
 }
 
+/// What to render once a query has been resolved to a single item.
+#[derive(Debug, Clone)]
+pub(crate) enum ItemView {
+    /// Signature and documentation at the given detail level.
+    Docs(DetailLevel),
+    /// The item's definition source, located through its rustdoc span.
+    Source(SourceLocator),
+}
+
 /// Structured outcome of an `inspect_item` call.
 ///
 /// Tests should match on this enum to assert on concrete fields (full path,
 /// kind, candidate list) rather than substring-matching the rendered output.
-#[derive(Debug, Clone)]
+/// Serialized as the tool's `structuredContent`, tagged by `status`.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+#[serde(tag = "status", rename_all = "snake_case")]
 pub enum StructuredInspectResult {
     /// A single item was resolved. `rendered` contains the formatted output
     /// (signature + docs + detail per the requested `detail_level`).
@@ -69,11 +92,15 @@ pub enum StructuredInspectResult {
 }
 
 /// One disambiguation candidate shown to the user.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
 pub struct InspectCandidate {
     pub full_path: String,
     pub kind: String,
     pub first_doc_line: Option<String>,
+    /// Crate version, set when the workspace has several versions of the crate.
+    pub version: Option<String>,
+    /// Workspace members using this version of the crate (when `version` is set).
+    pub used_by: Vec<String>,
 }
 
 /// Handles inspect_item requests by resolving paths or searching across crates.
@@ -95,10 +122,24 @@ pub async fn handle_inspect_item(
 /// Errors (missing workspace, no matches, kind mismatch) still surface as
 /// `Err(String)` so callers can display them uniformly.
 #[tracing::instrument(skip_all, fields(query = %request.query))]
-#[allow(clippy::too_many_lines)]
 pub async fn handle_inspect_item_structured(
     state: &Arc<DocState>,
     request: InspectItemRequest,
+) -> Result<StructuredInspectResult, String> {
+    let view = ItemView::Docs(request.detail_level);
+    inspect_item_with_view(state, &request, &view).await
+}
+
+/// Resolve `request.query` to a single item and render it as `view`.
+///
+/// Shared by `inspect_item` and `view_source`: both accept the same queries
+/// and disambiguate the same way, and differ only in what they render.
+/// `request.detail_level` is ignored in favour of `view`.
+#[allow(clippy::too_many_lines, clippy::items_after_statements)]
+pub(crate) async fn inspect_item_with_view(
+    state: &Arc<DocState>,
+    request: &InspectItemRequest,
+    view: &ItemView,
 ) -> Result<StructuredInspectResult, String> {
     // Parse the item path to check if it targets stdlib
     let path_check = parse_item_path(&request.query);
@@ -109,17 +150,21 @@ pub async fn handle_inspect_item_structured(
         .first()
         .is_some_and(|first| StdlibDocs::is_stdlib_crate(first));
 
+    if request.features.is_some() && targets_stdlib {
+        return Err("Features can only be chosen for workspace crates".to_string());
+    }
+
     // If targeting stdlib and stdlib is available, handle it directly
     if targets_stdlib && let Some(stdlib) = state.stdlib() {
         tracing::debug!(query = %request.query, "Routing to stdlib handler");
-        return stdlib_inspect_structured(stdlib, &request, false).await;
+        return stdlib_inspect_structured(stdlib, request, view, false).await;
     }
 
     // Try workspace-based lookup
-    let Some(workspace_ctx) = state.workspace().await else {
+    let Some(workspace_ctx) = state.select_workspace(request.workspace.as_deref()).await? else {
         // No workspace - try stdlib fallback for common types
         if let Some(stdlib) = state.stdlib() {
-            return stdlib_inspect_structured(stdlib, &request, true).await;
+            return stdlib_inspect_structured(stdlib, request, view, true).await;
         }
 
         tracing::warn!("No workspace configured and stdlib not available");
@@ -136,12 +181,6 @@ pub async fn handle_inspect_item_structured(
     // Parse the item path
     let mut path = parse_item_path(&request.query);
 
-    // Verify workspace is configured
-    state
-        .working_directory()
-        .await
-        .ok_or_else(|| "No working directory configured. Use set_workspace first.".to_string())?;
-
     // Build list of known crates (members + dependencies)
     let mut known_crates = workspace_ctx.members.clone();
     known_crates.extend(
@@ -150,49 +189,134 @@ pub async fn handle_inspect_item_structured(
             .map(CrateName::new_unchecked),
     );
 
-    let query_ctx = QueryContext::new(Arc::new(workspace_ctx.clone()));
-
     let is_path_query = path.path_components.len() > 1 || request.query.contains("::");
-    let specified_crate = resolve_crate_from_path(&mut path, &known_crates);
-
-    if is_path_query && specified_crate.is_some() {
-        let crate_name = specified_crate.clone().unwrap();
+    // `syn@1::Item` names one version of a crate, while a bare `syn::Item` covers
+    // every version when the workspace uses several.
+    let versioned_crates = match path.path_components.first() {
+        Some(first) => crate_versions(&workspace_ctx, first)?,
+        None => Vec::new(),
+    };
+    let specified_crates = if versioned_crates.is_empty() {
+        resolve_crate_from_path(&mut path, &known_crates)
+            .into_iter()
+            .collect()
+    } else {
+        path.path_components.remove(0);
+        versioned_crates
+    };
+    if request.features.is_some() && specified_crates.is_empty() {
+        return Err(format!(
+            "Features apply to the crate the query names; prefix '{}' with a workspace crate",
+            request.query
+        ));
+    }
 
-        let full_path = if path.path_components.is_empty() {
-            crate_name.as_str().to_string()
-        } else {
-            format!(
-                "{}::{}",
-                crate_name.as_str(),
-                path.path_components.join("::")
-            )
+    // `Type::assoc` without a crate prefix, e.g. `QueryContext::resolve_path`.
+    if specified_crates.is_empty() && path.path_components.len() > 1 {
+        // Scoped so the non-Send QueryContext is gone before the stdlib fallback awaits.
+        let associated = {
+            let query_ctx = state.query_context(workspace_ctx.clone());
+            let crate_names: Vec<&str> = known_crates.iter().map(CrateName::as_str).collect();
+            let matches = resolve_associated_query(&query_ctx, &crate_names, &path.path_components);
+            (!matches.is_empty()).then(|| associated_result(matches, request, view))
         };
+        if let Some(result) = associated {
+            return result;
+        }
 
-        let mut suggestions = Vec::new();
+        // The owning type isn't in the workspace; `Vec::push` and friends live in std.
+        if let Some(stdlib) = state.stdlib()
+            && let Some(result) = stdlib_associated_structured(stdlib, request, view).await
+        {
+            return result;
+        }
+    }
 
-        if let Some(item_ref) = query_ctx.resolve_path(&full_path, &mut suggestions) {
-            tracing::debug!(path = %full_path, "Resolved item via direct path lookup");
+    let query_ctx = match &request.features {
+        Some(features) => specified_crates.iter().fold(
+            state.query_context(workspace_ctx.clone()),
+            |ctx, crate_name| ctx.with_features(crate_name.as_str(), features),
+        ),
+        None => state.query_context(workspace_ctx.clone()),
+    };
 
-            if let Some(kind_filter) = request.kind
-                && !matches_kind(item_ref.inner(), kind_filter)
-            {
-                return Err(format!(
-                    "Item '{}' found but is not a {:?}",
-                    path.full_path(),
-                    kind_filter
-                ));
+    // A bare crate name (`serde`) resolves to the crate root.
+    if !specified_crates.is_empty() && (is_path_query || path.path_components.is_empty()) {
+        let mut found = Vec::new();
+        let mut found_other_kind = false;
+
+        for crate_name in &specified_crates {
+            let full_path = if path.path_components.is_empty() {
+                crate_name.as_str().to_string()
+            } else {
+                format!(
+                    "{}::{}",
+                    crate_name.as_str(),
+                    path.path_components.join("::")
+                )
+            };
+
+            let mut suggestions = Vec::new();
+
+            if let Some(item_ref) = query_ctx.resolve_path(&full_path, &mut suggestions) {
+                tracing::debug!(path = %full_path, "Resolved item via direct path lookup");
+
+                if let Some(kind_filter) = request.kind
+                    && !matches_kind(item_ref.inner(), kind_filter)
+                {
+                    found_other_kind = true;
+                    continue;
+                }
+                found.push((item_ref, crate_name, full_path));
             }
+        }
+
+        if found.is_empty()
+            && found_other_kind
+            && let Some(kind_filter) = request.kind
+        {
+            return Err(format!(
+                "Item '{}' found but is not a {:?}",
+                path.full_path(),
+                kind_filter
+            ));
+        }
+
+        if found.len() == 1 {
+            let (item_ref, crate_name, _) = found.swap_remove(0);
+            return build_item_result(item_ref, view, crate_name.as_str())
+                .map(|result| with_version_note(result, &workspace_ctx));
+        }
 
-            return build_item_result(item_ref, request.detail_level, crate_name.as_str());
+        if found.len() > 1 {
+            tracing::debug!(
+                versions = found.len(),
+                "Path resolved in several crate versions, returning disambiguation"
+            );
+            let candidates = found
+                .into_iter()
+                .map(|(item_ref, crate_name, full_path)| {
+                    let (version, used_by) = version_usage(&workspace_ctx, crate_name.as_str());
+                    InspectCandidate {
+                        full_path,
+                        kind: item_kind_str(item_ref.inner()).to_string(),
+                        first_doc_line: first_doc_line(item_ref.comment()),
+                        version,
+                        used_by,
+                    }
+                })
+                .collect();
+            return Ok(StructuredInspectResult::Disambiguation {
+                query: request.query.clone(),
+                candidates,
+            });
         }
     }
 
     // Fall back to search-based resolution for non-path queries or queries without crate
     let search_query = path.full_path();
 
-    let crates_to_search: Vec<CrateName> = if let Some(crate_name) = specified_crate {
-        vec![crate_name]
-    } else {
+    let crates_to_search: Vec<CrateName> = if specified_crates.is_empty() {
         let mut crates = workspace_ctx.members.clone();
         crates.extend(
             workspace_ctx
@@ -200,6 +324,8 @@ pub async fn handle_inspect_item_structured(
                 .map(CrateName::new_unchecked),
         );
         crates
+    } else {
+        specified_crates
     };
 
     let mut all_results = Vec::new();
@@ -289,12 +415,13 @@ pub async fn handle_inspect_item_structured(
         "Search completed"
     );
 
-    // Deduplicate results by item ID (same item may appear at different paths due to re-exports)
+    // Deduplicate results by item ID (same item may appear at different paths due to re-exports).
+    // IDs are per crate, so versions of the same crate don't collapse into one.
     {
         let mut seen_ids = HashSet::new();
         all_results.retain(|result| {
             if let Some(id) = &result.id {
-                seen_ids.insert(*id)
+                seen_ids.insert((result.source_crate.clone(), *id))
             } else {
                 true
             }
@@ -391,6 +518,7 @@ pub async fn handle_inspect_item_structured(
             crates_to_search
                 .first()
                 .map(super::super::types::CrateName::as_str),
+            &workspace_ctx,
         );
         return Ok(StructuredInspectResult::Disambiguation {
             query: search_query,
@@ -432,12 +560,219 @@ pub async fn handle_inspect_item_structured(
         ));
     }
 
-    build_item_result(item, request.detail_level, crate_name)
+    build_item_result(item, view, crate_name)
+        .map(|result| with_version_note(result, &workspace_ctx))
+}
+
+/// Crates named by the first component of a query when it targets crate versions.
+///
+/// `syn@1` resolves to the matching version; a bare `syn` resolves to every version
+/// when the workspace has more than one. Returns an empty list otherwise, leaving the
+/// component to regular crate resolution.
+fn crate_versions(workspace: &WorkspaceContext, first: &str) -> Result<Vec<CrateName>, String> {
+    if let Some((name, _)) = first.split_once('@') {
+        let Some(meta) = workspace.get_crate(first) else {
+            let available: Vec<_> = workspace
+                .crate_versions(name)
+                .into_iter()
+                .filter_map(|meta| meta.version.as_deref())
+                .collect();
+            return Err(if available.is_empty() {
+                format!("Crate '{name}' not found in workspace")
+            } else {
+                format!(
+                    "No version of '{name}' matches '{first}'. Available versions: {}",
+                    available.join(", ")
+                )
+            });
+        };
+        return Ok(vec![CrateName::new_unchecked(
+            workspace.qualified_name(meta),
+        )]);
+    }
+
+    if !workspace.has_multiple_versions(first) {
+        return Ok(Vec::new());
+    }
+    Ok(workspace
+        .crate_versions(first)
+        .into_iter()
+        .map(|meta| CrateName::new_unchecked(workspace.qualified_name(meta)))
+        .collect())
+}
+
+/// Version and using members of a crate stored as `name@version`.
+///
+/// Empty for crates the workspace has only one version of.
+fn version_usage(workspace: &WorkspaceContext, crate_name: &str) -> (Option<String>, Vec<String>) {
+    if !crate_name.contains('@') {
+        return (None, Vec::new());
+    }
+    workspace
+        .get_crate(crate_name)
+        .map_or((None, Vec::new()), |meta| {
+            (
+                meta.version.clone(),
+                meta.used_by
+                    .iter()
+                    .map(|member| member.as_str().to_string())
+                    .collect(),
+            )
+        })
+}
+
+/// Note which version of a crate an item comes from, when the workspace has several.
+fn with_version_note(
+    result: StructuredInspectResult,
+    workspace: &WorkspaceContext,
+) -> StructuredInspectResult {
+    let StructuredInspectResult::Item {
+        full_path,
+        kind,
+        crate_name,
+        rendered,
+    } = result
+    else {
+        return result;
+    };
+
+    let rendered = match version_usage(workspace, &crate_name) {
+        (Some(version), used_by) if used_by.is_empty() => {
+            format!("// from {crate_name} (version {version})\n{rendered}")
+        }
+        (Some(version), used_by) => format!(
+            "// from {crate_name} (version {version}, used by {})\n{rendered}",
+            used_by.join(", ")
+        ),
+        (None, _) => rendered,
+    };
+    StructuredInspectResult::Item {
+        full_path,
+        kind,
+        crate_name,
+        rendered,
+    }
+}
+
+/// First non-blank line of an item's docs, trimmed.
+fn first_doc_line(docs: Option<&str>) -> Option<String> {
+    docs.and_then(|docs| {
+        docs.lines()
+            .find(|line| !line.trim().is_empty())
+            .map(|line| line.trim().to_string())
+    })
+}
+
+/// Resolve `Type::assoc` queries that lack a crate prefix.
+///
+/// Locates items named like the first path component through each crate's
+/// search index, then descends into their associated items with
+/// [`QueryContext::resolve_relative`]. Returns each match with its display path.
+fn resolve_associated_query<'a>(
+    query_ctx: &'a QueryContext,
+    crates: &[&str],
+    components: &[String],
+) -> Vec<(ItemRef<'a, Item>, String)> {
+    let Some((owner_name, rest)) = components.split_first() else {
+        return Vec::new();
+    };
+    let rest = rest.join("::");
+
+    let mut matches: Vec<(ItemRef<'a, Item>, String)> = Vec::new();
+    for crate_name in crates {
+        let Ok(index) = TermIndex::load_or_build(query_ctx, crate_name) else {
+            continue;
+        };
+        for hit in index.search(owner_name, 20) {
+            let Some((owner, segments)) =
+                query_ctx.get_item_from_id_path(hit.item.crate_name.as_str(), &hit.item.item_path)
+            else {
+                continue;
+            };
+            if owner.name() != Some(owner_name.as_str())
+                || !matches!(
+                    owner.inner(),
+                    ItemEnum::Struct(_)
+                        | ItemEnum::Enum(_)
+                        | ItemEnum::Union(_)
+                        | ItemEnum::Trait(_)
+                        | ItemEnum::Primitive(_)
+                )
+            {
+                continue;
+            }
+            let Some(found) = query_ctx.resolve_relative(owner, &rest) else {
+                continue;
+            };
+            let is_duplicate = matches.iter().any(|(existing, _)| {
+                existing.id == found.id
+                    && existing.crate_index().name() == found.crate_index().name()
+            });
+            if !is_duplicate {
+                matches.push((found, format!("{}::{rest}", segments.join("::"))));
+            }
+        }
+    }
+    matches
+}
+
+/// Turn [`resolve_associated_query`] matches into a result: the item itself
+/// when unambiguous, otherwise a disambiguation list.
+fn associated_result(
+    mut matches: Vec<(ItemRef<'_, Item>, String)>,
+    request: &InspectItemRequest,
+    view: &ItemView,
+) -> Result<StructuredInspectResult, String> {
+    if let Some(kind_filter) = request.kind {
+        matches.retain(|(item, _)| matches_kind(item.inner(), kind_filter));
+        if matches.is_empty() {
+            return Err(format!(
+                "Item '{}' found but is not a {:?}",
+                request.query, kind_filter
+            ));
+        }
+    }
+
+    if matches.len() > 1 {
+        let candidates = matches
+            .iter()
+            .take(10)
+            .map(|(item, full_path)| InspectCandidate {
+                full_path: full_path.clone(),
+                kind: item_kind_str(item.inner()).to_string(),
+                first_doc_line: first_doc_line(item.comment()),
+                version: None,
+                used_by: Vec::new(),
+            })
+            .collect();
+        return Ok(StructuredInspectResult::Disambiguation {
+            query: request.query.clone(),
+            candidates,
+        });
+    }
+
+    let (item, full_path) = matches.swap_remove(0);
+    let crate_name = item.crate_index().name().to_string();
+    build_item_result(item, view, &crate_name).map(|result| match result {
+        StructuredInspectResult::Item {
+            kind,
+            crate_name,
+            rendered,
+            ..
+        } => StructuredInspectResult::Item {
+            full_path,
+            kind,
+            crate_name,
+            rendered,
+        },
+        other @ StructuredInspectResult::Disambiguation { .. } => other,
+    })
 }
 
 /// Build a [`StructuredInspectResult::Item`] from a resolved [`ItemRef`].
 ///
-/// Renders the item via [`format_item_output`] and captures its structural
+/// Renders the item via [`format_item_output`] (or [`render_source`] for
+/// [`ItemView::Source`]) and captures its structural
 /// fields (fully-qualified path, kind) alongside the rendered blob.
 ///
 /// `path_string()` already includes the crate segment (e.g.
@@ -446,7 +781,7 @@ pub async fn handle_inspect_item_structured(
 /// `{crate_name}::{name}`.
 fn build_item_result(
     item: ItemRef<'_, Item>,
-    detail_level: DetailLevel,
+    view: &ItemView,
     crate_name: &str,
 ) -> Result<StructuredInspectResult, String> {
     let kind = format!("{:?}", item.kind());
@@ -455,7 +790,14 @@ fn build_item_result(
         .path_string()
         .unwrap_or_else(|| format!("{crate_name}::{name}"));
 
-    let rendered = format_item_output(item, detail_level, crate_name)?;
+    let rendered = match view {
+        ItemView::Docs(detail_level) => format_item_output(item, *detail_level, crate_name)?,
+        ItemView::Source(locator) => {
+            let mut output = String::new();
+            render_source(&mut output, item, locator, crate_name)?;
+            output
+        }
+    };
 
     Ok(StructuredInspectResult::Item {
         full_path,
@@ -470,6 +812,7 @@ fn build_item_result(
 fn build_candidates(
     results: &[DetailedSearchResult],
     fallback_crate: Option<&str>,
+    workspace: &WorkspaceContext,
 ) -> Vec<InspectCandidate> {
     results
         .iter()
@@ -483,23 +826,26 @@ fn build_candidates(
                 result.path.clone()
             };
 
-            let first_doc_line = result.docs.as_ref().and_then(|docs| {
-                docs.lines()
-                    .find(|line| !line.trim().is_empty())
-                    .map(|line| line.trim().to_string())
-            });
+            let (version, used_by) = result
+                .source_crate
+                .as_ref()
+                .map_or((None, Vec::new()), |src_crate| {
+                    version_usage(workspace, src_crate.as_str())
+                });
 
             InspectCandidate {
                 full_path,
                 kind: result.kind.clone(),
-                first_doc_line,
+                first_doc_line: first_doc_line(result.docs.as_deref()),
+                version,
+                used_by,
             }
         })
         .collect()
 }
 
 /// Render a [`StructuredInspectResult`] into the human-readable MCP output format.
-fn render_inspect_result(result: &StructuredInspectResult) -> String {
+pub fn render_inspect_result(result: &StructuredInspectResult) -> String {
     match result {
         StructuredInspectResult::Item { rendered, .. } => rendered.clone(),
         StructuredInspectResult::Disambiguation { query, candidates } => {
@@ -515,6 +861,9 @@ fn format_candidates(query: &str, candidates: &[InspectCandidate]) -> String {
 
     for (i, cand) in candidates.iter().enumerate() {
         let _ = write!(&mut error, "{}. {} [{}]", i + 1, cand.full_path, cand.kind);
+        if !cand.used_by.is_empty() {
+            let _ = write!(&mut error, " (used by {})", cand.used_by.join(", "));
+        }
         if let Some(line) = &cand.first_doc_line
             && !line.is_empty()
         {
@@ -547,11 +896,32 @@ fn format_item_output(
             render_constant(&mut output, item, type_, detail_level, crate_name)
         }
         ItemEnum::Static(s) => render_static(&mut output, item, s, detail_level, crate_name),
-        ItemEnum::Macro(_) | ItemEnum::ProcMacro(_) => {
-            return Err(format!(
-                "'{}' is a macro; macros are not currently supported by inspect_item",
-                item.name().unwrap_or("unknown")
-            ));
+        ItemEnum::AssocConst { type_, value } => render_assoc_const(
+            &mut output,
+            item,
+            type_,
+            value.as_deref(),
+            detail_level,
+            crate_name,
+        ),
+        ItemEnum::AssocType {
+            generics,
+            bounds,
+            type_,
+        } => render_assoc_type(
+            &mut output,
+            item,
+            generics,
+            bounds,
+            type_.as_ref(),
+            detail_level,
+            crate_name,
+        ),
+        ItemEnum::Macro(source) => {
+            render_macro(&mut output, item, source, detail_level, crate_name)
+        }
+        ItemEnum::ProcMacro(pm) => {
+            render_proc_macro(&mut output, item, pm, detail_level, crate_name)
         }
         _ => {
             return Err(format!(
@@ -573,6 +943,7 @@ fn format_item_output(
 async fn stdlib_inspect_structured(
     stdlib: &Arc<StdlibDocs>,
     request: &InspectItemRequest,
+    view: &ItemView,
     add_no_workspace_hint: bool,
 ) -> Result<StructuredInspectResult, String> {
     let path = parse_item_path(&request.query);
@@ -593,6 +964,10 @@ async fn stdlib_inspect_structured(
         return Err("Empty query".to_string());
     };
 
+    if let Some(result) = stdlib_associated_structured(stdlib, request, view).await {
+        return maybe_append_hint(result, add_no_workspace_hint);
+    }
+
     let query_ctx = stdlib.build_query_context(&target_crate).await?;
 
     let full_path = if path.path_components.len() > 1 {
@@ -615,7 +990,7 @@ async fn stdlib_inspect_structured(
                         request.query, kind_filter
                     ));
                 }
-                build_item_result(item_ref, request.detail_level, &target_crate)
+                build_item_result(item_ref, view, &target_crate)
             })
     };
 
@@ -659,7 +1034,7 @@ async fn stdlib_inspect_structured(
                 request.query, kind_filter
             ));
         }
-        let result = build_item_result(item_ref, request.detail_level, &target_crate);
+        let result = build_item_result(item_ref, view, &target_crate);
         return maybe_append_hint(result, add_no_workspace_hint);
     }
 
@@ -667,6 +1042,26 @@ async fn stdlib_inspect_structured(
     Err(format!("Failed to resolve item '{}'", request.query))
 }
 
+/// Resolve `Type::assoc` queries such as `Vec::push` against `std`.
+///
+/// Returns `None` when the query isn't of that shape or nothing matched, so
+/// callers can fall back to their own resolution.
+async fn stdlib_associated_structured(
+    stdlib: &Arc<StdlibDocs>,
+    request: &InspectItemRequest,
+    view: &ItemView,
+) -> Option<Result<StructuredInspectResult, String>> {
+    let path = parse_item_path(&request.query);
+    let first = path.path_components.first()?;
+    if path.path_components.len() < 2 || StdlibDocs::is_stdlib_crate(first) {
+        return None;
+    }
+
+    let query_ctx = stdlib.build_query_context("std").await.ok()?;
+    let matches = resolve_associated_query(&query_ctx, &["std"], &path.path_components);
+    (!matches.is_empty()).then(|| associated_result(matches, request, view))
+}
+
 /// Append a "no workspace configured" hint to a stdlib Item result's rendered
 /// output, matching the behavior of the original string handler's fallback.
 fn maybe_append_hint(
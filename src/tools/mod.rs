@@ -1,4 +1,7 @@
+pub mod cache;
+pub mod find_implementors;
 pub mod inspect_crate;
 pub mod inspect_item;
 pub mod search;
 pub(crate) mod set_workspace;
+pub mod view_source;
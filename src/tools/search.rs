@@ -1,4 +1,15 @@
-//! TF-IDF search handler for finding documentation items.
+//! Full-text search handler for finding documentation items.
+//!
+//! Text queries are parsed by [`parse_search_query`] and may hold phrases,
+//! exclusions, field qualifiers and `kind:` filters.
+//!
+//! Besides free-text queries, [`SearchMode::Signature`] ranks functions and
+//! methods by how well their signature unifies with a type-signature query
+//! such as `&str -> Result<_, io::Error>` (see [`crate::search::signature`]).
+//!
+//! Without a `crate_name`, every crate in the requested [`SearchScope`] whose
+//! docs are available is searched and the hits are merged, each labeled with
+//! its crate.
 //!
 //! # Structured and rendered APIs
 //!
@@ -6,8 +17,8 @@
 //!
 //! - [`handle_search_structured`] returns a typed [`StructuredSearchResult`]
 //!   that tests and programmatic consumers can match on. It contains
-//!   fully-qualified paths, kinds, relevance scores, and first doc lines —
-//!   no presentation concerns.
+//!   fully-qualified paths, kinds, relevance scores, first doc lines, and doc
+//!   snippets with matches marked by byte ranges — no presentation concerns.
 //! - [`handle_search`] wraps the structured variant and renders it into the
 //!   human-readable string format exposed over the MCP tool interface.
 //!
@@ -15,44 +26,227 @@
 //! brittleness of string-containment tests on MCP output.
 
 use crate::{
-    search::{QueryContext, TermIndex, score_to_percent},
+    error::{QueryError, ToolError},
+    format::TypeFormatter,
+    search::{
+        CrateIndex, ItemFilter, ItemKind, QueryContext, TermIndex,
+        index::SearchMatch,
+        item_enum_to_kind,
+        query::{PathSuggestion, SearchQuery, parse_search_query},
+        score_to_percent,
+        signature::{SignatureQuery, parse_signature_query, search_signatures},
+        snippet::{Highlighter, Snippet},
+    },
     stdlib::StdlibDocs,
     worker::DocState,
+    workspace::{CrateMetadata, CrateOrigin, WorkspaceContext},
 };
 use rmcp::schemars;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{fmt::Write as _, sync::Arc};
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct SearchRequest {
-    /// Search query term
+    /// Search query: words, "quoted phrases", -excluded words, name:/doc:/path: qualified
+    /// words and kind:struct, or a type signature like '&str -> usize' in signature mode
     pub query: String,
-    /// Crate to search within
-    pub crate_name: String,
+    /// Crate to search within. Use 'name@version' (e.g. 'syn@1') to pick one of several versions.
+    /// Omit to search every crate in scope
+    #[serde(default)]
+    pub crate_name: Option<String>,
     /// Maximum number of results to return (default: 10)
     #[serde(default = "default_limit")]
     pub limit: usize,
+    /// Search mode: 'text' (default) matches names and docs, 'signature' matches function signatures
+    #[serde(default)]
+    pub mode: SearchMode,
+    /// Workspace to query: its path or any directory inside it. Defaults to the
+    /// workspace configured with set_workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    /// Crates searched when crate_name is omitted: 'all' (default) for workspace members and
+    /// their direct dependencies, 'members' for workspace members only, 'direct' for the
+    /// direct dependencies only
+    #[serde(default)]
+    pub scope: SearchScope,
+    /// When crate_name is omitted, only search these crates (names or 'name@version' specs)
+    #[serde(default)]
+    pub crates: Vec<String>,
+    /// When crate_name is omitted, also search the standard library crates
+    #[serde(default)]
+    pub include_stdlib: bool,
+    /// Only return items of these kinds
+    #[serde(default)]
+    pub kinds: Vec<ItemKind>,
+    /// Only return items under this module path, e.g. 'tokio::sync'
+    #[serde(default)]
+    pub path_prefix: Option<String>,
+    /// Only return deprecated items (true), or leave them out (false)
+    #[serde(default)]
+    pub deprecated: Option<bool>,
+    /// Only return unsafe functions and traits (true), or leave them out (false)
+    #[serde(default, rename = "unsafe")]
+    pub is_unsafe: Option<bool>,
+    /// Only return async functions (true), or leave them out (false)
+    #[serde(default, rename = "async")]
+    pub is_async: Option<bool>,
+    /// Document crate_name with exactly these features instead of those the workspace
+    /// enables, e.g. to search feature-gated items
+    #[serde(default)]
+    pub features: Option<Vec<String>>,
+}
+
+impl SearchRequest {
+    /// The request's filters, narrowed to `query_kinds`, the kinds named by
+    /// `kind:` in the query, when there are any.
+    fn filter(&self, query_kinds: &[ItemKind]) -> ItemFilter {
+        let kinds: Vec<ItemKind> = if query_kinds.is_empty() {
+            self.kinds.clone()
+        } else {
+            query_kinds
+                .iter()
+                .copied()
+                .filter(|kind| self.kinds.is_empty() || self.kinds.contains(kind))
+                .collect()
+        };
+        ItemFilter::new(
+            &kinds,
+            self.path_prefix.as_deref(),
+            self.deprecated,
+            self.is_unsafe,
+            self.is_async,
+        )
+    }
+}
+
+impl Default for SearchRequest {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            crate_name: None,
+            limit: default_limit(),
+            mode: SearchMode::default(),
+            workspace: None,
+            scope: SearchScope::default(),
+            crates: Vec::new(),
+            include_stdlib: false,
+            kinds: Vec::new(),
+            path_prefix: None,
+            deprecated: None,
+            is_unsafe: None,
+            is_async: None,
+            features: None,
+        }
+    }
 }
 
 const fn default_limit() -> usize {
     10
 }
 
+/// How the search query is interpreted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchMode {
+    #[default]
+    Text,
+    Signature,
+}
+
+/// A request's query, parsed according to its [`SearchMode`].
+enum ParsedQuery {
+    Text(SearchQuery),
+    Signature(SignatureQuery),
+}
+
+impl ParsedQuery {
+    /// Parse the request's query according to its mode.
+    ///
+    /// A query whose `kind:`s share no kind with the request's `kinds` is an
+    /// error, since no item could pass both.
+    fn parse(request: &SearchRequest) -> Result<Self, ToolError> {
+        Ok(match request.mode {
+            SearchMode::Text => {
+                let text = parse_search_query(&request.query)?;
+                let conflicting = !request.kinds.is_empty()
+                    && !text.kinds.is_empty()
+                    && !text.kinds.iter().any(|kind| request.kinds.contains(kind));
+                if conflicting {
+                    return Err(QueryError::InvalidQuery {
+                        query: request.query.clone(),
+                        reason: "its 'kind:' filters match none of the requested kinds".to_string(),
+                    }
+                    .into());
+                }
+                Self::Text(text)
+            }
+            SearchMode::Signature => Self::Signature(parse_signature_query(&request.query)?),
+        })
+    }
+
+    /// Kinds the query itself restricts results to.
+    fn kinds(&self) -> &[ItemKind] {
+        match self {
+            Self::Text(text) => &text.kinds,
+            Self::Signature(_) => &[],
+        }
+    }
+}
+
+/// Which workspace crates a search without `crate_name` covers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchScope {
+    /// Workspace members and their dependencies.
+    #[default]
+    All,
+    /// Workspace members only.
+    Members,
+    /// Dependencies of workspace members, without the members.
+    ///
+    /// The workspace only records the dependencies members declare directly,
+    /// so these are every external crate it knows.
+    Direct,
+}
+
+impl SearchScope {
+    fn includes(self, meta: &CrateMetadata) -> bool {
+        match self {
+            Self::All => meta.origin != CrateOrigin::Standard,
+            Self::Members => meta.origin == CrateOrigin::Local,
+            Self::Direct => meta.origin == CrateOrigin::External,
+        }
+    }
+}
+
 /// Structured result of a search operation, independent of any string rendering.
 ///
 /// Tests should match on this to assert on concrete fields (full paths, kinds,
-/// relevance) rather than substring-matching the rendered output.
-#[derive(Debug, Clone)]
+/// relevance) rather than substring-matching the rendered output. Serialized
+/// as the tool's `structuredContent`, tagged by `status`.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+#[serde(tag = "status", rename_all = "snake_case")]
 pub enum StructuredSearchResult {
     /// The search ran and returned at least one hit.
     Hits {
-        crate_name: String,
+        /// Crate searched; `None` for a search across crates.
+        crate_name: Option<String>,
         query: String,
         is_stdlib: bool,
         hits: Vec<StructuredSearchHit>,
+        /// Versions searched, when the workspace has several versions of the crate.
+        versions: Vec<CrateVersion>,
+        /// Crates a search across crates left out because their docs aren't generated yet.
+        skipped: Vec<String>,
     },
     /// The search ran against a valid crate but found zero matches.
-    Empty { crate_name: String, query: String },
+    Empty {
+        /// Crate searched; `None` for a search across crates.
+        crate_name: Option<String>,
+        query: String,
+        /// Crates a search across crates left out because their docs aren't generated yet.
+        skipped: Vec<String>,
+    },
     /// The target crate was not found. Suggestions may be provided.
     CrateNotFound {
         attempted: String,
@@ -61,20 +255,71 @@ pub enum StructuredSearchResult {
 }
 
 /// A single search hit with all fields needed for rendering or programmatic use.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
 pub struct StructuredSearchHit {
     /// Fully-qualified path like `std::collections::HashMap`.
     pub full_path: String,
+    /// Crate the item belongs to.
+    pub crate_name: String,
     /// Kind as a debug-printed string (e.g. `"Struct"`, `"Function"`, `"Trait"`).
     pub kind: String,
     /// Relevance on a 0-100 scale, normalized against the top result.
     pub relevance: u32,
     /// First non-empty line of the item's doc comment, if any.
     pub first_doc_line: Option<String>,
+    /// Part of the doc comment best matching the query. Only populated in text
+    /// mode, when the query matched the docs.
+    pub snippet: Option<SearchSnippet>,
+    /// Rendered function signature. Only populated in signature mode.
+    pub signature: Option<String>,
+    /// Crate version the item belongs to, set when the workspace has several
+    /// versions of the crate. `full_path` then starts with `name@version`.
+    pub version: Option<String>,
+}
+
+/// A window of an item's docs with the words matching the query marked.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct SearchSnippet {
+    /// Doc text with whitespace collapsed, starting or ending with `...` where
+    /// docs were left out.
+    pub text: String,
+    /// Byte ranges of `text` holding matched words, sorted and non-overlapping.
+    pub highlights: Vec<HighlightRange>,
+}
+
+/// A half-open byte range of [`SearchSnippet::text`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, schemars::JsonSchema)]
+pub struct HighlightRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl From<Snippet> for SearchSnippet {
+    fn from(snippet: Snippet) -> Self {
+        Self {
+            text: snippet.text,
+            highlights: snippet
+                .highlights
+                .into_iter()
+                .map(|range| HighlightRange {
+                    start: range.start,
+                    end: range.end,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// One version of a crate the workspace uses in several versions.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct CrateVersion {
+    pub version: String,
+    /// Workspace members depending on this version.
+    pub used_by: Vec<String>,
 }
 
 /// A fuzzy crate-name suggestion surfaced when the requested crate cannot be resolved.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
 pub struct CrateSuggestion {
     pub path: String,
     /// Optional kind (e.g. `"Crate"`, `"Module"`). When `None`, the suggestion
@@ -82,12 +327,12 @@ pub struct CrateSuggestion {
     pub kind: Option<String>,
 }
 
-/// Execute the search operation using TF-IDF indexing and return the rendered output.
+/// Execute the search operation using the BM25F search index and return the rendered output.
 ///
 /// This is the string-returning wrapper consumed by the MCP tool interface.
 /// For programmatic use or tests that want to assert on structured fields,
 /// call [`handle_search_structured`] directly.
-#[tracing::instrument(skip_all, fields(query = %request.query, crate_name = %request.crate_name))]
+#[tracing::instrument(skip_all, fields(query = %request.query, crate_name = ?request.crate_name))]
 pub async fn handle_search(
     state: &Arc<DocState>,
     request: SearchRequest,
@@ -101,159 +346,580 @@ pub async fn handle_search(
 /// Returns a [`StructuredSearchResult`] rather than a rendered string.
 /// Errors (missing workspace, stdlib not available) still surface as `Err(String)`
 /// so they can be displayed uniformly by callers.
-#[tracing::instrument(skip_all, fields(query = %request.query, crate_name = %request.crate_name))]
+#[tracing::instrument(skip_all, fields(query = %request.query, crate_name = ?request.crate_name))]
 pub async fn handle_search_structured(
     state: &Arc<DocState>,
     request: SearchRequest,
 ) -> Result<StructuredSearchResult, String> {
+    // Reject malformed queries before loading anything.
+    let query = ParsedQuery::parse(&request).map_err(|e| e.user_message())?;
+
+    let Some(crate_name) = request.crate_name.as_deref() else {
+        if request.features.is_some() {
+            return Err("Features apply to crate_name; set it to a workspace crate".to_string());
+        }
+        return search_all_crates(state, &request, &query).await;
+    };
+
     // Route stdlib crates to the dedicated handler.
-    if StdlibDocs::is_stdlib_crate(&request.crate_name)
+    if StdlibDocs::is_stdlib_crate(crate_name)
         && let Some(stdlib) = state.stdlib()
     {
-        tracing::debug!(crate_name = %request.crate_name, "Routing search to stdlib");
-        return stdlib_search_structured(stdlib, &request).await;
+        if request.features.is_some() {
+            return Err("Features can only be chosen for workspace crates".to_string());
+        }
+        tracing::debug!(crate_name, "Routing search to stdlib");
+        return stdlib_search_structured(stdlib, crate_name, &request, &query).await;
     }
 
     // Workspace-based search.
-    let Some(workspace_ctx) = state.workspace().await else {
+    let Some(workspace_ctx) = state.select_workspace(request.workspace.as_deref()).await? else {
         if let Some(stdlib) = state.stdlib() {
-            if StdlibDocs::is_stdlib_crate(&request.crate_name) {
-                return stdlib_search_structured(stdlib, &request).await;
-            }
-
             return Err(format!(
-                "Crate '{}' not found. No workspace configured.\n\n\
+                "Crate '{crate_name}' not found. No workspace configured.\n\n\
                  Available for search without workspace:\n\
                  • Standard library: {}\n\n\
                  Use set_workspace to configure a Rust project.",
-                request.crate_name,
                 stdlib.available_crates().join(", ")
             ));
         }
 
-        tracing::warn!("Search failed: no workspace and no stdlib available");
-        return Err(
-            "No workspace configured and standard library docs not available.\n\n\
-             To configure a workspace:\n\
-             • Use set_workspace with a path to a Rust project\n\n\
-             To enable standard library docs:\n\
-             • Run: rustup component add rust-docs-json --toolchain nightly"
-                .to_string(),
-        );
+        return Err(no_docs_available());
     };
 
-    let query_ctx = QueryContext::new(Arc::new(workspace_ctx));
-    Ok(run_search(&query_ctx, &request, false))
+    let query_ctx = match &request.features {
+        Some(features) => state
+            .query_context(workspace_ctx)
+            .with_features(crate_name, features),
+        None => state.query_context(workspace_ctx),
+    };
+    Ok(run_search(&query_ctx, crate_name, &request, &query, false))
+}
+
+/// Error for a search with neither a workspace nor stdlib docs to search.
+fn no_docs_available() -> String {
+    tracing::warn!("Search failed: no workspace and no stdlib available");
+    "No workspace configured and standard library docs not available.\n\n\
+     To configure a workspace:\n\
+     • Use set_workspace with a path to a Rust project\n\n\
+     To enable standard library docs:\n\
+     • Run: rustup component add rust-docs-json --toolchain nightly"
+        .to_string()
 }
 
 /// Structured stdlib search. Shared between the direct-route and the
 /// no-workspace-fallback paths in [`handle_search_structured`].
 async fn stdlib_search_structured(
     stdlib: &Arc<StdlibDocs>,
+    crate_name: &str,
+    request: &SearchRequest,
+    query: &ParsedQuery,
+) -> Result<StructuredSearchResult, String> {
+    let query_ctx = stdlib.build_query_context(crate_name).await?;
+    Ok(run_search(&query_ctx, crate_name, request, query, true))
+}
+
+/// Search every crate the request's scope covers and merge the hits.
+///
+/// Only crates whose docs are already available are searched, so the search
+/// never waits on rustdoc; the rest are reported as skipped.
+async fn search_all_crates(
+    state: &Arc<DocState>,
     request: &SearchRequest,
+    query: &ParsedQuery,
 ) -> Result<StructuredSearchResult, String> {
-    let query_ctx = stdlib.build_query_context(&request.crate_name).await?;
-    Ok(run_search(&query_ctx, request, true))
+    let workspace = state.select_workspace(request.workspace.as_deref()).await?;
+    let stdlib_crates: Vec<&str> = state
+        .stdlib()
+        .filter(|_| request.include_stdlib)
+        .map(|stdlib| stdlib.available_crates())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|name| request.crates.is_empty() || request.crates.iter().any(|c| c == name))
+        .collect();
+
+    if workspace.is_none() && stdlib_crates.is_empty() {
+        return match state.stdlib() {
+            Some(stdlib) => Err(format!(
+                "No workspace configured.\n\n\
+                 Set include_stdlib to search the standard library ({}), \
+                 or use set_workspace to configure a Rust project.",
+                stdlib.available_crates().join(", ")
+            )),
+            None => Err(no_docs_available()),
+        };
+    }
+
+    let mut merged = MergedHits::default();
+    // Scoped so the non-Send QueryContext is gone before the stdlib searches await.
+    if let Some(workspace) = workspace {
+        let crate_names = scoped_crates(&workspace, request);
+        let query_ctx = state.query_context(workspace);
+        merged.search(&query_ctx, &crate_names, request, query);
+    }
+    if let Some(stdlib) = state.stdlib() {
+        for crate_name in stdlib_crates {
+            let query_ctx = stdlib.build_query_context(crate_name).await?;
+            merged.search(&query_ctx, &[crate_name.to_string()], request, query);
+        }
+    }
+
+    Ok(merged.finish(request, matches!(query, ParsedQuery::Signature(_))))
+}
+
+/// Qualified names of the workspace crates a search across crates covers, in
+/// generation priority order.
+fn scoped_crates(workspace: &WorkspaceContext, request: &SearchRequest) -> Vec<String> {
+    let allowed: Vec<&CrateMetadata> = request
+        .crates
+        .iter()
+        .flat_map(|spec| workspace.crate_versions(spec))
+        .collect();
+
+    workspace
+        .prioritized_crates()
+        .iter()
+        .filter_map(|name| workspace.get_crate(name.as_str()))
+        .filter(|meta| request.scope.includes(meta))
+        .filter(|meta| request.crates.is_empty() || allowed.iter().any(|a| a.key() == meta.key()))
+        .map(|meta| workspace.qualified_name(meta))
+        .collect()
+}
+
+/// Hits gathered by a search across crates, with the score they're merged by.
+#[derive(Default)]
+struct MergedHits {
+    hits: Vec<(f32, StructuredSearchHit)>,
+    skipped: Vec<String>,
+}
+
+impl MergedHits {
+    /// Search each of `crate_names` whose docs `query_ctx` has, skipping the rest.
+    fn search(
+        &mut self,
+        query_ctx: &QueryContext,
+        crate_names: &[String],
+        request: &SearchRequest,
+        query: &ParsedQuery,
+    ) {
+        let filter = request.filter(query.kinds());
+        for crate_name in crate_names {
+            if !query_ctx.has_docs(crate_name) {
+                self.skipped.push(crate_name.clone());
+                continue;
+            }
+            let text = match query {
+                ParsedQuery::Text(text) => text,
+                ParsedQuery::Signature(signature) => {
+                    match query_ctx.load_crate(crate_name) {
+                        Ok(crate_index) => self.hits.extend(signature_hits(
+                            crate_index,
+                            &crate_label(query_ctx, crate_name),
+                            signature,
+                            &filter,
+                            request.limit,
+                        )),
+                        Err(e) => tracing::debug!(crate_name, error = %e, "Skipping crate"),
+                    }
+                    continue;
+                }
+            };
+
+            let Ok(index) = TermIndex::load_or_build(query_ctx, crate_name) else {
+                tracing::debug!(crate_name, "Skipping crate that failed to load");
+                continue;
+            };
+            let highlighter = Highlighter::new(text);
+            self.hits.extend(
+                index
+                    .search_normalized(text, request.limit, |path| {
+                        passes(query_ctx, crate_name, &filter, path)
+                    })
+                    .iter()
+                    .map(|m| (m.rank, text_hit(query_ctx, m, &highlighter, 0))),
+            );
+        }
+    }
+
+    /// Keep the best `limit` hits. Text relevance is relative to the best hit,
+    /// signature relevance stays absolute.
+    fn finish(mut self, request: &SearchRequest, is_signature: bool) -> StructuredSearchResult {
+        self.hits.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        self.hits.truncate(request.limit);
+
+        tracing::debug!(
+            query = %request.query,
+            result_count = self.hits.len(),
+            skipped = self.skipped.len(),
+            "Search across crates completed"
+        );
+
+        let Some(&(max_score, _)) = self.hits.first() else {
+            return StructuredSearchResult::Empty {
+                crate_name: None,
+                query: request.query.clone(),
+                skipped: self.skipped,
+            };
+        };
+
+        let hits = self
+            .hits
+            .into_iter()
+            .map(|(score, mut hit)| {
+                if !is_signature {
+                    hit.relevance = score_to_percent(score / max_score);
+                }
+                hit
+            })
+            .collect();
+
+        StructuredSearchResult::Hits {
+            crate_name: None,
+            query: request.query.clone(),
+            is_stdlib: false,
+            hits,
+            versions: Vec::new(),
+            skipped: self.skipped,
+        }
+    }
+}
+
+/// Name a crate is labeled with in search hits: the name it's published under,
+/// without any version.
+fn crate_label(query_ctx: &QueryContext, crate_name: &str) -> String {
+    query_ctx.workspace().get_crate(crate_name).map_or_else(
+        || {
+            crate_name
+                .split('@')
+                .next()
+                .unwrap_or(crate_name)
+                .to_string()
+        },
+        |meta| meta.name.as_str().to_string(),
+    )
 }
 
 /// Core search routine: resolves the crate, runs the query, and builds a
 /// [`StructuredSearchResult`]. Shared between workspace and stdlib paths.
 fn run_search(
     query_ctx: &QueryContext,
+    crate_name: &str,
     request: &SearchRequest,
+    query: &ParsedQuery,
     is_stdlib: bool,
 ) -> StructuredSearchResult {
-    let index = match TermIndex::load_or_build(query_ctx, &request.crate_name) {
-        Ok(index) => index,
-        Err(mut suggestions) => {
-            tracing::debug!(
-                crate_name = %request.crate_name,
-                suggestions = suggestions.len(),
-                "Crate not found, returning suggestions"
-            );
-            suggestions.sort_by(|a, b| b.score().total_cmp(&a.score()));
-            let suggestions: Vec<CrateSuggestion> = suggestions
-                .into_iter()
-                .take(5)
-                .filter(|s| s.score() > 0.8)
-                .map(|s| CrateSuggestion {
-                    path: s.path().to_string(),
-                    kind: s.item().map(|item| format!("{:?}", item.kind())),
-                })
-                .collect();
-
-            return StructuredSearchResult::CrateNotFound {
-                attempted: request.crate_name.clone(),
-                suggestions,
+    let text = match query {
+        ParsedQuery::Text(text) => text,
+        ParsedQuery::Signature(signature) => {
+            let mut suggestions = vec![];
+            return match query_ctx.resolve_path(crate_name, &mut suggestions) {
+                Some(item) => run_signature_search(
+                    item.crate_index(),
+                    &crate_label(query_ctx, crate_name),
+                    request,
+                    signature,
+                    is_stdlib,
+                ),
+                None => crate_not_found(crate_name, suggestions),
             };
         }
     };
 
-    let matches = index.search(&request.query, request.limit);
+    // A crate present in several versions is searched in each of them
+    let workspace = query_ctx.workspace();
+    let versions = searched_versions(workspace, crate_name);
+    let crate_names: Vec<String> = if versions.is_empty() {
+        vec![crate_name.to_string()]
+    } else {
+        versions
+            .iter()
+            .map(|meta| workspace.qualified_name(meta))
+            .collect()
+    };
+
+    let filter = request.filter(query.kinds());
+    let mut matches = Vec::new();
+    for name in &crate_names {
+        let index = match TermIndex::load_or_build(query_ctx, name) {
+            Ok(index) => index,
+            Err(suggestions) => return crate_not_found(crate_name, suggestions),
+        };
+        matches.extend(index.search_where(text, request.limit, |path| {
+            passes(query_ctx, name, &filter, path)
+        }));
+    }
+    if crate_names.len() > 1 {
+        matches.sort_by(|a, b| b.rank.total_cmp(&a.rank));
+        matches.truncate(request.limit);
+    }
 
     tracing::debug!(
         query = %request.query,
-        crate_name = %request.crate_name,
+        crate_name,
         result_count = matches.len(),
         "Search completed"
     );
 
     if matches.is_empty() {
         return StructuredSearchResult::Empty {
-            crate_name: request.crate_name.clone(),
+            crate_name: Some(crate_name.to_string()),
             query: request.query.clone(),
+            skipped: Vec::new(),
         };
     }
 
     let max_score = matches.first().map_or(1.0, |r| r.rank);
+    let highlighter = Highlighter::new(text);
     let hits: Vec<StructuredSearchHit> = matches
         .iter()
         .map(|m| {
             let relevance = score_to_percent(m.rank / max_score);
-            match query_ctx.get_item_from_id_path(m.item.crate_name.as_str(), &m.item.item_path) {
-                Some((item, path_segments)) => {
-                    let full_path = path_segments.join("::");
-                    let kind = format!("{:?}", item.kind());
-                    let first_doc_line = item.comment().and_then(|docs| {
-                        docs.lines()
-                            .find(|line| !line.trim().is_empty())
-                            .map(|line| line.trim().to_string())
-                    });
-                    StructuredSearchHit {
-                        full_path,
-                        kind,
-                        relevance,
-                        first_doc_line,
-                    }
-                }
-                None => StructuredSearchHit {
-                    full_path: "[Unable to resolve item]".to_string(),
-                    kind: "Unknown".to_string(),
-                    relevance,
-                    first_doc_line: None,
-                },
-            }
+            text_hit(query_ctx, m, &highlighter, relevance)
         })
         .collect();
 
     StructuredSearchResult::Hits {
-        crate_name: request.crate_name.clone(),
+        crate_name: Some(crate_name.to_string()),
         query: request.query.clone(),
         is_stdlib,
         hits,
+        versions: versions
+            .into_iter()
+            .filter_map(CrateVersion::from_metadata)
+            .collect(),
+        skipped: Vec::new(),
+    }
+}
+
+/// Whether the item at ID path `path` of `crate_name` passes `filter`.
+fn passes(query_ctx: &QueryContext, crate_name: &str, filter: &ItemFilter, path: &[u32]) -> bool {
+    filter.is_empty()
+        || query_ctx
+            .get_item_from_id_path(crate_name, path)
+            .is_some_and(|(item, segments)| filter.matches(&item, &segments))
+}
+
+/// Resolve a text search match into a hit, with a snippet of its docs from `highlighter`.
+fn text_hit(
+    query_ctx: &QueryContext,
+    m: &SearchMatch,
+    highlighter: &Highlighter,
+    relevance: u32,
+) -> StructuredSearchHit {
+    let crate_name = m.item.crate_name.as_str();
+    let version = crate_name
+        .contains('@')
+        .then(|| {
+            query_ctx
+                .workspace()
+                .get_version(crate_name)
+                .map(str::to_string)
+        })
+        .flatten();
+    let label = crate_label(query_ctx, crate_name);
+    match query_ctx.get_item_from_id_path(crate_name, &m.item.item_path) {
+        Some((item, mut path_segments)) => {
+            if version.is_some()
+                && let Some(first) = path_segments.first_mut()
+            {
+                *first = crate_name;
+            }
+            let full_path = path_segments.join("::");
+            let kind = format!("{:?}", item.kind());
+            let first_doc_line = item.comment().and_then(|docs| {
+                docs.lines()
+                    .find(|line| !line.trim().is_empty())
+                    .map(|line| line.trim().to_string())
+            });
+            let snippet = item
+                .comment()
+                .and_then(|docs| highlighter.snippet(docs))
+                .map(SearchSnippet::from);
+            StructuredSearchHit {
+                full_path,
+                crate_name: label,
+                kind,
+                relevance,
+                first_doc_line,
+                snippet,
+                signature: None,
+                version,
+            }
+        }
+        None => StructuredSearchHit {
+            full_path: "[Unable to resolve item]".to_string(),
+            crate_name: label,
+            kind: "Unknown".to_string(),
+            relevance,
+            first_doc_line: None,
+            snippet: None,
+            signature: None,
+            version,
+        },
+    }
+}
+
+impl CrateVersion {
+    fn from_metadata(meta: &CrateMetadata) -> Option<Self> {
+        Some(Self {
+            version: meta.version.clone()?,
+            used_by: meta
+                .used_by
+                .iter()
+                .map(|member| member.as_str().to_string())
+                .collect(),
+        })
+    }
+}
+
+/// Crate versions a search covers: every version of a bare crate name the workspace
+/// has several versions of, or the one selected by a `name@version` spec.
+///
+/// Empty for crates with a single version, which are searched by name.
+fn searched_versions<'w>(
+    workspace: &'w WorkspaceContext,
+    crate_name: &str,
+) -> Vec<&'w CrateMetadata> {
+    if crate_name.contains('@') {
+        return workspace.get_crate(crate_name).into_iter().collect();
+    }
+    if workspace.has_multiple_versions(crate_name) {
+        return workspace.crate_versions(crate_name);
+    }
+    Vec::new()
+}
+
+/// Rank the crate's functions and methods against a parsed signature query.
+///
+/// Relevance is the absolute unification score rather than being normalized
+/// against the top hit, so a weak best match still reads as weak.
+fn run_signature_search(
+    crate_index: &CrateIndex,
+    crate_name: &str,
+    request: &SearchRequest,
+    signature: &SignatureQuery,
+    is_stdlib: bool,
+) -> StructuredSearchResult {
+    let hits: Vec<StructuredSearchHit> = signature_hits(
+        crate_index,
+        crate_name,
+        signature,
+        &request.filter(&[]),
+        request.limit,
+    )
+    .into_iter()
+    .map(|(_, hit)| hit)
+    .collect();
+
+    tracing::debug!(
+        query = %request.query,
+        crate_name,
+        result_count = hits.len(),
+        "Signature search completed"
+    );
+
+    if hits.is_empty() {
+        return StructuredSearchResult::Empty {
+            crate_name: Some(crate_name.to_string()),
+            query: request.query.clone(),
+            skipped: Vec::new(),
+        };
+    }
+
+    StructuredSearchResult::Hits {
+        crate_name: Some(crate_name.to_string()),
+        query: request.query.clone(),
+        is_stdlib,
+        hits,
+        versions: Vec::new(),
+        skipped: Vec::new(),
+    }
+}
+
+/// Signature matches of one crate as hits, paired with their unification score.
+fn signature_hits(
+    crate_index: &CrateIndex,
+    crate_name: &str,
+    signature: &SignatureQuery,
+    filter: &ItemFilter,
+    limit: usize,
+) -> Vec<(f32, StructuredSearchHit)> {
+    let fmt = TypeFormatter::new(crate_index);
+    search_signatures(crate_index, signature, filter, limit)
+        .into_iter()
+        .filter_map(|m| {
+            let item = crate_index.get_item(m.id)?;
+            let mut rendered = String::new();
+            let _ = fmt.write_function_signature(&mut rendered, item);
+            let hit = StructuredSearchHit {
+                full_path: m.path,
+                crate_name: crate_name.to_string(),
+                kind: format!("{:?}", item_enum_to_kind(&item.inner)),
+                relevance: score_to_percent(m.score),
+                first_doc_line: item.docs.as_deref().and_then(|docs| {
+                    docs.lines()
+                        .find(|line| !line.trim().is_empty())
+                        .map(|line| line.trim().to_string())
+                }),
+                snippet: None,
+                signature: Some(rendered.trim_end().to_string()),
+                version: None,
+            };
+            Some((m.score, hit))
+        })
+        .collect()
+}
+
+/// Build a [`StructuredSearchResult::CrateNotFound`] from path-resolution suggestions.
+fn crate_not_found(
+    crate_name: &str,
+    mut suggestions: Vec<PathSuggestion<'_>>,
+) -> StructuredSearchResult {
+    tracing::debug!(
+        crate_name,
+        suggestions = suggestions.len(),
+        "Crate not found, returning suggestions"
+    );
+    suggestions.sort_by(|a, b| b.score().total_cmp(&a.score()));
+    let suggestions: Vec<CrateSuggestion> = suggestions
+        .into_iter()
+        .take(5)
+        .filter(|s| s.score() > 0.8)
+        .map(|s| CrateSuggestion {
+            path: s.path().to_string(),
+            kind: s.item().map(|item| format!("{:?}", item.kind())),
+        })
+        .collect();
+
+    StructuredSearchResult::CrateNotFound {
+        attempted: crate_name.to_string(),
+        suggestions,
     }
 }
 
 /// Render a [`StructuredSearchResult`] into the human-readable MCP output format.
-fn render_search_result(result: &StructuredSearchResult) -> String {
+pub fn render_search_result(result: &StructuredSearchResult) -> String {
     match result {
         StructuredSearchResult::Hits {
             crate_name,
             query,
             is_stdlib,
             hits,
-        } => render_hits(crate_name, query, *is_stdlib, hits),
-        StructuredSearchResult::Empty { crate_name, query } => render_empty(crate_name, query),
+            versions,
+            skipped,
+        } => render_hits(
+            crate_name.as_deref(),
+            query,
+            *is_stdlib,
+            hits,
+            versions,
+            skipped,
+        ),
+        StructuredSearchResult::Empty {
+            crate_name,
+            query,
+            skipped,
+        } => render_empty(crate_name.as_deref(), query, skipped),
         StructuredSearchResult::CrateNotFound {
             attempted,
             suggestions,
@@ -262,35 +928,85 @@ fn render_search_result(result: &StructuredSearchResult) -> String {
 }
 
 fn render_hits(
-    crate_name: &str,
+    crate_name: Option<&str>,
     query: &str,
     is_stdlib: bool,
     hits: &[StructuredSearchHit],
+    versions: &[CrateVersion],
+    skipped: &[String],
 ) -> String {
     let source = if is_stdlib { " (standard library)" } else { "" };
 
-    let mut output = format!("Search results for '{query}' in '{crate_name}'{source}:\n\n");
+    let mut output = match crate_name {
+        Some(crate_name) => format!("Search results for '{query}' in '{crate_name}'{source}:\n\n"),
+        None => format!("Search results for '{query}' across crates:\n\n"),
+    };
+
+    if versions.len() > 1 {
+        let _ = writeln!(&mut output, "Searched {} versions:", versions.len());
+        for version in versions {
+            let _ = write!(&mut output, "  - {}", version.version);
+            if !version.used_by.is_empty() {
+                let _ = write!(&mut output, " (used by {})", version.used_by.join(", "));
+            }
+            output.push('\n');
+        }
+        output.push('\n');
+    }
 
     for (idx, hit) in hits.iter().enumerate() {
+        // Label hits with their crate when they come from several
+        let label = if crate_name.is_none() {
+            format!(", {}", hit.crate_name)
+        } else {
+            String::new()
+        };
         let _ = writeln!(
             &mut output,
-            "{}. `{}` ({}) - relevance: {}%",
+            "{}. `{}` ({}{label}) - relevance: {}%",
             idx + 1,
             hit.full_path,
             hit.kind,
             hit.relevance
         );
-        if let Some(line) = &hit.first_doc_line {
+        if let Some(signature) = &hit.signature {
+            for line in signature.lines() {
+                let _ = writeln!(&mut output, "   {line}");
+            }
+        }
+        if let Some(snippet) = &hit.snippet {
+            let _ = writeln!(&mut output, "   {}", bold_highlights(snippet));
+        } else if let Some(line) = &hit.first_doc_line {
             let _ = writeln!(&mut output, "   {line}");
         }
         output.push('\n');
     }
 
+    render_skipped(&mut output, skipped);
+    output
+}
+
+/// The snippet's text with its highlights in Markdown bold.
+fn bold_highlights(snippet: &SearchSnippet) -> String {
+    let mut output = String::with_capacity(snippet.text.len() + 4 * snippet.highlights.len());
+    let mut rest = 0;
+    for range in &snippet.highlights {
+        output.push_str(&snippet.text[rest..range.start]);
+        output.push_str("**");
+        output.push_str(&snippet.text[range.start..range.end]);
+        output.push_str("**");
+        rest = range.end;
+    }
+    output.push_str(&snippet.text[rest..]);
     output
 }
 
-fn render_empty(crate_name: &str, query: &str) -> String {
-    let mut msg = format!("No results found for '{query}' in crate '{crate_name}'.\n\n");
+fn render_empty(crate_name: Option<&str>, query: &str, skipped: &[String]) -> String {
+    let mut msg = match crate_name {
+        Some(crate_name) => format!("No results found for '{query}' in crate '{crate_name}'.\n\n"),
+        None => format!("No results found for '{query}' in any crate.\n\n"),
+    };
+    render_skipped(&mut msg, skipped);
     msg.push_str("Search tips:\n");
     msg.push_str("• Try a shorter or more general term\n");
     msg.push_str("• Search for types like 'HashMap', 'Vec', 'String'\n");
@@ -302,6 +1018,25 @@ fn render_empty(crate_name: &str, query: &str) -> String {
     msg
 }
 
+/// Maximum number of skipped crates listed by name.
+const MAX_SKIPPED_LISTED: usize = 10;
+
+/// Note the crates a search across crates couldn't cover yet.
+fn render_skipped(output: &mut String, skipped: &[String]) {
+    if skipped.is_empty() {
+        return;
+    }
+    let listed = skipped[..skipped.len().min(MAX_SKIPPED_LISTED)].join(", ");
+    let _ = write!(
+        output,
+        "Not searched, documentation not generated yet: {listed}"
+    );
+    if skipped.len() > MAX_SKIPPED_LISTED {
+        let _ = write!(output, " and {} more", skipped.len() - MAX_SKIPPED_LISTED);
+    }
+    output.push_str("\n\n");
+}
+
 fn render_crate_not_found(attempted: &str, suggestions: &[CrateSuggestion]) -> String {
     let mut result = format!("Crate '{attempted}' not found. Did you mean one of these?\n\n");
     for suggestion in suggestions {
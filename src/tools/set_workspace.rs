@@ -1,7 +1,13 @@
 use crate::error::{ConfigError, ToolError};
-use crate::types::CrateName;
-use crate::workspace::{CrateMetadata, CrateOrigin, WorkspaceContext, find_workspace_root};
-use cargo_metadata::{DependencyKind, Metadata, MetadataCommand};
+use crate::types::{CrateKey, CrateName};
+use crate::workspace::{
+    CrateMetadata, CrateOrigin, MemberSources, WorkspaceContext, find_workspace_root,
+};
+use cargo_metadata::{
+    DependencyKind, Metadata, MetadataCommand, Node, Package, PackageId, TargetKind,
+};
+use rmcp::schemars;
+use serde::Serialize;
 use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
@@ -108,11 +114,15 @@ pub(crate) async fn handle_set_workspace(
         .into());
     }
 
-    // Use cargo_metadata to discover workspace (CPU-bound, use spawn_blocking)
+    // Use cargo_metadata to discover workspace (CPU-bound, use spawn_blocking).
+    // Cargo finds `.cargo/config.toml` from its working directory, so run it
+    // from the workspace root for `build.target-dir` to apply.
     let cargo_toml_clone = cargo_toml.clone();
+    let metadata_dir = workspace_root.clone();
     let metadata = tokio::task::spawn_blocking(move || {
         MetadataCommand::new()
             .manifest_path(&cargo_toml_clone)
+            .current_dir(&metadata_dir)
             .exec()
             .map_err(|e| {
                 ToolError::Config(ConfigError::CargoMetadata {
@@ -137,62 +147,100 @@ pub(crate) async fn handle_set_workspace(
         root_crate: metadata
             .root_package()
             .map(|p| CrateName::new_unchecked(p.name.to_string())),
+        target_dir: metadata.target_directory.clone().into_std_path_buf(),
     };
 
     Ok((workspace_root, workspace_ctx, workspace_changed))
 }
 
+/// Structured outcome of a `set_workspace` call.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub(crate) struct StructuredWorkspaceResult {
+    /// Canonical workspace root now in use.
+    pub workspace_root: PathBuf,
+    /// Workspace that was configured before this call, if it differed.
+    pub previous_root: Option<PathBuf>,
+    /// Whether the configured workspace changed.
+    pub changed: bool,
+    /// Workspace member crates.
+    pub members: Vec<String>,
+    /// External dependencies, sorted by name.
+    pub dependencies: Vec<WorkspaceDependency>,
+}
+
+/// A resolved external dependency of the workspace.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub(crate) struct WorkspaceDependency {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+impl StructuredWorkspaceResult {
+    /// Summarize a freshly configured workspace.
+    pub(crate) fn new(
+        path: &Path,
+        metadata: &WorkspaceContext,
+        old_workspace: Option<&Path>,
+        changed: bool,
+    ) -> Self {
+        let mut dependencies: Vec<_> = metadata
+            .crate_info
+            .iter()
+            .filter(|(_, info)| info.origin == CrateOrigin::External)
+            .map(|(key, info)| WorkspaceDependency {
+                name: key.name.as_str().to_string(),
+                version: info.version.clone(),
+            })
+            .collect();
+        dependencies.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+
+        Self {
+            workspace_root: path.to_path_buf(),
+            previous_root: old_workspace.filter(|_| changed).map(Path::to_path_buf),
+            changed,
+            members: metadata
+                .members
+                .iter()
+                .map(|member| member.as_str().to_string())
+                .collect(),
+            dependencies,
+        }
+    }
+}
+
 /// Format a user-friendly response showing workspace configuration results.
-pub(crate) fn format_response(
-    path: &Path,
-    metadata: &WorkspaceContext,
-    old_workspace: Option<&Path>,
-    changed: bool,
-) -> String {
+pub(crate) fn render_workspace_result(result: &StructuredWorkspaceResult) -> String {
     use std::fmt::Write as _;
 
-    let header = if !changed {
-        format!("Workspace already set to: `{}`\n\n", path.display())
-    } else if let Some(old) = old_workspace {
+    let path = result.workspace_root.display();
+    let header = if !result.changed {
+        format!("Workspace already set to: `{path}`\n\n")
+    } else if let Some(old) = &result.previous_root {
         format!(
-            "Workspace changed:\n  From: `{}`\n  To:   `{}`\n\n",
+            "Workspace changed:\n  From: `{}`\n  To:   `{path}`\n\n",
             old.display(),
-            path.display()
         )
     } else {
-        format!("Workspace set to: `{}`\n\n", path.display())
+        format!("Workspace set to: `{path}`\n\n")
     };
 
     let mut response = header;
 
-    if !metadata.members.is_empty() {
-        let _ = writeln!(response, "Workspace members ({}):", metadata.members.len());
-        for member in &metadata.members {
+    if !result.members.is_empty() {
+        let _ = writeln!(response, "Workspace members ({}):", result.members.len());
+        for member in &result.members {
             let _ = writeln!(response, "  - {}", member);
         }
         response.push('\n');
     }
 
-    let dep_count = metadata
-        .crate_info
-        .values()
-        .filter(|info| info.origin == CrateOrigin::External)
-        .count();
-
+    let dep_count = result.dependencies.len();
     if dep_count > 0 {
         let _ = writeln!(response, "Dependencies ({dep_count}):");
 
-        let mut dep_names: Vec<_> = metadata
-            .crate_info
-            .iter()
-            .filter(|(_, info)| info.origin == CrateOrigin::External)
-            .map(|(name, info)| (name.as_str(), info))
-            .collect();
-        dep_names.sort_by_key(|(name, _)| *name);
-
-        for (name, info) in dep_names.iter().take(10) {
-            let version = info.version.as_deref().unwrap_or("unknown");
-            let _ = writeln!(response, "  - {name} v{version}");
+        for dep in result.dependencies.iter().take(10) {
+            let version = dep.version.as_deref().unwrap_or("unknown");
+            let _ = writeln!(response, "  - {} v{version}", dep.name);
         }
         if dep_count > 10 {
             let _ = writeln!(response, "  ... and {} more", dep_count - 10);
@@ -203,92 +251,159 @@ pub(crate) fn format_response(
 }
 
 /// Generate comprehensive crate information with usage tracking.
+///
+/// Dependencies come from the resolve graph, so a crate that the workspace uses in
+/// several versions gets one entry per version.
 fn collect_crate_metadata(
     metadata: &Metadata,
     member_names: &[CrateName],
-) -> HashMap<CrateName, CrateMetadata> {
+) -> HashMap<CrateKey, CrateMetadata> {
     let mut crates = HashMap::new();
     let is_workspace = member_names.len() > 1;
     let root_package_name = metadata.root_package().map(|p| p.name.as_str());
+    let resolved_features = resolved_features(metadata);
 
     // 1. Add workspace members
     for package in metadata.workspace_packages() {
-        crates.insert(
-            CrateName::new_unchecked(package.name.to_string()),
-            CrateMetadata {
-                origin: CrateOrigin::Local,
-                name: CrateName::new_unchecked(package.name.to_string()),
-                version: Some(package.version.to_string()),
-                description: package.description.clone(),
-                dev_dep: false,
-                is_root_crate: !is_workspace
-                    && root_package_name.is_some_and(|rp| package.name == rp),
-                used_by: vec![],
-            },
-        );
+        let metadata = CrateMetadata {
+            origin: CrateOrigin::Local,
+            name: CrateName::new_unchecked(package.name.to_string()),
+            version: Some(package.version.to_string()),
+            description: package.description.clone(),
+            dev_dep: false,
+            is_root_crate: !is_workspace && root_package_name.is_some_and(|rp| package.name == rp),
+            used_by: vec![],
+            features: resolved_features.get(&package.id).cloned(),
+            sources: Some(member_sources(package)),
+        };
+        crates.insert(metadata.key(), metadata);
     }
 
-    // 2. Track dependency usage
-    let mut dep_usage: BTreeMap<String, Vec<CrateName>> = BTreeMap::new();
-    let mut dep_dev_status: BTreeMap<String, bool> = BTreeMap::new();
+    // 2. Track dependency usage per resolved package, so that each version of a
+    //    crate records the members that actually use it
+    let packages: HashMap<&PackageId, &Package> =
+        metadata.packages.iter().map(|p| (&p.id, p)).collect();
+    let nodes: HashMap<&PackageId, &Node> = metadata
+        .resolve
+        .iter()
+        .flat_map(|resolve| &resolve.nodes)
+        .map(|node| (&node.id, node))
+        .collect();
+
+    let mut dep_usage: BTreeMap<&PackageId, (Vec<CrateName>, bool)> = BTreeMap::new();
 
     for package in metadata.workspace_packages() {
-        for dep in &package.dependencies {
-            if dep.path.is_some() || member_names.iter().any(|m| m.matches(&dep.name)) {
+        let Some(node) = nodes.get(&package.id) else {
+            continue;
+        };
+        for dep in &node.deps {
+            let Some(dep_package) = packages.get(&dep.pkg) else {
+                continue;
+            };
+            // Path dependencies and other members aren't external crates
+            if dep_package.source.is_none() || metadata.workspace_members.contains(&dep.pkg) {
                 continue;
             }
 
-            let is_dev = matches!(dep.kind, DependencyKind::Development);
-            dep_usage
-                .entry(dep.name.clone())
-                .or_default()
-                .push(CrateName::new_unchecked(package.name.to_string()));
-
-            let current_dev = dep_dev_status.get(&dep.name).copied().unwrap_or(false);
-            dep_dev_status.insert(dep.name.clone(), current_dev || is_dev);
+            let is_dev = dep
+                .dep_kinds
+                .iter()
+                .any(|info| matches!(info.kind, DependencyKind::Development));
+            let (used_by, dev_dep) = dep_usage.entry(&dep.pkg).or_default();
+            used_by.push(CrateName::new_unchecked(package.name.to_string()));
+            *dev_dep |= is_dev;
         }
     }
 
     // 3. Convert dependencies to CrateMetadata
-    for (dep_name, using_crates) in dep_usage {
-        let dev_dep = dep_dev_status.get(&dep_name).copied().unwrap_or(false);
-        let pkg_metadata = metadata.packages.iter().find(|p| p.name == dep_name);
-
-        crates.insert(
-            CrateName::new_unchecked(dep_name.clone()),
-            CrateMetadata {
-                origin: CrateOrigin::External,
-                name: CrateName::new_unchecked(dep_name),
-                version: pkg_metadata.map(|p| p.version.to_string()),
-                description: pkg_metadata.and_then(|p| p.description.clone()),
-                dev_dep,
-                is_root_crate: false,
-                used_by: using_crates,
-            },
-        );
+    for (id, (using_crates, dev_dep)) in dep_usage {
+        let package = packages[id];
+        let metadata = CrateMetadata {
+            origin: CrateOrigin::External,
+            name: CrateName::new_unchecked(package.name.to_string()),
+            version: Some(package.version.to_string()),
+            description: package.description.clone(),
+            dev_dep,
+            is_root_crate: false,
+            used_by: using_crates,
+            features: resolved_features.get(id).cloned(),
+            sources: None,
+        };
+        crates.insert(metadata.key(), metadata);
     }
 
     // 4. Add standard library crates
     if let Some(rustc_version) = get_rustc_version() {
         for stdlib_name in ["std", "core", "alloc", "proc_macro", "test"] {
-            crates.insert(
-                CrateName::new_unchecked(stdlib_name),
-                CrateMetadata {
-                    origin: CrateOrigin::Standard,
-                    name: CrateName::new_unchecked(stdlib_name),
-                    version: Some(rustc_version.clone()),
-                    description: None,
-                    dev_dep: false,
-                    is_root_crate: false,
-                    used_by: vec![],
-                },
-            );
+            let metadata = CrateMetadata {
+                origin: CrateOrigin::Standard,
+                name: CrateName::new_unchecked(stdlib_name),
+                version: Some(rustc_version.clone()),
+                description: None,
+                dev_dep: false,
+                is_root_crate: false,
+                used_by: vec![],
+                features: None,
+                sources: None,
+            };
+            crates.insert(metadata.key(), metadata);
         }
     }
 
     crates
 }
 
+/// Features activated for each package by cargo's resolver, sorted by name.
+///
+/// Empty when `cargo metadata` ran without dependency resolution.
+fn resolved_features(metadata: &Metadata) -> HashMap<PackageId, Vec<String>> {
+    metadata
+        .resolve
+        .iter()
+        .flat_map(|resolve| &resolve.nodes)
+        .map(|node| {
+            let mut features: Vec<String> = node.features.iter().map(ToString::to_string).collect();
+            features.sort();
+            (node.id.clone(), features)
+        })
+        .collect()
+}
+
+/// Source locations of a workspace member's targets.
+///
+/// Each target contributes the directory of its entry point, except build scripts,
+/// which are tracked as single files so the whole package directory isn't hashed.
+/// Directories nested inside another are dropped.
+fn member_sources(package: &Package) -> MemberSources {
+    let mut source_dirs: Vec<PathBuf> = Vec::new();
+    let mut extra_files = Vec::new();
+
+    for target in &package.targets {
+        let src_path = target.src_path.clone().into_std_path_buf();
+        if target.kind.contains(&TargetKind::CustomBuild) {
+            extra_files.push(src_path);
+        } else if let Some(dir) = src_path.parent() {
+            source_dirs.push(dir.to_path_buf());
+        }
+    }
+
+    source_dirs.sort();
+    source_dirs.dedup();
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for dir in source_dirs {
+        if !roots.iter().any(|root| dir.starts_with(root)) {
+            roots.push(dir);
+        }
+    }
+    extra_files.retain(|file| !roots.iter().any(|root| file.starts_with(root)));
+
+    MemberSources {
+        manifest_path: package.manifest_path.clone().into_std_path_buf(),
+        source_dirs: roots,
+        extra_files,
+    }
+}
+
 /// Detect the rustc version for standard library crates.
 fn get_rustc_version() -> Option<String> {
     let output = std::process::Command::new("rustc")
@@ -306,3 +421,76 @@ fn get_rustc_version() -> Option<String> {
         .nth(1)
         .map(std::string::ToString::to_string)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn records_resolved_features() {
+        let root = env!("CARGO_MANIFEST_DIR").to_string();
+        let_assert!(Ok((_, workspace, _)) = handle_set_workspace(root, None).await);
+
+        let_assert!(Some(tokio) = workspace.get_crate("tokio"));
+        let_assert!(Some(features) = &tokio.features);
+        check!(features.contains(&"macros".to_string()));
+        check!(features.contains(&"rt-multi-thread".to_string()));
+        check!(features.is_sorted());
+
+        let_assert!(Some(member) = workspace.get_crate("rustdoc-mcp"));
+        check!(member.features.is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn records_configured_target_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(dir.path()).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "").unwrap();
+        std::fs::create_dir(root.join(".cargo")).unwrap();
+        std::fs::write(
+            root.join(".cargo/config.toml"),
+            "[build]\ntarget-dir = \"out\"\n",
+        )
+        .unwrap();
+
+        let_assert!(
+            Ok((_, workspace, _)) = handle_set_workspace(root.display().to_string(), None).await
+        );
+        // CARGO_TARGET_DIR takes precedence over the config file
+        let expected =
+            std::env::var_os("CARGO_TARGET_DIR").map_or_else(|| root.join("out"), PathBuf::from);
+        check!(workspace.target_dir == expected);
+        check!(workspace.doc_dir() == expected.join("doc"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn records_member_sources() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let_assert!(
+            Ok((_, workspace, _)) = handle_set_workspace(root.display().to_string(), None).await
+        );
+
+        let_assert!(Some(member) = workspace.get_crate("rustdoc-mcp"));
+        let_assert!(Some(sources) = &member.sources);
+        check!(sources.manifest_path == root.join("Cargo.toml"));
+        // Binary and library entry points both live under src/, which is kept once
+        check!(sources.source_dirs.contains(&root.join("src")));
+        check!(
+            !sources
+                .source_dirs
+                .iter()
+                .any(|dir| dir.starts_with(root.join("src/bin")))
+        );
+        check!(sources.source_dirs.contains(&root.join("tests")));
+
+        let_assert!(Some(tokio) = workspace.get_crate("tokio"));
+        check!(tokio.sources.is_none());
+    }
+}
@@ -0,0 +1,70 @@
+//! Source view tool handler.
+//!
+//! Resolves a query exactly like [`crate::tools::inspect_item`] and prints the
+//! item's definition from disk, located through its rustdoc span. See
+//! [`crate::format::source`] for how span filenames map to workspace,
+//! `~/.cargo/registry/src`, and `rust-src` files.
+
+use crate::format::source::SourceLocator;
+use crate::search::ItemKind;
+use crate::tools::inspect_item::{
+    InspectItemRequest, ItemView, StructuredInspectResult, inspect_item_with_view,
+    render_inspect_result,
+};
+use crate::worker::DocState;
+
+use rmcp::schemars;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ViewSourceRequest {
+    /// Item whose source to show (e.g., "serde_json::from_str", "Vec", "HashMap")
+    pub query: String,
+    /// Optional filter by item kind (struct, enum, function, trait, module, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ItemKind>,
+    /// Workspace to query: its path or any directory inside it. Defaults to the
+    /// workspace configured with set_workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+/// Show the source of an item's definition with surrounding context.
+///
+/// This is the string-returning wrapper. For programmatic access or tests,
+/// call [`handle_view_source_structured`] directly.
+#[tracing::instrument(skip_all, fields(query = %request.query))]
+pub async fn handle_view_source(
+    state: &Arc<DocState>,
+    request: ViewSourceRequest,
+) -> Result<String, String> {
+    let structured = handle_view_source_structured(state, request).await?;
+    Ok(render_inspect_result(&structured))
+}
+
+/// Structured variant of [`handle_view_source`].
+///
+/// Returns the same [`StructuredInspectResult`] as `inspect_item`, with the
+/// source listing in `rendered`.
+#[tracing::instrument(skip_all, fields(query = %request.query))]
+pub async fn handle_view_source_structured(
+    state: &Arc<DocState>,
+    request: ViewSourceRequest,
+) -> Result<StructuredInspectResult, String> {
+    let workspace_root = state
+        .select_workspace(request.workspace.as_deref())
+        .await?
+        .map(|ctx| ctx.root);
+    let sysroot = state.stdlib().map(|stdlib| stdlib.sysroot().clone());
+    let view = ItemView::Source(SourceLocator::new(workspace_root, sysroot));
+
+    let request = InspectItemRequest {
+        query: request.query,
+        kind: request.kind,
+        detail_level: crate::format::DetailLevel::default(),
+        workspace: request.workspace,
+        features: None,
+    };
+    inspect_item_with_view(state, &request, &view).await
+}
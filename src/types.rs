@@ -4,6 +4,7 @@
 //! - Type kinds (struct, enum, union)
 //! - Field visibility
 //! - Crate names (with validation and normalization)
+//! - Crate keys (name plus resolved version)
 
 use serde::{Deserialize, Serialize};
 use std::borrow::{Borrow, Cow};
@@ -211,6 +212,14 @@ impl CrateName {
         target_doc.join(format!("{}.index", self.normalized))
     }
 
+    /// Get the path to the digest recording what this crate's docs were generated from.
+    #[inline]
+    pub fn digest_path(&self, target_doc: &Path) -> PathBuf {
+        target_doc
+            .join(".digests")
+            .join(format!("{}.digest.json", self.normalized))
+    }
+
     /// Check if this crate name matches another string (normalized comparison).
     ///
     /// Both names are normalized before comparison, so `serde-json` matches `serde_json`.
@@ -308,6 +317,38 @@ impl<'de> Deserialize<'de> for CrateName {
     }
 }
 
+/// Identifies a crate by name and resolved version.
+///
+/// A lockfile can contain several versions of the same crate (e.g. `syn 1.x` pulled in
+/// by one dependency and `syn 2.x` by another), so the name alone is not a unique key.
+/// Displays as `name@version`, the same form users write in queries.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CrateKey {
+    /// Crate name
+    pub name: CrateName,
+    /// Resolved version (if known)
+    pub version: Option<String>,
+}
+
+impl CrateKey {
+    /// Create a key from a crate name and optional version.
+    pub fn new(name: impl Into<String>, version: Option<&str>) -> Self {
+        Self {
+            name: CrateName::new_unchecked(name),
+            version: version.map(str::to_string),
+        }
+    }
+}
+
+impl fmt::Display for CrateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "{}@{version}", self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -386,6 +427,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_crate_key_display() {
+        check!(CrateKey::new("syn", Some("1.0.109")).to_string() == "syn@1.0.109");
+        check!(CrateKey::new("serde-json", None).to_string() == "serde-json");
+        check!(CrateKey::new("serde-json", None) == CrateKey::new("serde_json", None));
+    }
+
     #[test]
     fn test_crate_name_unchecked() {
         // Should work even with unusual names (trusted source)
@@ -0,0 +1,419 @@
+//! Filesystem watcher for a workspace's manifests and member sources.
+//!
+//! The background worker used to poll for changes; instead it now waits on
+//! debounced events from this watcher. A batch of events is classified into a
+//! [`WorkspaceChanges`]: manifest or lockfile edits mean the workspace metadata
+//! must be reloaded, while source edits only affect the members owning them.
+
+use crate::types::CrateName;
+use crate::workspace::{CrateOrigin, WorkspaceContext};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{DebounceEventResult, Debouncer, new_debouncer};
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// How long the filesystem must be quiet before a batch of changes is reported.
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// What a batch of filesystem changes means for the workspace.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WorkspaceChanges {
+    /// A `Cargo.toml` or `Cargo.lock` changed, so members, dependencies or
+    /// features may differ and the workspace metadata must be reloaded.
+    pub manifest: bool,
+    /// Workspace members whose sources changed.
+    pub members: Vec<CrateName>,
+}
+
+impl WorkspaceChanges {
+    /// Whether the batch touched anything relevant.
+    pub const fn is_empty(&self) -> bool {
+        !self.manifest && self.members.is_empty()
+    }
+}
+
+/// The gitignore rules that apply to a workspace's files.
+///
+/// Made of the `.gitignore` files in the workspace root, its parents up to the
+/// repository root and each member's package directory, and the repository's
+/// `.git/info/exclude`. As in git, rules in deeper directories take precedence.
+#[derive(Debug, Default)]
+pub struct IgnoreRules {
+    /// Matchers ordered from the deepest directory up, with `.git/info/exclude` last
+    matchers: Vec<Gitignore>,
+}
+
+impl IgnoreRules {
+    /// Load the rules for `workspace`, skipping unreadable files.
+    pub fn new(workspace: &WorkspaceContext) -> Self {
+        let repo_root = workspace
+            .root
+            .ancestors()
+            .find(|dir| dir.join(".git").exists());
+        let mut dirs: Vec<&Path> = match repo_root {
+            Some(repo_root) => workspace
+                .root
+                .ancestors()
+                .take_while(|dir| dir.starts_with(repo_root))
+                .collect(),
+            None => vec![&workspace.root],
+        };
+        dirs.extend(
+            workspace
+                .crate_info
+                .values()
+                .filter(|meta| meta.origin == CrateOrigin::Local)
+                .filter_map(|meta| meta.sources.as_ref()?.manifest_path.parent()),
+        );
+        dirs.sort();
+        dirs.dedup();
+        dirs.sort_by_key(|dir| Reverse(dir.components().count()));
+
+        let mut matchers: Vec<Gitignore> = dirs
+            .into_iter()
+            .filter_map(|dir| load_gitignore(dir, &dir.join(".gitignore")))
+            .collect();
+        matchers.extend(
+            repo_root.and_then(|root| load_gitignore(root, &root.join(".git/info/exclude"))),
+        );
+        Self { matchers }
+    }
+
+    /// Whether `path`, or a directory it is in, is ignored.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let is_dir = path.is_dir();
+        self.matchers
+            .iter()
+            .filter(|matcher| path.starts_with(matcher.path()))
+            .map(|matcher| matcher.matched_path_or_any_parents(path, is_dir))
+            .find(|matched| !matched.is_none())
+            .is_some_and(|matched| matched.is_ignore())
+    }
+}
+
+/// A matcher for the gitignore-style `file`, with patterns relative to `dir`.
+fn load_gitignore(dir: &Path, file: &Path) -> Option<Gitignore> {
+    if !file.is_file() {
+        return None;
+    }
+    let mut builder = GitignoreBuilder::new(dir);
+    if let Some(e) = builder.add(file) {
+        tracing::debug!(path = %file.display(), error = %e, "Ignoring unreadable gitignore entries");
+    }
+    builder
+        .build()
+        .inspect_err(|e| tracing::debug!(path = %file.display(), error = %e, "Skipping gitignore"))
+        .ok()
+}
+
+/// Watches the manifests, lockfile and member source trees of one workspace.
+pub struct WorkspaceWatcher {
+    root: PathBuf,
+    ignore_rules: IgnoreRules,
+    events: mpsc::UnboundedReceiver<Vec<PathBuf>>,
+    // Dropping the debouncer stops the watch
+    _debouncer: Debouncer<RecommendedWatcher>,
+}
+
+impl std::fmt::Debug for WorkspaceWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkspaceWatcher")
+            .field("root", &self.root)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WorkspaceWatcher {
+    /// Start watching `workspace`.
+    ///
+    /// Directories that don't exist (e.g. a member without `tests/`) are skipped.
+    pub fn new(workspace: &WorkspaceContext) -> notify_debouncer_mini::notify::Result<Self> {
+        let (tx, events) = mpsc::unbounded_channel();
+        let mut debouncer =
+            new_debouncer(
+                DEBOUNCE_TIMEOUT,
+                move |result: DebounceEventResult| match result {
+                    Ok(events) => {
+                        let _ = tx.send(events.into_iter().map(|event| event.path).collect());
+                    }
+                    Err(e) => tracing::warn!(error = %e, "Filesystem watcher error"),
+                },
+            )?;
+
+        for (path, mode) in watch_targets(workspace) {
+            if !path.exists() {
+                continue;
+            }
+            debouncer.watcher().watch(&path, mode)?;
+            tracing::debug!(path = %path.display(), ?mode, "Watching");
+        }
+
+        Ok(Self {
+            root: workspace.root.clone(),
+            ignore_rules: IgnoreRules::new(workspace),
+            events,
+            _debouncer: debouncer,
+        })
+    }
+
+    /// Root of the watched workspace.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Wait for the next batch of changes relevant to `workspace`.
+    ///
+    /// Batches touching only ignored or unrelated files are skipped. Returns `None`
+    /// once the watcher has stopped.
+    pub async fn next_changes(&mut self, workspace: &WorkspaceContext) -> Option<WorkspaceChanges> {
+        loop {
+            let paths = self.events.recv().await?;
+            let changes = classify(workspace, &self.ignore_rules, &paths);
+            if !changes.is_empty() {
+                return Some(changes);
+            }
+        }
+    }
+}
+
+/// Directories to watch: the workspace root (for `Cargo.toml` and `Cargo.lock`),
+/// each member's package directory (manifest and build script) and its source trees.
+///
+/// Files are watched through their directory, since editors often save by replacing
+/// the file, which would end a watch on the file itself.
+fn watch_targets(workspace: &WorkspaceContext) -> Vec<(PathBuf, RecursiveMode)> {
+    let mut targets = vec![(workspace.root.clone(), RecursiveMode::NonRecursive)];
+
+    for meta in workspace.crate_info.values() {
+        if meta.origin != CrateOrigin::Local {
+            continue;
+        }
+        let Some(sources) = &meta.sources else {
+            targets.push((workspace.root.join("src"), RecursiveMode::Recursive));
+            continue;
+        };
+        if let Some(package_dir) = sources.manifest_path.parent() {
+            targets.push((package_dir.to_path_buf(), RecursiveMode::NonRecursive));
+        }
+        for dir in &sources.source_dirs {
+            targets.push((dir.clone(), RecursiveMode::Recursive));
+        }
+        for file in &sources.extra_files {
+            if let Some(dir) = file.parent() {
+                targets.push((dir.to_path_buf(), RecursiveMode::NonRecursive));
+            }
+        }
+    }
+
+    // Watch each directory once, recursively if any target needs it
+    targets.sort_by(|a, b| a.0.cmp(&b.0));
+    targets.dedup_by(|later, earlier| {
+        later.0 == earlier.0 && {
+            if later.1 == RecursiveMode::Recursive {
+                earlier.1 = RecursiveMode::Recursive;
+            }
+            true
+        }
+    });
+    // A recursive watch covers anything below it
+    let recursive: Vec<PathBuf> = targets
+        .iter()
+        .filter(|(_, mode)| *mode == RecursiveMode::Recursive)
+        .map(|(path, _)| path.clone())
+        .collect();
+    targets.retain(|(path, _)| {
+        !recursive
+            .iter()
+            .any(|dir| path != dir && path.starts_with(dir))
+    });
+    targets
+}
+
+/// Classify changed paths into manifest changes and affected members.
+///
+/// Paths under `target/` or ignored by the workspace's [`IgnoreRules`] are skipped,
+/// as are files other than manifests, the lockfile and Rust sources.
+pub fn classify(
+    workspace: &WorkspaceContext,
+    ignore_rules: &IgnoreRules,
+    paths: &[PathBuf],
+) -> WorkspaceChanges {
+    let root = &workspace.root;
+    let target_dir = &workspace.target_dir;
+    let mut changes = WorkspaceChanges::default();
+
+    for path in paths {
+        if path.starts_with(target_dir) || ignore_rules.is_ignored(path) {
+            continue;
+        }
+
+        let file_name = path.file_name().and_then(|name| name.to_str());
+        if matches!(file_name, Some("Cargo.toml" | "Cargo.lock")) {
+            let is_member_manifest = workspace.crate_info.values().any(|meta| {
+                meta.sources
+                    .as_ref()
+                    .is_some_and(|sources| sources.manifest_path == *path)
+            });
+            if path.parent() == Some(root.as_path()) || is_member_manifest {
+                changes.manifest = true;
+            }
+            continue;
+        }
+
+        if path.extension().is_none_or(|ext| ext != "rs") {
+            continue;
+        }
+
+        let members = workspace
+            .crate_info
+            .values()
+            .filter(|meta| meta.origin == CrateOrigin::Local);
+        for member in members {
+            let owns_path = member.sources.as_ref().map_or_else(
+                || path.starts_with(root.join("src")),
+                |sources| {
+                    sources.source_dirs.iter().any(|dir| path.starts_with(dir))
+                        || sources.extra_files.contains(path)
+                },
+            );
+            if owns_path && !changes.members.contains(&member.name) {
+                changes.members.push(member.name.clone());
+            }
+        }
+    }
+
+    changes.members.sort();
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CrateKey;
+    use crate::workspace::{CrateMetadata, MemberSources};
+    use assert2::check;
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    fn member(root: &Path, name: &str) -> CrateMetadata {
+        let dir = root.join(name);
+        CrateMetadata {
+            origin: CrateOrigin::Local,
+            version: Some("0.1.0".to_string()),
+            description: None,
+            dev_dep: false,
+            name: CrateName::new_unchecked(name),
+            is_root_crate: false,
+            used_by: vec![],
+            features: None,
+            sources: Some(MemberSources {
+                manifest_path: dir.join("Cargo.toml"),
+                source_dirs: vec![dir.join("src"), dir.join("tests")],
+                extra_files: vec![dir.join("build.rs")],
+            }),
+        }
+    }
+
+    fn workspace(root: &Path) -> WorkspaceContext {
+        let crate_info: HashMap<CrateKey, CrateMetadata> = ["app", "core"]
+            .into_iter()
+            .map(|name| {
+                let meta = member(root, name);
+                (meta.key(), meta)
+            })
+            .collect();
+        WorkspaceContext {
+            root: root.to_path_buf(),
+            members: vec![
+                CrateName::new_unchecked("app"),
+                CrateName::new_unchecked("core"),
+            ],
+            crate_info,
+            root_crate: None,
+            target_dir: root.join("target"),
+        }
+    }
+
+    #[rstest]
+    #[case::root_manifest("Cargo.toml", true, &[])]
+    #[case::lockfile("Cargo.lock", true, &[])]
+    #[case::member_manifest("core/Cargo.toml", true, &[])]
+    #[case::member_source("core/src/lib.rs", false, &["core"])]
+    #[case::member_test("app/tests/it.rs", false, &["app"])]
+    #[case::build_script("app/build.rs", false, &["app"])]
+    #[case::generated_docs("target/doc/app.json", false, &[])]
+    #[case::target_source("target/debug/build/out.rs", false, &[])]
+    #[case::gitignored("core/src/generated/bindings.rs", false, &[])]
+    #[case::member_gitignored("core/src/scratch.rs", false, &[])]
+    #[case::git_excluded("app/src/local.rs", false, &[])]
+    #[case::member_whitelisted("core/src/generated/keep.rs", false, &["core"])]
+    #[case::non_rust("core/src/notes.md", false, &[])]
+    #[case::fixture_manifest("core/tests/fixture/Cargo.toml", false, &[])]
+    fn classifies_changes(#[case] path: &str, #[case] manifest: bool, #[case] members: &[&str]) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".git/info")).unwrap();
+        std::fs::create_dir_all(dir.path().join("core")).unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "/target\ngenerated/\n").unwrap();
+        std::fs::write(
+            dir.path().join("core/.gitignore"),
+            "/src/scratch.rs\n!/src/generated/keep.rs\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join(".git/info/exclude"), "/app/src/local.rs\n").unwrap();
+        let ws = workspace(dir.path());
+
+        let changes = classify(&ws, &IgnoreRules::new(&ws), &[dir.path().join(path)]);
+        check!(changes.manifest == manifest);
+        let member_names: Vec<_> = changes.members.iter().map(CrateName::as_str).collect();
+        check!(member_names == members);
+    }
+
+    #[test]
+    fn batches_deduplicate_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let ws = workspace(dir.path());
+
+        let paths = [
+            dir.path().join("core/src/lib.rs"),
+            dir.path().join("core/src/parse.rs"),
+            dir.path().join("app/src/main.rs"),
+        ];
+        let changes = classify(&ws, &IgnoreRules::default(), &paths);
+        let member_names: Vec<_> = changes.members.iter().map(CrateName::as_str).collect();
+        check!(member_names == ["app", "core"]);
+        check!(!changes.manifest);
+    }
+
+    #[test]
+    fn recursive_watches_cover_nested_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ws = workspace(dir.path());
+        // A build script inside the source tree needs no watch of its own
+        for meta in ws.crate_info.values_mut() {
+            if let Some(sources) = &mut meta.sources {
+                sources.extra_files = vec![sources.source_dirs[0].join("gen/build.rs")];
+            }
+        }
+        let targets = watch_targets(&ws);
+
+        check!(targets.contains(&(dir.path().join("core/src"), RecursiveMode::Recursive)));
+        check!(!targets.iter().any(|(path, _)| path.ends_with("src/gen")));
+    }
+
+    #[test]
+    fn watches_each_directory_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let ws = workspace(dir.path());
+        let targets = watch_targets(&ws);
+
+        let mut paths: Vec<_> = targets.iter().map(|(path, _)| path.clone()).collect();
+        paths.dedup();
+        check!(paths.len() == targets.len());
+        check!(targets.contains(&(dir.path().to_path_buf(), RecursiveMode::NonRecursive)));
+        check!(targets.contains(&(dir.path().join("core/src"), RecursiveMode::Recursive)));
+        check!(targets.contains(&(dir.path().join("app"), RecursiveMode::NonRecursive)));
+    }
+}
@@ -1,39 +1,59 @@
-//! Background worker for continuous workspace detection and documentation generation.
+//! Background worker for workspace detection and documentation generation.
 //!
-//! The worker runs in a loop, detecting workspace changes and pre-generating
-//! documentation for crates. Tool handlers can await in-flight generation
-//! via shared futures. Supports graceful shutdown via `CancellationToken`.
-
-use crate::search::CrateIndex;
+//! The worker detects the workspace on startup and pre-generates documentation
+//! for its crates, then sleeps until the [filesystem watcher](crate::watcher)
+//! reports changes or another workspace is configured. Tool handlers can await
+//! in-flight generation via shared futures. Supports graceful shutdown via
+//! `CancellationToken`.
+
+use crate::memory::{CachedDocs, MemoryCache, MemoryKey, MemoryUsage};
+use crate::progress::{self, Progress};
+use crate::search::{CrateIndex, QueryContext, TermIndex, index_metrics};
 use crate::stdlib::StdlibDocs;
 use crate::tools::set_workspace::handle_set_workspace;
 use crate::types::CrateName;
-use crate::workspace::{WorkspaceContext, auto_detect_workspace};
+use crate::watcher::{WorkspaceChanges, WorkspaceWatcher};
+use crate::workspace::{
+    CrateOrigin, DocTarget, FEATURES_SEPARATOR, WorkspaceContext, auto_detect_workspace,
+    detect_workspace_from, feature_doc_name, features_override,
+};
 use anyhow::Result;
 use futures::FutureExt;
 use futures::future::{BoxFuture, Shared};
-use lru::LruCache;
 use std::collections::HashMap;
-use std::num::NonZeroUsize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock};
-use tokio::time::{Duration, Instant, interval_at};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{Mutex, RwLock, broadcast, watch};
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::TaskTracker;
 
-/// Maximum number of parsed CrateIndex entries to keep in memory.
-const LRU_CACHE_SIZE: usize = 50;
-
-/// Interval between workspace detection cycles.
-const DETECTION_INTERVAL: Duration = Duration::from_secs(5);
-
 /// Timeout for graceful shutdown before forcefully terminating.
 const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// Capacity of the [`DocEvent`] channel; slow subscribers see `Lagged` past this.
+const DOC_EVENT_CAPACITY: usize = 64;
+
 /// Type alias for shared doc generation futures.
 type SharedDocFuture = Shared<BoxFuture<'static, Result<Arc<CrateIndex>, String>>>;
 
+/// A generation in progress: its shared result and the progress it publishes,
+/// which every caller awaiting it relays to its own reporter.
+#[derive(Clone)]
+struct InFlight {
+    future: SharedDocFuture,
+    progress: Arc<watch::Sender<Option<Progress>>>,
+}
+
+impl InFlight {
+    /// Await the generation, relaying its progress.
+    async fn wait(self) -> Result<Arc<CrateIndex>, String> {
+        progress::follow(&self.progress, self.future).await
+    }
+}
+
 /// Cancellation-aware helpers for background tasks.
 ///
 /// Wraps a `CancellationToken` and `TaskTracker` to provide structured
@@ -94,38 +114,123 @@ impl ServiceContext {
     }
 }
 
+/// Changes to the available documentation, broadcast to interested listeners
+/// (e.g. the MCP server, which forwards them as `resources/list_changed`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocEvent {
+    /// A workspace was configured or replaced.
+    WorkspaceChanged,
+    /// Documentation for a crate finished generating and is now cached.
+    DocsReady(CrateName),
+}
+
+/// A configured workspace.
+struct WorkspaceSlot {
+    context: WorkspaceContext,
+    working_directory: PathBuf,
+    /// Path to Cargo.lock (for dependency fingerprinting)
+    cargo_lock_path: Option<PathBuf>,
+}
+
+impl WorkspaceSlot {
+    const fn new(
+        working_directory: PathBuf,
+        context: WorkspaceContext,
+        cargo_lock_path: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            context,
+            working_directory,
+            cargo_lock_path,
+        }
+    }
+
+    /// Replace the workspace context, keeping cached docs that are still valid.
+    ///
+    /// Members are always dropped, since their docs depend on the manifests and
+    /// sources, as is any dependency that was added or re-resolved with different
+    /// features. Dropped entries are reloaded (and, if stale, regenerated) on the
+    /// next request.
+    fn update(
+        &mut self,
+        working_directory: PathBuf,
+        context: WorkspaceContext,
+        cargo_lock_path: Option<PathBuf>,
+        memory: &MemoryCache,
+    ) {
+        for meta in context.crate_info.values() {
+            let unchanged = meta.origin != CrateOrigin::Local
+                && self
+                    .context
+                    .crate_info
+                    .get(&meta.key())
+                    .is_some_and(|previous| previous.features == meta.features);
+            if !unchanged {
+                memory.remove(&MemoryKey::Workspace(
+                    context.root.clone(),
+                    CrateName::new_unchecked(context.qualified_name(meta)),
+                ));
+            }
+        }
+        self.context = context;
+        self.working_directory = working_directory;
+        self.cargo_lock_path = cargo_lock_path;
+    }
+
+    /// Key docs are cached under: the crate's qualified name (`name@version` when the
+    /// workspace has several versions of it), normalized so hyphenated lookups
+    /// (e.g. "rust-stemmers") match entries stored as "rust_stemmers".
+    fn doc_key(&self, crate_name: &str) -> CrateName {
+        CrateName::new_unchecked(self.context.canonical_crate_name(crate_name))
+    }
+}
+
+/// The workspace tools use unless they name another.
+#[derive(Debug)]
+struct ActiveWorkspace {
+    root: PathBuf,
+    /// Chosen with `set_workspace`, so client roots don't replace it
+    chosen: bool,
+}
+
 /// Shared state for documentation caching and generation.
 ///
 /// This is the central coordination point for:
-/// - Caching parsed CrateIndex entries (LRU)
+/// - Tracking every configured workspace
+/// - Holding parsed CrateIndex entries of all workspaces in one byte-budgeted
+///   [`MemoryCache`], shared with the stdlib docs
 /// - Tracking in-flight generation tasks (shared futures)
-/// - Storing workspace context
+/// - Remembering the active workspace, which tools use unless they name another
+///
+/// Crate-level methods such as [`get_docs`](Self::get_docs) take the root of the
+/// configured workspace they act on.
 pub struct DocState {
-    /// LRU cache of parsed crate indices
-    cache: RwLock<LruCache<CrateName, Arc<CrateIndex>>>,
+    /// Configured workspaces by root
+    workspaces: RwLock<HashMap<PathBuf, WorkspaceSlot>>,
 
-    /// In-flight generation futures (can be awaited by multiple callers)
-    in_flight: Mutex<HashMap<CrateName, SharedDocFuture>>,
+    /// Parsed crate indices of every workspace and the stdlib
+    memory: Arc<MemoryCache>,
 
-    /// Current workspace context (if detected/configured)
-    workspace: RwLock<Option<WorkspaceContext>>,
+    /// The active workspace (if detected/configured)
+    active: RwLock<Option<ActiveWorkspace>>,
 
-    /// Current working directory
-    working_directory: RwLock<Option<PathBuf>>,
-
-    /// Path to Cargo.lock (for dependency fingerprinting)
-    cargo_lock_path: RwLock<Option<PathBuf>>,
+    /// In-flight generation futures by workspace root and crate (can be awaited
+    /// by multiple callers)
+    in_flight: Mutex<HashMap<(PathBuf, CrateName), InFlight>>,
 
     /// Standard library documentation (if available)
     stdlib: Option<Arc<StdlibDocs>>,
+
+    /// Broadcasts documentation changes to subscribers
+    events: broadcast::Sender<DocEvent>,
 }
 
 impl std::fmt::Debug for DocState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DocState")
-            .field("cache_size", &self.cache.blocking_read().len())
+            .field("workspace_count", &self.workspaces.blocking_read().len())
             .field("in_flight_count", &self.in_flight.blocking_lock().len())
-            .field("has_workspace", &self.workspace.blocking_read().is_some())
+            .field("active_workspace", &*self.active.blocking_read())
             .field("has_stdlib", &self.stdlib.is_some())
             .finish_non_exhaustive()
     }
@@ -133,30 +238,85 @@ impl std::fmt::Debug for DocState {
 
 impl DocState {
     /// Create a new DocState with optional stdlib support.
+    ///
+    /// Workspace docs are cached in the stdlib's memory cache, so that both count
+    /// against one budget.
     pub fn new(stdlib: Option<Arc<StdlibDocs>>) -> Self {
+        let memory = stdlib.as_ref().map_or_else(
+            || Arc::new(MemoryCache::from_env()),
+            |stdlib| stdlib.memory().clone(),
+        );
         Self {
-            cache: RwLock::new(LruCache::new(NonZeroUsize::new(LRU_CACHE_SIZE).unwrap())),
+            workspaces: RwLock::new(HashMap::new()),
+            memory,
+            active: RwLock::new(None),
             in_flight: Mutex::new(HashMap::new()),
-            workspace: RwLock::new(None),
-            working_directory: RwLock::new(None),
-            cargo_lock_path: RwLock::new(None),
             stdlib,
+            events: broadcast::channel(DOC_EVENT_CAPACITY).0,
         }
     }
 
-    /// Get the current workspace context.
+    /// Root of the active workspace.
+    async fn active_root(&self) -> Option<PathBuf> {
+        self.active
+            .read()
+            .await
+            .as_ref()
+            .map(|active| active.root.clone())
+    }
+
+    /// Make the configured workspace at `root` the active one.
+    ///
+    /// Reconfiguring the active workspace keeps it chosen if it was.
+    async fn activate(&self, root: PathBuf, chosen: bool) {
+        let mut active = self.active.write().await;
+        let chosen = chosen
+            || active
+                .as_ref()
+                .is_some_and(|previous| previous.root == root && previous.chosen);
+        *active = Some(ActiveWorkspace { root, chosen });
+    }
+
+    /// Read from the active workspace's slot.
+    async fn with_active<T>(&self, read: impl FnOnce(&WorkspaceSlot) -> T) -> Option<T> {
+        let root = self.active_root().await?;
+        self.workspaces.read().await.get(&root).map(read)
+    }
+
+    /// Get the active workspace context.
     pub async fn workspace(&self) -> Option<WorkspaceContext> {
-        self.workspace.read().await.clone()
+        self.with_active(|slot| slot.context.clone()).await
     }
 
-    /// Get the current working directory.
+    /// Get the context of the configured workspace rooted at `root`.
+    pub async fn workspace_at(&self, root: &Path) -> Option<WorkspaceContext> {
+        self.workspaces
+            .read()
+            .await
+            .get(root)
+            .map(|slot| slot.context.clone())
+    }
+
+    /// Roots of all configured workspaces, sorted.
+    pub async fn workspace_roots(&self) -> Vec<PathBuf> {
+        let mut roots: Vec<_> = self.workspaces.read().await.keys().cloned().collect();
+        roots.sort();
+        roots
+    }
+
+    /// Get the active workspace's working directory.
     pub async fn working_directory(&self) -> Option<PathBuf> {
-        self.working_directory.read().await.clone()
+        self.with_active(|slot| slot.working_directory.clone())
+            .await
     }
 
-    /// Get the Cargo.lock path.
-    pub async fn cargo_lock_path(&self) -> Option<PathBuf> {
-        self.cargo_lock_path.read().await.clone()
+    /// Get the Cargo.lock path of the workspace rooted at `root`.
+    pub async fn cargo_lock_path(&self, root: &Path) -> Option<PathBuf> {
+        self.workspaces
+            .read()
+            .await
+            .get(root)
+            .and_then(|slot| slot.cargo_lock_path.clone())
     }
 
     /// Get the stdlib documentation provider.
@@ -164,124 +324,365 @@ impl DocState {
         self.stdlib.as_ref()
     }
 
+    /// Subscribe to documentation changes.
+    pub fn subscribe(&self) -> broadcast::Receiver<DocEvent> {
+        self.events.subscribe()
+    }
+
+    /// Notify subscribers; having none is fine.
+    fn emit(&self, event: DocEvent) {
+        let _ = self.events.send(event);
+    }
+
     /// Check if a workspace has been configured.
     pub async fn has_workspace(&self) -> bool {
-        self.workspace.read().await.is_some()
+        self.active.read().await.is_some()
     }
 
-    /// Update the workspace context.
+    /// Configure a workspace the user chose and make it the active one.
+    ///
+    /// Workspaces configured before keep their cached docs, so switching back and
+    /// forth stays warm. Reconfiguring a known workspace keeps the entries its new
+    /// metadata doesn't invalidate. Client roots don't replace a chosen workspace
+    /// (see [`Self::set_roots`]).
     pub async fn set_workspace(
         &self,
         working_dir: PathBuf,
         workspace: WorkspaceContext,
         cargo_lock: Option<PathBuf>,
     ) {
-        *self.working_directory.write().await = Some(working_dir);
-        *self.workspace.write().await = Some(workspace);
-        *self.cargo_lock_path.write().await = cargo_lock;
+        self.configure(working_dir, workspace, cargo_lock, true)
+            .await;
+    }
+
+    /// Configure a workspace detected from the working directory, or reload the
+    /// active one, and make it the active one.
+    ///
+    /// Like [`Self::set_workspace`], except that a workspace only detected can be
+    /// replaced by client roots.
+    pub(crate) async fn set_detected_workspace(
+        &self,
+        working_dir: PathBuf,
+        workspace: WorkspaceContext,
+        cargo_lock: Option<PathBuf>,
+    ) {
+        self.configure(working_dir, workspace, cargo_lock, false)
+            .await;
     }
 
-    /// Clear cached docs (e.g., when workspace changes).
+    async fn configure(
+        &self,
+        working_dir: PathBuf,
+        workspace: WorkspaceContext,
+        cargo_lock: Option<PathBuf>,
+        chosen: bool,
+    ) {
+        let root = workspace.root.clone();
+        self.insert_workspace(working_dir, workspace, cargo_lock)
+            .await;
+        self.activate(root, chosen).await;
+        self.emit(DocEvent::WorkspaceChanged);
+    }
+
+    async fn insert_workspace(
+        &self,
+        working_dir: PathBuf,
+        workspace: WorkspaceContext,
+        cargo_lock: Option<PathBuf>,
+    ) {
+        let mut workspaces = self.workspaces.write().await;
+        match workspaces.get_mut(&workspace.root) {
+            Some(slot) => slot.update(working_dir, workspace, cargo_lock, &self.memory),
+            None => {
+                workspaces.insert(
+                    workspace.root.clone(),
+                    WorkspaceSlot::new(working_dir, workspace, cargo_lock),
+                );
+            }
+        }
+    }
+
+    /// Resolve the workspace a tool call targets.
+    ///
+    /// Without a path this is the active workspace. A path may name any directory
+    /// inside a workspace; one that isn't configured yet is loaded, without
+    /// becoming the active workspace.
+    pub async fn select_workspace(
+        &self,
+        path: Option<&str>,
+    ) -> Result<Option<WorkspaceContext>, String> {
+        let Some(path) = path else {
+            return Ok(self.workspace().await);
+        };
+
+        let expanded = crate::workspace::expand_tilde(path);
+        if let Ok(canonical) = tokio::fs::canonicalize(expanded.as_ref()).await {
+            let containing = self
+                .workspaces
+                .read()
+                .await
+                .values()
+                .filter(|slot| canonical.starts_with(&slot.context.root))
+                .max_by_key(|slot| slot.context.root.components().count())
+                .map(|slot| slot.context.clone());
+            if containing.is_some() {
+                return Ok(containing);
+            }
+        }
+
+        self.load_workspace(path).await.map(Some)
+    }
+
+    /// Load the workspace at `path` without making it the active one.
+    async fn load_workspace(&self, path: &str) -> Result<WorkspaceContext, String> {
+        let (working_dir, workspace, _changed) = handle_set_workspace(path.to_string(), None)
+            .await
+            .map_err(|e| e.user_message())?;
+        let cargo_lock = workspace.root.join("Cargo.lock");
+        let cargo_lock = cargo_lock.exists().then_some(cargo_lock);
+        tracing::info!(workspace = %workspace.root.display(), "Loaded additional workspace");
+        self.insert_workspace(working_dir, workspace.clone(), cargo_lock)
+            .await;
+        Ok(workspace)
+    }
+
+    /// Load the workspaces containing the MCP client's roots.
+    ///
+    /// Each root is resolved to its workspace the way the working directory is at
+    /// startup. Unless the active workspace is among them or was chosen with
+    /// [`Self::set_workspace`], the first one becomes active. Returns the workspace
+    /// roots found.
+    pub async fn set_roots(&self, roots: &[PathBuf]) -> Vec<PathBuf> {
+        let mut found: Vec<PathBuf> = Vec::new();
+        for root in roots {
+            match detect_workspace_from(root).await {
+                Some(workspace_root) if !found.contains(&workspace_root) => {
+                    found.push(workspace_root);
+                }
+                Some(_) => {}
+                None => tracing::debug!(root = %root.display(), "No Rust workspace in client root"),
+            }
+        }
+
+        let mut loaded = Vec::new();
+        for workspace_root in found {
+            if self.workspace_at(&workspace_root).await.is_none()
+                && let Err(e) = self
+                    .load_workspace(&workspace_root.display().to_string())
+                    .await
+            {
+                tracing::warn!(workspace = %workspace_root.display(), error = %e, "Failed to load client root");
+                continue;
+            }
+            loaded.push(workspace_root);
+        }
+
+        let keep_active = self
+            .active
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(|active| active.chosen || loaded.contains(&active.root));
+        if !keep_active && let Some(first) = loaded.first() {
+            self.activate(first.clone(), false).await;
+            self.emit(DocEvent::WorkspaceChanged);
+        }
+
+        loaded
+    }
+
+    /// Clear cached docs of every workspace.
     pub async fn clear_cache(&self) {
         tracing::debug!("Clearing documentation cache");
-        self.cache.write().await.clear();
+        self.memory
+            .retain(|key| matches!(key, MemoryKey::Stdlib(_)));
         self.in_flight.lock().await.clear();
     }
 
+    /// A query context for `workspace` that keeps its search indices in this
+    /// state's memory cache and generates missing docs through this state.
+    pub fn query_context(self: &Arc<Self>, workspace: WorkspaceContext) -> QueryContext {
+        QueryContext::new(Arc::new(workspace))
+            .with_memory(self.memory.clone())
+            .with_doc_state(self.clone())
+    }
+
+    /// Memory held by parsed docs, including the stdlib's.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.memory.usage()
+    }
+
     /// Get docs for a crate, waiting for in-flight generation if needed.
     ///
     /// This is the main entry point for tool handlers. It:
-    /// 1. Checks the LRU cache
+    /// 1. Checks the memory cache
     /// 2. Checks for in-flight generation (awaits if found)
     /// 3. Starts new generation if needed
-    pub async fn get_docs(&self, crate_name: &str) -> Result<Arc<CrateIndex>, String> {
-        // Normalize the name so hyphenated lookups (e.g. "rust-stemmers") correctly
-        // match entries stored under the normalized key ("rust_stemmers").
-        let key = CrateName::new_unchecked(crate_name);
+    pub async fn get_docs(
+        self: &Arc<Self>,
+        root: &Path,
+        crate_name: &str,
+    ) -> Result<Arc<CrateIndex>, String> {
+        self.get_docs_inner(root, crate_name, None).await
+    }
 
-        // 1. Check cache first
-        {
-            let mut cache = self.cache.write().await;
-            if let Some(index) = cache.get(&key) {
+    /// Get docs for a crate built with exactly `features` enabled.
+    ///
+    /// The workspace normally builds each crate with the features cargo's resolver
+    /// activates; this overrides that set, e.g. to see feature-gated APIs the
+    /// workspace doesn't enable. Docs built with an override are stored, cached and
+    /// generated under a [feature-qualified name](feature_doc_name), apart from the
+    /// crate's resolved docs.
+    pub async fn get_docs_with_features(
+        self: &Arc<Self>,
+        root: &Path,
+        crate_name: &str,
+        features: &[String],
+    ) -> Result<Arc<CrateIndex>, String> {
+        let workspace = self.workspace_at(root).await;
+        let meta = workspace
+            .as_ref()
+            .and_then(|workspace| workspace.get_crate(crate_name))
+            .ok_or_else(|| format!("Crate '{}' not found in workspace", crate_name))?;
+        let features = features_override(meta, features);
+
+        self.get_docs_inner(root, crate_name, features).await
+    }
+
+    async fn get_docs_inner(
+        self: &Arc<Self>,
+        root: &Path,
+        crate_name: &str,
+        features_override: Option<Vec<String>>,
+    ) -> Result<Arc<CrateIndex>, String> {
+        // Docs built with a features override are kept apart from the resolved ones
+        let key =
+            self.doc_key(root, crate_name)
+                .await
+                .map(|(root, name)| match &features_override {
+                    Some(features) => (root, feature_doc_name(&name, features)),
+                    None => (root, name),
+                });
+
+        if let Some(key) = &key {
+            // 1. Check cache first
+            if let Some(cached) = self.memory.get(&memory_key(key)) {
                 tracing::debug!(crate_name, "Cache hit");
-                return Ok(index.clone());
+                index_metrics::record_memory_hit();
+                return Ok(cached.index);
             }
-        }
+            index_metrics::record_memory_miss();
 
-        // 2. Check for in-flight generation
-        let maybe_future = {
-            let in_flight = self.in_flight.lock().await;
-            in_flight.get(&key).cloned()
-        };
+            // 2. Check for in-flight generation
+            let maybe_future = {
+                let in_flight = self.in_flight.lock().await;
+                in_flight.get(key).cloned()
+            };
 
-        if let Some(future) = maybe_future {
-            tracing::debug!(crate_name, "Awaiting in-flight generation");
-            return future.await;
+            if let Some(in_flight) = maybe_future {
+                tracing::debug!(crate_name, "Awaiting in-flight generation");
+                return in_flight.wait().await;
+            }
         }
 
         // 3. Start new generation
-        self.generate_docs(crate_name).await
+        self.generate_docs(root, crate_name, features_override)
+            .await
     }
 
-    /// Start documentation generation for a crate.
+    /// Start documentation generation for a crate of the workspace at `root`.
     ///
     /// Creates a shared future that can be awaited by multiple callers.
-    async fn generate_docs(&self, crate_name: &str) -> Result<Arc<CrateIndex>, String> {
-        let workspace = self
-            .workspace
+    async fn generate_docs(
+        self: &Arc<Self>,
+        root: &Path,
+        crate_name: &str,
+        features_override: Option<Vec<String>>,
+    ) -> Result<Arc<CrateIndex>, String> {
+        let (workspace, working_dir, cargo_lock) = self
+            .workspaces
             .read()
             .await
-            .clone()
+            .get(root)
+            .map(|slot| {
+                (
+                    slot.context.clone(),
+                    slot.working_directory.clone(),
+                    slot.cargo_lock_path.clone(),
+                )
+            })
             .ok_or_else(|| "No workspace configured".to_string())?;
 
-        let working_dir = self
-            .working_directory
-            .read()
-            .await
-            .clone()
-            .ok_or_else(|| "No working directory configured".to_string())?;
-
-        let cargo_lock = self.cargo_lock_path.read().await.clone();
-
         // Get crate metadata
         let meta = workspace
             .get_crate(crate_name)
             .ok_or_else(|| format!("Crate '{}' not found in workspace", crate_name))?;
 
-        let is_workspace_member = meta.origin == crate::workspace::CrateOrigin::Local;
-        let version = meta.version.clone();
-        let crate_name_owned = CrateName::new_unchecked(crate_name);
+        let target_dir = workspace.target_dir.clone();
+        let mut target = DocTarget::new(meta, &workspace);
+        let mut doc_name = CrateName::new_unchecked(workspace.qualified_name(meta));
+        if let Some(features) = &features_override {
+            target.features = Some(features.clone());
+            target.features_override = true;
+            doc_name = feature_doc_name(&doc_name, features);
+        }
 
-        // Create the generation future
+        // Create the generation future, publishing its progress to every waiter
+        let progress_tx = Arc::new(watch::Sender::new(None));
         let generation_future: BoxFuture<'static, Result<Arc<CrateIndex>, String>> =
-            Box::pin(async move {
+            Box::pin(progress::publish(progress_tx.clone(), async move {
                 crate::workspace::get_docs(
-                    &crate_name_owned,
-                    version.as_deref(),
+                    &target,
                     &working_dir,
-                    is_workspace_member,
+                    &target_dir,
                     cargo_lock.as_deref(),
                 )
                 .await
                 .map(Arc::new)
                 .map_err(|e| e.to_string())
-            });
+            }));
 
         // Make it shared so multiple callers can await
-        let shared_future = generation_future.shared();
+        let in_flight_entry = InFlight {
+            future: generation_future.shared(),
+            progress: progress_tx,
+        };
 
-        let key = CrateName::new_unchecked(crate_name);
+        let key = (workspace.root.clone(), doc_name);
 
         // Store in in_flight map
         {
             let mut in_flight = self.in_flight.lock().await;
-            in_flight.insert(key.clone(), shared_future.clone());
+            in_flight.insert(key.clone(), in_flight_entry.clone());
         }
 
         tracing::info!(crate_name, "Starting documentation generation");
 
-        // Await the result
-        let result = shared_future.await;
+        // Finish the generation in its own task, so that it's recorded even if
+        // this caller stops waiting, e.g. when background generation is cancelled
+        let progress = in_flight_entry.progress.clone();
+        let finish = tokio::spawn(Arc::clone(self).finish_generation(
+            crate_name.to_string(),
+            key,
+            in_flight_entry.future,
+            features_override,
+        ));
+        progress::follow(&progress, finish)
+            .await
+            .unwrap_or_else(|e| Err(format!("Documentation generation panicked: {e}")))
+    }
+
+    /// Await a generation started by [`Self::generate_docs`], then drop it from
+    /// the in-flight map and cache its docs.
+    async fn finish_generation(
+        self: Arc<Self>,
+        crate_name: String,
+        key: (PathBuf, CrateName),
+        future: SharedDocFuture,
+        features_override: Option<Vec<String>>,
+    ) -> Result<Arc<CrateIndex>, String> {
+        let crate_name = crate_name.as_str();
+        let result = future.await;
 
         // Remove from in_flight
         {
@@ -289,21 +690,31 @@ impl DocState {
             if in_flight.remove(&key).is_none() {
                 tracing::warn!(
                     crate_name,
-                    normalized = key.normalized(),
+                    normalized = key.1.normalized(),
                     "in_flight entry was missing during removal"
                 );
             }
         }
 
-        // Cache on success
+        // Cache on success, under the workspace the docs belong to even if
+        // another one became active meanwhile
         match &result {
             Ok(index) => {
-                let index = index.clone();
-                {
-                    let mut cache = self.cache.write().await;
-                    cache.put(key, index);
+                let (root, name) = key;
+                let resolved = features_override.is_none();
+                if self.workspaces.read().await.contains_key(&root) {
+                    let cached = CachedDocs {
+                        index: index.clone(),
+                        features_override,
+                    };
+                    self.memory
+                        .insert(MemoryKey::Workspace(root, name.clone()), cached);
                 }
                 tracing::debug!(crate_name, "Docs cached in memory");
+                // Only the resolved docs are listed as resources
+                if resolved {
+                    self.emit(DocEvent::DocsReady(name));
+                }
             }
             Err(e) => {
                 tracing::warn!(crate_name, error = %e, "Documentation generation failed");
@@ -313,31 +724,79 @@ impl DocState {
         result
     }
 
+    /// Key of a crate of the workspace at `root`: the root and the crate's cache
+    /// key (see [`WorkspaceSlot::doc_key`]). `None` if no workspace is configured
+    /// there.
+    async fn doc_key(&self, root: &Path, crate_name: &str) -> Option<(PathBuf, CrateName)> {
+        self.workspaces
+            .read()
+            .await
+            .get(root)
+            .map(|slot| (slot.context.root.clone(), slot.doc_key(crate_name)))
+    }
+
+    /// Drop a crate's cached docs, including those built with a features override,
+    /// so the next request reloads (and, if its sources changed, regenerates) them.
+    pub async fn invalidate(&self, root: &Path, crate_name: &str) {
+        if let Some(key) = self.doc_key(root, crate_name).await {
+            self.memory.remove(&memory_key(&key));
+            let overrides = format!("{}{FEATURES_SEPARATOR}", key.1.normalized());
+            self.memory.retain(|cached| {
+                !matches!(cached, MemoryKey::Workspace(root, name)
+                    if *root == key.0 && name.normalized().starts_with(&overrides))
+            });
+        }
+    }
+
     /// Check if docs are cached for a crate.
-    pub async fn is_cached(&self, crate_name: &str) -> bool {
-        let key = CrateName::new_unchecked(crate_name);
-        self.cache.read().await.contains(&key)
+    pub async fn is_cached(&self, root: &Path, crate_name: &str) -> bool {
+        self.doc_key(root, crate_name)
+            .await
+            .is_some_and(|key| self.memory.contains(&memory_key(&key)))
+    }
+
+    /// Crates of the workspace at `root` whose docs are in the memory cache.
+    pub fn cached_crates(&self, root: &Path) -> Vec<CrateName> {
+        self.memory.workspace_crates(root)
+    }
+
+    /// Number of crates being generated, across all workspaces.
+    pub async fn generating_count(&self) -> usize {
+        self.in_flight.lock().await.len()
     }
 
     /// Check if generation is in progress for a crate.
-    pub async fn is_generating(&self, crate_name: &str) -> bool {
-        let key = CrateName::new_unchecked(crate_name);
+    pub async fn is_generating(&self, root: &Path, crate_name: &str) -> bool {
+        let Some(key) = self.doc_key(root, crate_name).await else {
+            return false;
+        };
         self.in_flight.lock().await.contains_key(&key)
     }
 
     /// Get a cached CrateIndex without triggering generation.
-    pub async fn get_cached(&self, crate_name: &str) -> Option<Arc<CrateIndex>> {
-        let key = CrateName::new_unchecked(crate_name);
-        self.cache.write().await.get(&key).cloned()
+    pub async fn get_cached(&self, root: &Path, crate_name: &str) -> Option<Arc<CrateIndex>> {
+        let key = self.doc_key(root, crate_name).await?;
+        self.memory
+            .get(&memory_key(&key))
+            .map(|cached| cached.index)
     }
 
-    /// Put a CrateIndex directly into the cache.
-    pub async fn put_cached(&self, crate_name: CrateName, index: Arc<CrateIndex>) {
-        self.cache.write().await.put(crate_name, index);
+    /// Put a CrateIndex directly into the cache of the workspace at `root`.
+    pub fn put_cached(&self, root: &Path, crate_name: CrateName, index: Arc<CrateIndex>) {
+        self.memory.insert(
+            MemoryKey::Workspace(root.to_path_buf(), crate_name),
+            CachedDocs::resolved(index),
+        );
     }
 }
 
+/// Memory cache key of a crate keyed by [`DocState::doc_key`].
+fn memory_key((root, crate_name): &(PathBuf, CrateName)) -> MemoryKey {
+    MemoryKey::Workspace(root.clone(), crate_name.clone())
+}
+
 /// Background worker that continuously detects workspaces and pre-generates docs.
+#[derive(Clone)]
 struct BackgroundWorker {
     state: Arc<DocState>,
     ctx: ServiceContext,
@@ -348,80 +807,196 @@ impl BackgroundWorker {
         Self { state, ctx }
     }
 
-    /// Run the background worker loop until cancelled.
+    /// Run the background worker until cancelled.
+    ///
+    /// After detecting the workspace, the worker is idle until the filesystem
+    /// watcher reports changes or a workspace is configured through a tool.
     async fn run(&self) {
-        // Run detection immediately on start
-        self.detect_and_generate().await;
-
-        let mut ticker = interval_at(Instant::now() + DETECTION_INTERVAL, DETECTION_INTERVAL);
-
-        while self.ctx.tick(&mut ticker).await {
-            self.detect_and_generate().await;
+        let mut events = self.state.subscribe();
+
+        self.detect_workspace().await;
+        // Detection's own `WorkspaceChanged` is covered by the explicit start below
+        while events.try_recv().is_ok() {}
+
+        let mut watcher = None;
+        let mut generation = None;
+        self.on_workspace_changed(&mut watcher, &mut generation)
+            .await;
+
+        loop {
+            let workspace = self.state.workspace().await;
+            tokio::select! {
+                () = self.ctx.token.cancelled() => break,
+                event = events.recv() => match event {
+                    Ok(DocEvent::WorkspaceChanged) | Err(RecvError::Lagged(_)) => {
+                        self.on_workspace_changed(&mut watcher, &mut generation)
+                            .await;
+                    }
+                    Ok(DocEvent::DocsReady(_)) => {}
+                    Err(RecvError::Closed) => break,
+                },
+                Some(changes) = next_changes(watcher.as_mut(), workspace.as_ref()) => {
+                    self.apply_changes(changes).await;
+                }
+            }
         }
 
+        if let Some(generation) = generation {
+            generation.abort();
+        }
         tracing::info!("Background worker shutting down");
     }
 
-    /// Perform one cycle of workspace detection and doc generation.
-    async fn detect_and_generate(&self) {
-        if self.ctx.is_cancelled() {
+    /// Detect the workspace from the working directory, unless client roots or
+    /// `set_workspace` already configured one.
+    async fn detect_workspace(&self) {
+        if self.ctx.is_cancelled() || self.state.has_workspace().await {
             return;
         }
 
-        // 1. Detect workspace
         let Some(workspace_path) = auto_detect_workspace().await else {
             tracing::trace!("No workspace detected");
             return;
         };
 
-        // 2. Check if workspace changed
-        let current_workspace = self.state.workspace().await;
-        let workspace_changed = current_workspace
-            .as_ref()
-            .is_none_or(|w| w.root != workspace_path);
+        tracing::info!(workspace_path = %workspace_path.display(), "Workspace detected, configuring");
 
-        if !workspace_changed {
-            tracing::debug!(workspace = %workspace_path.display(), "Workspace unchanged, scanning for uncached crates");
-            if let Some(workspace) = current_workspace {
-                self.generate_uncached_docs(&workspace).await;
+        match handle_set_workspace(workspace_path.display().to_string(), None).await {
+            // Client roots or set_workspace take precedence over the working directory
+            Ok(_) if self.state.has_workspace().await => {
+                tracing::debug!("Workspace configured during detection, keeping it");
+            }
+            Ok((canonical_path, workspace_info, _changed)) => {
+                self.configure(canonical_path, workspace_info).await;
+            }
+            Err(e) => {
+                tracing::warn!(error = ?e, "Background workspace detection failed");
             }
+        }
+    }
+
+    /// Install `workspace_info` as the current workspace.
+    async fn configure(&self, canonical_path: PathBuf, workspace_info: WorkspaceContext) {
+        let cargo_lock = canonical_path.join("Cargo.lock");
+        let cargo_lock = if cargo_lock.exists() {
+            Some(cargo_lock)
+        } else {
+            None
+        };
+
+        tracing::info!(
+            workspace = %canonical_path.display(),
+            members = workspace_info.members.len(),
+            crates = workspace_info.crate_info.len(),
+            "Background worker configured workspace"
+        );
+
+        self.state
+            .set_detected_workspace(canonical_path, workspace_info, cargo_lock)
+            .await;
+    }
+
+    /// Watch the newly configured workspace and generate its missing docs.
+    ///
+    /// Generation runs as its own task, so the worker keeps handling events and
+    /// file changes meanwhile; a later workspace change aborts it and starts over.
+    async fn on_workspace_changed(
+        &self,
+        watcher: &mut Option<WorkspaceWatcher>,
+        generation: &mut Option<JoinHandle<()>>,
+    ) {
+        if let Some(previous) = generation.take() {
+            previous.abort();
+        }
+        let Some(workspace) = self.state.workspace().await else {
+            *watcher = None;
+            return;
+        };
+
+        *watcher = match WorkspaceWatcher::new(&workspace) {
+            Ok(new_watcher) => Some(new_watcher),
+            Err(e) => {
+                tracing::warn!(error = %e, workspace = %workspace.root.display(), "Failed to watch workspace");
+                None
+            }
+        };
+
+        let worker = self.clone();
+        *generation = Some(self.ctx.tracker.spawn(async move {
+            worker.generate_uncached_docs(&workspace).await;
+        }));
+    }
+
+    /// React to a batch of filesystem changes.
+    async fn apply_changes(&self, changes: WorkspaceChanges) {
+        tracing::debug!(?changes, "Workspace files changed");
+
+        if changes.manifest {
+            // Emits `WorkspaceChanged`, which regenerates whatever was invalidated
+            self.reload_workspace().await;
             return;
         }
 
-        // 3. Configure the new workspace
-        tracing::info!(workspace_path = %workspace_path.display(), "Workspace change detected, reconfiguring");
+        for member in &changes.members {
+            if self.ctx.is_cancelled() {
+                return;
+            }
+            self.regenerate_member(member).await;
+        }
+    }
+
+    /// Re-read the workspace metadata after a manifest or lockfile change.
+    ///
+    /// Reconfiguring drops the cached docs the new metadata invalidates.
+    async fn reload_workspace(&self) {
+        let Some(current) = self.state.workspace().await else {
+            return;
+        };
 
-        match handle_set_workspace(workspace_path.display().to_string(), None).await {
+        match handle_set_workspace(current.root.display().to_string(), Some(&current.root)).await {
             Ok((canonical_path, workspace_info, _changed)) => {
-                let cargo_lock = canonical_path.join("Cargo.lock");
-                let cargo_lock = if cargo_lock.exists() {
-                    Some(cargo_lock)
-                } else {
-                    None
-                };
-
-                self.state.clear_cache().await;
-
-                self.state
-                    .set_workspace(canonical_path.clone(), workspace_info.clone(), cargo_lock)
-                    .await;
-
-                tracing::info!(
-                    workspace = %canonical_path.display(),
-                    members = workspace_info.members.len(),
-                    crates = workspace_info.crate_info.len(),
-                    "Background worker configured workspace"
-                );
-
-                self.generate_uncached_docs(&workspace_info).await;
+                self.configure(canonical_path, workspace_info).await;
             }
             Err(e) => {
-                tracing::warn!(error = ?e, "Background workspace detection failed");
+                tracing::warn!(error = ?e, "Failed to reload workspace after manifest change");
             }
         }
     }
 
+    /// Regenerate a member's docs and search index after its sources changed.
+    async fn regenerate_member(&self, member: &CrateName) {
+        let Some(workspace) = self.state.workspace().await else {
+            return;
+        };
+        self.state
+            .invalidate(&workspace.root, member.as_str())
+            .await;
+
+        if let Err(e) = self.state.get_docs(&workspace.root, member.as_str()).await {
+            tracing::warn!(crate_name = %member, error = %e, "Regenerating documentation failed");
+            return;
+        }
+
+        let crate_name = member.as_str().to_string();
+        let state = self.state.clone();
+        // The search index is built from a QueryContext, which isn't Send
+        let rebuilt = tokio::task::spawn_blocking(move || {
+            let query_ctx = state.query_context(workspace);
+            TermIndex::load_or_build(&query_ctx, &crate_name).is_ok()
+        })
+        .await;
+
+        match rebuilt {
+            Ok(true) => tracing::info!(crate_name = %member, "Regenerated documentation"),
+            Ok(false) => tracing::warn!(crate_name = %member, "Rebuilding search index failed"),
+            Err(e) => tracing::warn!(crate_name = %member, error = %e, "Index rebuild panicked"),
+        }
+    }
+
     /// Generate docs for crates that aren't cached yet.
+    ///
+    /// Safe to abort at any point: a generation already started finishes, and is
+    /// cached, on its own.
     async fn generate_uncached_docs(&self, workspace: &WorkspaceContext) {
         let prioritized = workspace.prioritized_crates();
         let total = prioritized.len();
@@ -434,9 +1009,17 @@ impl BackgroundWorker {
             if StdlibDocs::is_stdlib_crate(crate_name.as_str()) {
                 continue;
             }
-            if self.state.is_cached(crate_name.as_str()).await {
+            if self
+                .state
+                .is_cached(&workspace.root, crate_name.as_str())
+                .await
+            {
                 already_cached += 1;
-            } else if self.state.is_generating(crate_name.as_str()).await {
+            } else if self
+                .state
+                .is_generating(&workspace.root, crate_name.as_str())
+                .await
+            {
                 already_generating += 1;
             } else {
                 to_generate.push(crate_name.clone());
@@ -458,7 +1041,11 @@ impl BackgroundWorker {
                 return;
             }
 
-            match self.state.get_docs(crate_name.as_str()).await {
+            match self
+                .state
+                .get_docs(&workspace.root, crate_name.as_str())
+                .await
+            {
                 Ok(_) => {
                     tracing::info!(crate_name = %crate_name, "Background documentation ready");
                 }
@@ -472,6 +1059,17 @@ impl BackgroundWorker {
     }
 }
 
+/// Wait for the watcher's next batch of changes, or forever without a watcher.
+async fn next_changes(
+    watcher: Option<&mut WorkspaceWatcher>,
+    workspace: Option<&WorkspaceContext>,
+) -> Option<WorkspaceChanges> {
+    match (watcher, workspace) {
+        (Some(watcher), Some(workspace)) => watcher.next_changes(workspace).await,
+        _ => std::future::pending().await,
+    }
+}
+
 /// Spawn the background worker as a tracked task.
 ///
 /// Returns the `ServiceContext` which can be used to trigger graceful shutdown.
@@ -489,7 +1087,8 @@ pub fn spawn_background_worker(state: Arc<DocState>) -> ServiceContext {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use assert2::check;
+    use crate::workspace::CrateMetadata;
+    use assert2::{check, let_assert};
 
     #[tokio::test]
     async fn test_doc_state_new() {
@@ -502,8 +1101,281 @@ mod tests {
     #[tokio::test]
     async fn test_cache_operations() {
         let state = DocState::new(None);
-        check!(!state.is_cached("test_crate").await);
-        check!(state.get_cached("test_crate").await.is_none());
+        let root = Path::new("/tmp/ws");
+        check!(!state.is_cached(root, "test_crate").await);
+        check!(state.get_cached(root, "test_crate").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_set_workspace_emits_event() {
+        let state = DocState::new(None);
+        let mut events = state.subscribe();
+
+        let workspace = WorkspaceContext {
+            root: PathBuf::from("/tmp/ws"),
+            members: vec![],
+            crate_info: HashMap::new(),
+            root_crate: None,
+            target_dir: PathBuf::from("/tmp/ws/target"),
+        };
+        state
+            .set_workspace(PathBuf::from("/tmp/ws"), workspace, None)
+            .await;
+
+        check!(events.try_recv() == Ok(DocEvent::WorkspaceChanged));
+    }
+
+    fn crate_meta(name: &str, origin: CrateOrigin) -> CrateMetadata {
+        CrateMetadata {
+            origin,
+            version: Some("1.0.0".to_string()),
+            description: None,
+            dev_dep: false,
+            name: CrateName::new_unchecked(name),
+            is_root_crate: false,
+            used_by: vec![],
+            features: None,
+            sources: None,
+        }
+    }
+
+    fn workspace_at(root: &str) -> WorkspaceContext {
+        let crate_info = [
+            crate_meta("app", CrateOrigin::Local),
+            crate_meta("serde", CrateOrigin::External),
+        ]
+        .into_iter()
+        .map(|meta| (meta.key(), meta))
+        .collect();
+        WorkspaceContext {
+            root: PathBuf::from(root),
+            members: vec![CrateName::new_unchecked("app")],
+            crate_info,
+            root_crate: None,
+            target_dir: Path::new(root).join("target"),
+        }
+    }
+
+    fn empty_index(dir: &Path) -> Arc<CrateIndex> {
+        let path = dir.join("empty.json");
+        let json = r#"{"root":0,"crate_version":null,"includes_private":false,"index":{},"paths":{},"external_crates":{},"target":{"triple":"x86_64-unknown-linux-gnu","target_features":[]},"format_version":56}"#;
+        std::fs::write(&path, json).unwrap();
+        Arc::new(CrateIndex::load(&path).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_switching_workspaces_keeps_caches() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = DocState::new(None);
+        let (a, b) = (Path::new("/ws/a"), Path::new("/ws/b"));
+
+        state
+            .set_workspace(a.to_path_buf(), workspace_at("/ws/a"), None)
+            .await;
+        state.put_cached(
+            a,
+            CrateName::new_unchecked("serde"),
+            empty_index(dir.path()),
+        );
+
+        state
+            .set_workspace(b.to_path_buf(), workspace_at("/ws/b"), None)
+            .await;
+        check!(!state.is_cached(b, "serde").await);
+        check!(state.is_cached(a, "serde").await);
+        check!(state.workspace_roots().await.len() == 2);
+
+        state
+            .set_workspace(a.to_path_buf(), workspace_at("/ws/a"), None)
+            .await;
+        check!(state.is_cached(a, "serde").await);
+    }
+
+    /// An in-flight generation producing `index`.
+    fn generating(index: Arc<CrateIndex>) -> InFlight {
+        let future: BoxFuture<'static, Result<Arc<CrateIndex>, String>> =
+            Box::pin(async move { Ok(index) });
+        InFlight {
+            future: future.shared(),
+            progress: Arc::new(watch::Sender::new(None)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_features_override_kept_apart_from_resolved_docs() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = Arc::new(DocState::new(None));
+        let root = Path::new("/ws/a");
+        state
+            .set_workspace(root.to_path_buf(), workspace_at("/ws/a"), None)
+            .await;
+        let resolved = empty_index(dir.path());
+        state.put_cached(root, CrateName::new_unchecked("serde"), resolved.clone());
+
+        // A build with the override another request started
+        let features = vec!["derive".to_string()];
+        let overridden = empty_index(dir.path());
+        let key = (
+            root.to_path_buf(),
+            feature_doc_name(&CrateName::new_unchecked("serde"), &features),
+        );
+        state
+            .in_flight
+            .lock()
+            .await
+            .insert(key.clone(), generating(overridden.clone()));
+
+        let_assert!(Ok(index) = state.get_docs_with_features(root, "serde", &features).await);
+        check!(Arc::ptr_eq(&index, &overridden));
+        let_assert!(Ok(index) = state.get_docs(root, "serde").await);
+        check!(Arc::ptr_eq(&index, &resolved));
+
+        // Invalidating the crate drops its override builds too
+        let cached = CachedDocs {
+            index: overridden,
+            features_override: Some(features),
+        };
+        state.memory.insert(memory_key(&key), cached);
+        state.invalidate(root, "serde").await;
+        check!(!state.memory.contains(&memory_key(&key)));
+        check!(!state.is_cached(root, "serde").await);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_generation_still_finishes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let state = Arc::new(DocState::new(None));
+        state
+            .set_workspace(root.clone(), workspace_at(root.to_str().unwrap()), None)
+            .await;
+
+        let waiter = tokio::spawn({
+            let (state, root) = (state.clone(), root.clone());
+            async move { state.get_docs(&root, "serde").await }
+        });
+        while !waiter.is_finished() && !state.is_generating(&root, "serde").await {
+            tokio::task::yield_now().await;
+        }
+        waiter.abort();
+
+        // The generation (which fails: there's no such dependency) still leaves the map
+        let finished = tokio::time::timeout(Duration::from_secs(30), async {
+            while state.is_generating(&root, "serde").await {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        });
+        check!(finished.await.is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_query_context_awaits_in_flight_generation() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        let root = dir.path().to_str().unwrap();
+        let state = Arc::new(DocState::new(None));
+        state
+            .set_workspace(dir.path().to_path_buf(), workspace_at(root), None)
+            .await;
+
+        // A generation another request started
+        let index = empty_index(dir.path());
+        state.in_flight.lock().await.insert(
+            (dir.path().to_path_buf(), CrateName::new_unchecked("serde")),
+            generating(index.clone()),
+        );
+
+        let query_ctx = state.query_context(workspace_at(root));
+        let_assert!(Ok(loaded) = query_ctx.load_shared("serde"));
+        check!(Arc::ptr_eq(&loaded, &index));
+    }
+
+    #[tokio::test]
+    async fn test_reconfiguring_drops_members_and_changed_crates() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = DocState::new(None);
+        let root = Path::new("/ws/a");
+        state
+            .set_workspace(root.to_path_buf(), workspace_at("/ws/a"), None)
+            .await;
+        for name in ["app", "serde"] {
+            state.put_cached(
+                root,
+                CrateName::new_unchecked(name),
+                empty_index(dir.path()),
+            );
+        }
+
+        state
+            .set_workspace(root.to_path_buf(), workspace_at("/ws/a"), None)
+            .await;
+        check!(!state.is_cached(root, "app").await);
+        check!(state.is_cached(root, "serde").await);
+
+        let mut workspace = workspace_at("/ws/a");
+        for meta in workspace.crate_info.values_mut() {
+            meta.features = Some(vec!["derive".to_string()]);
+        }
+        state
+            .set_workspace(root.to_path_buf(), workspace, None)
+            .await;
+        check!(!state.is_cached(root, "serde").await);
+    }
+
+    #[tokio::test]
+    async fn test_select_workspace_by_contained_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(dir.path()).unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+        let state = DocState::new(None);
+
+        let root_str = root.to_str().unwrap();
+        state
+            .set_workspace(root.clone(), workspace_at(root_str), None)
+            .await;
+        state
+            .set_workspace(PathBuf::from("/ws/b"), workspace_at("/ws/b"), None)
+            .await;
+
+        let src = root.join("src");
+        let_assert!(Ok(Some(selected)) = state.select_workspace(src.to_str()).await);
+        check!(selected.root == root);
+        let_assert!(Ok(Some(active)) = state.select_workspace(None).await);
+        check!(active.root == PathBuf::from("/ws/b"));
+    }
+
+    #[tokio::test]
+    async fn test_set_roots_activates_containing_workspace() {
+        let crate_root = std::fs::canonicalize(env!("CARGO_MANIFEST_DIR")).unwrap();
+        let state = DocState::new(None);
+        state
+            .set_detected_workspace(PathBuf::from("/ws/b"), workspace_at("/ws/b"), None)
+            .await;
+
+        let roots = [crate_root.join("src"), PathBuf::from("/nonexistent/root")];
+        let loaded = state.set_roots(&roots).await;
+        check!(loaded == std::slice::from_ref(&crate_root));
+        let_assert!(Some(active) = state.workspace().await);
+        check!(active.root == crate_root);
+    }
+
+    #[tokio::test]
+    async fn test_set_roots_keeps_chosen_workspace() {
+        let crate_root = std::fs::canonicalize(env!("CARGO_MANIFEST_DIR")).unwrap();
+        let state = DocState::new(None);
+        state
+            .set_workspace(PathBuf::from("/ws/b"), workspace_at("/ws/b"), None)
+            .await;
+        // Reloading the chosen workspace keeps it chosen
+        state
+            .set_detected_workspace(PathBuf::from("/ws/b"), workspace_at("/ws/b"), None)
+            .await;
+
+        let loaded = state.set_roots(&[crate_root.join("src")]).await;
+        check!(loaded == std::slice::from_ref(&crate_root));
+        check!(state.workspace_at(&crate_root).await.is_some());
+        let_assert!(Some(active) = state.workspace().await);
+        check!(active.root == PathBuf::from("/ws/b"));
     }
 
     #[tokio::test]
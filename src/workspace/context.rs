@@ -1,12 +1,16 @@
 //! Workspace context and crate metadata types.
 
-use crate::types::CrateName;
+use crate::types::{CrateKey, CrateName};
+use cargo_metadata::semver::Version;
+use rmcp::schemars;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Type of crate in the workspace context
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum CrateOrigin {
     /// A workspace member (local crate)
@@ -34,6 +38,36 @@ pub struct CrateMetadata {
     pub is_root_crate: bool,
     /// Which workspace members use this dependency
     pub used_by: Vec<CrateName>,
+    /// Features activated by cargo's resolver, sorted.
+    ///
+    /// `None` when unknown, in which case docs are built with the crate's default features.
+    pub features: Option<Vec<String>>,
+    /// Where a workspace member's sources live, for change detection.
+    ///
+    /// `None` for dependencies, and when unknown (the workspace root's `Cargo.toml`
+    /// and `src` are assumed).
+    pub sources: Option<MemberSources>,
+}
+
+impl CrateMetadata {
+    /// Key identifying this crate in [`WorkspaceContext::crate_info`].
+    pub fn key(&self) -> CrateKey {
+        CrateKey {
+            name: self.name.clone(),
+            version: self.version.clone(),
+        }
+    }
+}
+
+/// Source locations of a workspace member, as reported by `cargo metadata`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberSources {
+    /// The member's own `Cargo.toml`
+    pub manifest_path: PathBuf,
+    /// Directories containing the entry points of the member's targets (e.g. `src`, `tests`)
+    pub source_dirs: Vec<PathBuf>,
+    /// Individual source files outside those directories, such as `build.rs`
+    pub extra_files: Vec<PathBuf>,
 }
 
 /// Context about a Rust workspace discovered via cargo metadata.
@@ -47,14 +81,23 @@ pub struct WorkspaceContext {
     /// Workspace members (crate names)
     pub members: Vec<CrateName>,
 
-    /// Detailed crate information with usage tracking, indexed by crate name
-    pub crate_info: HashMap<CrateName, CrateMetadata>,
+    /// Detailed crate information with usage tracking, indexed by crate name and version
+    pub crate_info: HashMap<CrateKey, CrateMetadata>,
 
     /// Root crate name (if this is a single-crate workspace)
     pub root_crate: Option<CrateName>,
+
+    /// Cargo's target directory, as reported by `cargo metadata`: honors
+    /// `CARGO_TARGET_DIR` and `build.target-dir`, and defaults to `<root>/target`
+    pub target_dir: PathBuf,
 }
 
 impl WorkspaceContext {
+    /// Directory rustdoc writes JSON docs to, along with digests and search indexes.
+    pub fn doc_dir(&self) -> PathBuf {
+        self.target_dir.join("doc")
+    }
+
     /// Get the default crate name (root crate or first workspace member).
     pub fn default_crate_name(&self) -> Option<&CrateName> {
         self.root_crate.as_ref().or_else(|| self.members.first())
@@ -72,24 +115,90 @@ impl WorkspaceContext {
         None
     }
 
-    /// Get the version of a crate by name.
-    pub fn get_version(&self, name: &str) -> Option<&str> {
-        let key = CrateName::new_unchecked(name);
-        self.crate_info.get(&key).and_then(|m| m.version.as_deref())
+    /// Get the version of a crate by name or `name@version` spec.
+    pub fn get_version(&self, spec: &str) -> Option<&str> {
+        self.get_crate(spec).and_then(|m| m.version.as_deref())
     }
 
     /// Get an iterator over dependency names (excludes workspace members).
-    pub fn dependency_names(&self) -> impl Iterator<Item = &str> {
+    ///
+    /// Crates present in several versions are listed once per version, as `name@version`.
+    pub fn dependency_names(&self) -> impl Iterator<Item = String> + '_ {
+        self.crate_info
+            .values()
+            .filter(|meta| !self.members.contains(&meta.name))
+            .map(|meta| self.qualified_name(meta))
+    }
+
+    /// Get metadata for a crate by name or `name@version` spec.
+    ///
+    /// The version may be a prefix such as `1` or `1.0`. When several versions match,
+    /// the primary one is returned (see [`Self::crate_versions`]).
+    pub fn get_crate(&self, spec: &str) -> Option<&CrateMetadata> {
+        self.crate_versions(spec).into_iter().next()
+    }
+
+    /// Get all crates matching a name or `name@version` spec, primary version first.
+    ///
+    /// The primary version is the one used by the most workspace members, with ties
+    /// going to the highest version.
+    pub fn crate_versions(&self, spec: &str) -> Vec<&CrateMetadata> {
+        let (name, version) = split_crate_spec(spec);
+        let mut found: Vec<_> = self
+            .crate_info
+            .values()
+            .filter(|meta| meta.name.matches(name))
+            .filter(|meta| {
+                version.is_none_or(|wanted| {
+                    meta.version
+                        .as_deref()
+                        .is_some_and(|actual| version_matches(actual, wanted))
+                })
+            })
+            .collect();
+
+        found.sort_by(|a, b| {
+            b.used_by
+                .len()
+                .cmp(&a.used_by.len())
+                .then_with(|| compare_versions(b.version.as_deref(), a.version.as_deref()))
+        });
+        found
+    }
+
+    /// Check whether more than one version of a crate is in the workspace.
+    pub fn has_multiple_versions(&self, name: &str) -> bool {
         self.crate_info
             .keys()
-            .filter(|name| !self.members.iter().any(|m| m.matches(name.as_str())))
-            .map(super::super::types::CrateName::as_str)
+            .filter(|key| key.name.matches(name))
+            .nth(1)
+            .is_some()
+    }
+
+    /// Name under which a crate's docs are stored and queried.
+    ///
+    /// This is `name@version` when several versions of the crate are present, and the
+    /// plain crate name otherwise.
+    pub fn qualified_name(&self, meta: &CrateMetadata) -> String {
+        if self.has_multiple_versions(meta.name.normalized()) {
+            meta.key().to_string()
+        } else {
+            meta.name.as_str().to_string()
+        }
     }
 
-    /// Get metadata for a specific crate by name.
-    pub fn get_crate(&self, name: &str) -> Option<&CrateMetadata> {
-        let key = CrateName::new_unchecked(name);
-        self.crate_info.get(&key)
+    /// Resolve a crate name or `name@version` spec to its [qualified name](Self::qualified_name).
+    ///
+    /// Unknown crates are returned unchanged.
+    pub fn canonical_crate_name<'a>(&self, spec: &'a str) -> Cow<'a, str> {
+        match self.get_crate(spec) {
+            Some(meta)
+                if spec.contains('@') || self.has_multiple_versions(meta.name.normalized()) =>
+            {
+                Cow::Owned(self.qualified_name(meta))
+            }
+            _ => Cow::Borrowed(spec),
+        }
     }
 
     /// Get an iterator over crate info, optionally filtered by workspace member.
@@ -142,6 +251,126 @@ impl WorkspaceContext {
                 .then_with(|| a.name.normalized().cmp(b.name.normalized()))
         });
 
-        crates.into_iter().map(|c| c.name.clone()).collect()
+        crates
+            .into_iter()
+            .map(|c| CrateName::new_unchecked(self.qualified_name(c)))
+            .collect()
+    }
+}
+
+/// Split a `name@version` spec into its parts.
+fn split_crate_spec(spec: &str) -> (&str, Option<&str>) {
+    match spec.split_once('@') {
+        Some((name, version)) => (name, Some(version)),
+        None => (spec, None),
+    }
+}
+
+/// Check whether `actual` is `wanted` or starts with it as a version prefix (`1` matches `1.0.2`).
+fn version_matches(actual: &str, wanted: &str) -> bool {
+    actual
+        .strip_prefix(wanted)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// Order versions by semver precedence, falling back to string order.
+fn compare_versions(a: Option<&str>, b: Option<&str>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => match (Version::parse(a), Version::parse(b)) {
+            (Ok(va), Ok(vb)) => va.cmp(&vb),
+            _ => a.cmp(b),
+        },
+        _ => a.cmp(&b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+
+    fn dependency(name: &str, version: &str, used_by: &[&str]) -> CrateMetadata {
+        CrateMetadata {
+            origin: CrateOrigin::External,
+            version: Some(version.to_string()),
+            description: None,
+            dev_dep: false,
+            name: CrateName::new_unchecked(name),
+            is_root_crate: false,
+            used_by: used_by
+                .iter()
+                .map(|m| CrateName::new_unchecked(*m))
+                .collect(),
+            features: None,
+            sources: None,
+        }
+    }
+
+    fn workspace(crates: Vec<CrateMetadata>) -> WorkspaceContext {
+        WorkspaceContext {
+            root: PathBuf::from("/ws"),
+            members: vec![
+                CrateName::new_unchecked("app"),
+                CrateName::new_unchecked("cli"),
+            ],
+            crate_info: crates.into_iter().map(|meta| (meta.key(), meta)).collect(),
+            root_crate: None,
+            target_dir: PathBuf::from("/ws/target"),
+        }
+    }
+
+    #[test]
+    fn resolves_versioned_specs() {
+        let ws = workspace(vec![
+            dependency("syn", "1.0.109", &["cli"]),
+            dependency("syn", "2.0.87", &["app", "cli"]),
+            dependency("serde", "1.0.210", &["app"]),
+        ]);
+
+        // Bare names resolve to the version most members use
+        check!(ws.get_version("syn") == Some("2.0.87"));
+        check!(ws.get_version("syn@1") == Some("1.0.109"));
+        check!(ws.get_version("syn@2.0") == Some("2.0.87"));
+        check!(ws.get_version("syn@1.0.109") == Some("1.0.109"));
+        check!(ws.get_crate("syn@10").is_none());
+        check!(ws.get_crate("syn@1.0.10").is_none());
+
+        let versions: Vec<_> = ws
+            .crate_versions("syn")
+            .into_iter()
+            .filter_map(|m| m.version.as_deref())
+            .collect();
+        check!(versions == ["2.0.87", "1.0.109"]);
+    }
+
+    #[test]
+    fn qualifies_only_ambiguous_names() {
+        let ws = workspace(vec![
+            dependency("syn", "1.0.109", &["cli"]),
+            dependency("syn", "2.0.87", &["app"]),
+            dependency("serde", "1.0.210", &["app"]),
+        ]);
+
+        check!(ws.has_multiple_versions("syn"));
+        check!(!ws.has_multiple_versions("serde"));
+        check!(ws.canonical_crate_name("serde") == "serde");
+        check!(ws.canonical_crate_name("serde@1") == "serde");
+        check!(ws.canonical_crate_name("syn@1") == "syn@1.0.109");
+        check!(ws.canonical_crate_name("unknown") == "unknown");
+
+        let mut names: Vec<_> = ws.dependency_names().collect();
+        names.sort();
+        check!(names == ["serde", "syn@1.0.109", "syn@2.0.87"]);
+    }
+
+    #[test]
+    fn equal_usage_prefers_highest_version() {
+        let ws = workspace(vec![
+            dependency("syn", "1.0.109", &["app"]),
+            dependency("syn", "2.0.9", &["cli"]),
+            dependency("syn", "2.0.10", &["cli"]),
+        ]);
+        let_assert!(Some(primary) = ws.get_crate("syn"));
+        check!(primary.version.as_deref() == Some("2.0.10"));
     }
 }
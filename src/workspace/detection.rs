@@ -1,21 +1,16 @@
 //! Automatic workspace detection for MCP server startup.
 //!
 //! This module provides functionality to automatically detect a Rust workspace
-//! by walking up the directory tree from the process's current working directory,
-//! respecting Git repository boundaries and system directory constraints.
+//! by walking up the directory tree from a starting directory (the roots an MCP
+//! client exposes, or the process's current working directory), respecting Git
+//! repository boundaries and system directory constraints.
 
 use std::env;
 use std::path::{Path, PathBuf};
 
 /// Automatically detect a workspace starting from the current working directory.
 ///
-/// This function orchestrates the detection logic:
-/// 1. Get the current working directory
-/// 2. Walk up directories looking for Cargo.toml
-/// 3. Apply constraints (Git boundaries, system dirs, max depth)
-/// 4. Validate that we found a workspace root (not just a package)
-///
-/// Returns the canonicalized path to the workspace directory, or None if no valid workspace found.
+/// Used when the MCP client doesn't expose roots; see [`detect_workspace_from`].
 pub(crate) async fn auto_detect_workspace() -> Option<PathBuf> {
     let cwd = match env::current_dir() {
         Ok(dir) => dir,
@@ -25,10 +20,25 @@ pub(crate) async fn auto_detect_workspace() -> Option<PathBuf> {
         }
     };
 
-    tracing::debug!("Starting workspace auto-detection from: {}", cwd.display());
+    detect_workspace_from(&cwd).await
+}
+
+/// Detect the workspace containing `start`.
+///
+/// This function orchestrates the detection logic:
+/// 1. Walk up directories from `start` looking for Cargo.toml
+/// 2. Apply constraints (Git boundaries, system dirs, max depth)
+/// 3. Validate that we found a workspace root (not just a package)
+///
+/// Returns the canonicalized path to the workspace directory, or None if no valid workspace found.
+pub(crate) async fn detect_workspace_from(start: &Path) -> Option<PathBuf> {
+    tracing::debug!(
+        "Starting workspace auto-detection from: {}",
+        start.display()
+    );
 
     // Find Cargo.toml with all constraints applied
-    let cargo_toml_path = find_cargo_toml_with_constraints(&cwd)?;
+    let cargo_toml_path = find_cargo_toml_with_constraints(start)?;
     let workspace_dir = cargo_toml_path.parent()?.to_path_buf();
 
     tracing::debug!(
@@ -56,6 +66,43 @@ pub(crate) async fn auto_detect_workspace() -> Option<PathBuf> {
     }
 }
 
+/// Convert an MCP root URI to a local path.
+///
+/// Roots are `file://` URIs; other schemes and remote hosts yield `None`.
+/// Percent-encoded bytes are decoded, so `file:///home/me/my%20project` becomes
+/// `/home/me/my project`.
+pub fn root_uri_to_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    let path = if rest.starts_with('/') {
+        rest
+    } else {
+        rest.strip_prefix("localhost")
+            .filter(|path| path.starts_with('/'))?
+    };
+
+    let mut decoded = Vec::with_capacity(path.len());
+    let mut bytes = path.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let hex = [bytes.next()?, bytes.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            decoded.push(byte);
+        }
+    }
+    let decoded = String::from_utf8(decoded).ok()?;
+
+    // `file:///C:/project` names `C:/project` on Windows
+    #[cfg(windows)]
+    let decoded = match decoded.strip_prefix('/') {
+        Some(stripped) if stripped.as_bytes().get(1) == Some(&b':') => stripped.to_string(),
+        _ => decoded,
+    };
+
+    Some(PathBuf::from(decoded))
+}
+
 /// Find a Cargo.toml file walking up from the given path, respecting all constraints.
 ///
 /// Constraints:
@@ -17,8 +17,51 @@ pub struct LockfileEntry {
     pub source: Option<String>,
 }
 
-/// Parse Cargo.lock and return a map of crate name to lockfile entry
-pub async fn parse_cargo_lock(lock_path: &Path) -> Result<HashMap<CrateName, LockfileEntry>> {
+/// Parsed Cargo.lock entries, grouped by crate name.
+///
+/// A lockfile may contain several versions of the same crate.
+#[derive(Debug, Clone, Default)]
+pub struct Lockfile {
+    packages: HashMap<CrateName, Vec<LockfileEntry>>,
+}
+
+impl Lockfile {
+    /// Look up a package by name, and by exact version when one is given.
+    ///
+    /// Without a version, the first entry for the name is returned.
+    pub fn get(&self, name: &str, version: Option<&str>) -> Option<&LockfileEntry> {
+        let entries = self.packages.get(CrateName::normalize(name).as_ref())?;
+        match version {
+            Some(version) => entries.iter().find(|entry| entry.version == version),
+            None => entries.first(),
+        }
+    }
+
+    /// All locked versions of a crate.
+    pub fn versions(&self, name: &str) -> &[LockfileEntry] {
+        self.packages
+            .get(CrateName::normalize(name).as_ref())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterate over every locked package.
+    pub fn iter(&self) -> impl Iterator<Item = &LockfileEntry> {
+        self.packages.values().flatten()
+    }
+
+    /// Number of locked packages (counting each version separately).
+    pub fn len(&self) -> usize {
+        self.packages.values().map(Vec::len).sum()
+    }
+
+    /// Whether the lockfile has no packages.
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+}
+
+/// Parse Cargo.lock into its package entries
+pub async fn parse_cargo_lock(lock_path: &Path) -> Result<Lockfile> {
     let content = tokio::fs::read_to_string(lock_path)
         .await
         .with_context(|| format!("Failed to read Cargo.lock at {}", lock_path.display()))?;
@@ -26,7 +69,7 @@ pub async fn parse_cargo_lock(lock_path: &Path) -> Result<HashMap<CrateName, Loc
 
     tracing::debug!(package_count = lockfile.package.len(), "Parsed Cargo.lock");
 
-    let mut crates = HashMap::new();
+    let mut packages: HashMap<CrateName, Vec<LockfileEntry>> = HashMap::new();
 
     for package in lockfile.package {
         let checksum = match package.checksum {
@@ -39,20 +82,21 @@ pub async fn parse_cargo_lock(lock_path: &Path) -> Result<HashMap<CrateName, Loc
             None => None,
         };
 
-        crates.insert(
-            CrateName::new_unchecked(package.name.clone()),
-            LockfileEntry {
+        packages
+            .entry(CrateName::new_unchecked(package.name.clone()))
+            .or_default()
+            .push(LockfileEntry {
                 name: CrateName::new_unchecked(package.name),
                 version: package.version,
                 checksum,
                 source: package.source,
-            },
-        );
+            });
     }
+    let crates = Lockfile { packages };
 
     tracing::debug!(
         total_packages = crates.len(),
-        with_checksums = crates.values().filter(|e| e.checksum.is_some()).count(),
+        with_checksums = crates.iter().filter(|e| e.checksum.is_some()).count(),
         "Lockfile parsing complete"
     );
 
@@ -78,7 +122,7 @@ struct Package {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use assert2::check;
+    use assert2::{check, let_assert};
     use std::env;
     use std::path::PathBuf;
 
@@ -93,10 +137,38 @@ mod tests {
                 .expect("Failed to parse Cargo.lock");
             check!(!crates.is_empty());
 
-            check!(crates.contains_key("serde"));
-
-            let serde = &crates["serde"];
+            let_assert!(Some(serde) = crates.get("serde", None));
             check!(serde.checksum.is_some());
+            check!(crates.get("serde", Some(&serde.version)).is_some());
+            check!(crates.get("serde", Some("0.0.0")).is_none());
         }
     }
+
+    #[tokio::test]
+    async fn test_parse_cargo_lock_multiple_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock_path = dir.path().join("Cargo.lock");
+        std::fs::write(
+            &lock_path,
+            r#"version = 4
+
+[[package]]
+name = "syn"
+version = "1.0.109"
+
+[[package]]
+name = "syn"
+version = "2.0.87"
+"#,
+        )
+        .unwrap();
+
+        let_assert!(Ok(crates) = parse_cargo_lock(&lock_path).await);
+        check!(crates.len() == 2);
+        check!(crates.versions("syn").len() == 2);
+        let_assert!(Some(syn1) = crates.get("syn", Some("1.0.109")));
+        check!(syn1.version == "1.0.109");
+        let_assert!(Some(syn2) = crates.get("syn", Some("2.0.87")));
+        check!(syn2.version == "2.0.87");
+    }
 }
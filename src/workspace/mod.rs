@@ -6,13 +6,16 @@ pub mod lockfile;
 pub(crate) mod metadata;
 pub(crate) mod rustdoc;
 
-pub use context::{CrateMetadata, CrateOrigin, WorkspaceContext};
+pub use context::{CrateMetadata, CrateOrigin, MemberSources, WorkspaceContext};
 pub use detection::{
     find_cargo_toml_with_constraints, find_git_root, find_workspace_root, has_workspace_section,
-    is_boundary_directory, is_system_directory,
+    is_boundary_directory, is_system_directory, root_uri_to_path,
 };
 pub use rustdoc::generate_docs;
 
 // Internal re-exports
-pub(crate) use detection::{auto_detect_workspace, expand_tilde};
-pub(crate) use rustdoc::get_docs;
+pub(crate) use detection::{auto_detect_workspace, detect_workspace_from, expand_tilde};
+pub(crate) use rustdoc::{
+    DocTarget, FEATURES_SEPARATOR, current_digest, feature_doc_name, features_override, get_docs,
+    remove_docs,
+};
@@ -1,81 +1,198 @@
 //! Rustdoc JSON generation with digest-based caching.
 
+use super::context::{CrateMetadata, CrateOrigin, MemberSources, WorkspaceContext};
 use super::lockfile::parse_cargo_lock;
 use super::metadata::validate_version;
-use crate::cache::Hash;
+use crate::cache::{
+    CrateDigest, Hash, SharedCache, compute_dependency_digest, compute_workspace_digest,
+};
 use crate::error::Result;
+use crate::progress::{self, Progress};
+use crate::search::index_metrics;
 use crate::search::rustdoc::CrateIndex;
 use crate::types::CrateName;
 use anyhow::Context;
+use cargo_metadata::Message;
+use cargo_metadata::diagnostic::DiagnosticLevel;
 use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+
+/// A crate whose documentation should be loaded or generated.
+#[derive(Debug, Clone)]
+pub(crate) struct DocTarget {
+    pub crate_name: CrateName,
+    /// Resolved version (if known)
+    pub version: Option<String>,
+    pub is_workspace_member: bool,
+    /// Exact set of features to enable; `None` builds with the crate's default features.
+    pub features: Option<Vec<String>>,
+    /// `features` was requested instead of resolved by cargo: the docs are built in
+    /// their own target directory and stored under a [feature-qualified
+    /// name](feature_doc_name).
+    pub features_override: bool,
+    pub sources: Option<MemberSources>,
+    /// Store docs under `name@version`, for crates present in several versions.
+    pub versioned: bool,
+}
+
+impl DocTarget {
+    /// Describe a crate of `workspace` using its metadata.
+    pub(crate) fn new(meta: &CrateMetadata, workspace: &WorkspaceContext) -> Self {
+        Self {
+            crate_name: meta.name.clone(),
+            version: meta.version.clone(),
+            is_workspace_member: meta.origin == CrateOrigin::Local,
+            features: meta.features.clone(),
+            features_override: false,
+            sources: meta.sources.clone(),
+            versioned: workspace.has_multiple_versions(meta.name.normalized()),
+        }
+    }
+
+    /// Name the docs and digest are stored under: `name@version` when versioned,
+    /// feature-qualified for a features override.
+    pub(crate) fn doc_name(&self) -> CrateName {
+        let doc_name = match (&self.version, self.versioned) {
+            (Some(version), true) => {
+                CrateName::new_unchecked(format!("{}@{version}", self.crate_name))
+            }
+            _ => self.crate_name.clone(),
+        };
+        match &self.features {
+            Some(features) if self.features_override => feature_doc_name(&doc_name, features),
+            _ => doc_name,
+        }
+    }
+}
+
+/// The override enabling exactly `features` of the crate described by `meta`: the
+/// features sorted and deduplicated, or `None` if cargo resolves that set anyway.
+pub(crate) fn features_override(meta: &CrateMetadata, features: &[String]) -> Option<Vec<String>> {
+    let mut features = features.to_vec();
+    features.sort();
+    features.dedup();
+    (meta.features.as_ref() != Some(&features)).then_some(features)
+}
+
+/// Name the docs of `doc_name` built with exactly `features` are stored under:
+/// `doc_name+<hash of the features>`, so they don't replace the resolved build's.
+pub(crate) fn feature_doc_name(doc_name: &CrateName, features: &[String]) -> CrateName {
+    let hash = Hash::u64(xxhash_rust::xxh3::xxh3_64(features.join(",").as_bytes()));
+    CrateName::new_unchecked(format!("{doc_name}{FEATURES_SEPARATOR}{hash}"))
+}
+
+/// Separates a doc name from the hash of its overridden features.
+pub(crate) const FEATURES_SEPARATOR: char = '+';
+
+/// Directory under the target directory that builds with a features override use,
+/// so they neither clobber the resolved builds' output nor invalidate their artifacts.
+const FEATURE_BUILD_DIR: &str = "rustdoc-mcp-features";
+
+/// Serializes generation of docs stored under another name than the `{name}.json`
+/// cargo writes: two versions or feature sets of a crate can't be generated at once
+/// without clobbering each other.
+static RENAMED_GENERATION: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
 
 /// Loads or regenerates rustdoc JSON for a crate using digest-based caching.
-/// Regenerates documentation when source files change (workspace members) or when
-/// the dependency version/checksum changes (external dependencies).
+/// Regenerates documentation when source files change (workspace members), when
+/// the dependency version/checksum changes (external dependencies), or when a
+/// different feature set is requested.
+///
+/// Crates present in several versions keep their docs and digest per version, and
+/// builds with a features override per feature set. Docs and digests live under
+/// `target_dir/doc`.
+///
+/// Registry dependencies are first looked up in the [`SharedCache`], and docs
+/// generated for them are added to it, so each is documented once per machine.
 pub(crate) async fn get_docs(
-    crate_name: &CrateName,
-    version: Option<&str>,
+    target: &DocTarget,
     workspace_root: &Path,
-    is_workspace_member: bool,
+    target_dir: &Path,
     cargo_lock_path: Option<&Path>,
 ) -> Result<CrateIndex> {
-    use crate::cache::{
-        compute_dependency_digest, compute_workspace_digest, load_digest, save_digest,
-    };
+    use crate::cache::{load_digest, save_digest};
 
-    let doc_path = workspace_root
-        .join("target")
-        .join("doc")
-        .join(format!("{}.json", crate_name.normalized()));
-    let digest_path = workspace_root
-        .join("target")
-        .join("doc")
-        .join(".digests")
-        .join(format!("{}.digest.json", crate_name.normalized()));
-
-    // Compute current digest
-    let current_digest = if is_workspace_member {
-        compute_workspace_digest(crate_name.as_str(), workspace_root).await?
-    } else {
-        // For dependencies, get checksum from Cargo.lock
-        if let Some(lock_path) = cargo_lock_path {
-            let crates = parse_cargo_lock(lock_path).await?;
-            if let Some(pkg) = crates.get(crate_name.as_str()) {
-                let checksum = pkg.checksum.unwrap_or_else(|| {
-                    // Fallback for dependencies without checksums (e.g., path dependencies)
-                    Hash::sha256([0u8; 32])
-                });
-                compute_dependency_digest(crate_name.as_str(), &pkg.version, checksum).await?
-            } else {
-                // Dependency not in Cargo.lock, treat as workspace member
-                compute_workspace_digest(crate_name.as_str(), workspace_root).await?
-            }
-        } else {
-            // No Cargo.lock, treat as workspace member
-            compute_workspace_digest(crate_name.as_str(), workspace_root).await?
-        }
-    };
+    let crate_name = &target.crate_name;
+    let version = target.version.as_deref();
+    let features = target.features.as_deref();
+    let doc_name = target.doc_name();
+
+    let doc_dir = target_dir.join("doc");
+    let doc_path = doc_name.doc_json_path(&doc_dir);
+    let index_path = doc_name.index_path(&doc_dir);
+    let digest_path = doc_name.digest_path(&doc_dir);
+
+    let current_digest = current_digest(target, workspace_root, cargo_lock_path).await?;
 
     // Load saved digest
     let saved_digest = load_digest(&digest_path).await;
+    let shared_entry = SharedCache::global().entry(crate_name.as_str(), &current_digest);
 
     // Determine if regeneration is needed
     let needs_regen =
         !doc_path.exists() || saved_digest.is_none() || saved_digest.unwrap() != current_digest;
 
-    if needs_regen {
+    if needs_regen
+        && let Some(entry) = &shared_entry
+        && entry.restore(&doc_path, &index_path).await
+    {
+        save_digest(&digest_path, &current_digest).await?;
+        index_metrics::record_shared_hit();
+        tracing::info!(
+            crate_name = %crate_name,
+            doc_path = %doc_path.display(),
+            "Reusing documentation from the shared cache"
+        );
+    } else if needs_regen {
         tracing::info!(
             crate_name = %crate_name,
             version = version,
+            features = ?features,
             doc_path = %doc_path.display(),
             "Generating documentation"
         );
 
-        generate_docs(crate_name, version, workspace_root, is_workspace_member).await?;
+        let _guard = if target.versioned || target.features_override {
+            Some(RENAMED_GENERATION.lock().await)
+        } else {
+            None
+        };
+        let build_dir = if target.features_override {
+            target_dir.join(FEATURE_BUILD_DIR)
+        } else {
+            target_dir.to_path_buf()
+        };
+        let output_path = crate_name.doc_json_path(&build_dir.join("doc"));
+        // The output may be a hard link into the shared cache; unlink it so that
+        // rustdoc writes a new file instead of overwriting the cached one.
+        let _ = tokio::fs::remove_file(&output_path).await;
+        index_metrics::record_generation();
+        generate_docs(
+            crate_name,
+            version,
+            workspace_root,
+            &build_dir,
+            target.is_workspace_member,
+            features,
+        )
+        .await?;
+        if output_path != doc_path {
+            tokio::fs::rename(&output_path, &doc_path)
+                .await
+                .with_context(|| format!("Failed to store docs for '{doc_name}'"))?;
+        }
         save_digest(&digest_path, &current_digest).await?;
+        if let Some(entry) = &shared_entry {
+            entry.store_docs(&doc_path).await;
+        }
 
         tracing::info!(crate_name = %crate_name, "Documentation generated");
     } else {
+        // Docs may predate the shared cache; make sure it has them
+        if let Some(entry) = &shared_entry {
+            entry.store_docs(&doc_path).await;
+        }
         tracing::info!(
             crate_name = %crate_name,
             doc_path = %doc_path.display(),
@@ -85,7 +202,60 @@ pub(crate) async fn get_docs(
 
     CrateIndex::load_async(doc_path)
         .await
-        .with_context(|| format!("Failed to load rustdoc JSON for '{}'", crate_name))
+        .with_context(|| format!("Failed to load rustdoc JSON for '{}'", doc_name))
+}
+
+/// The digest `target`'s docs would be generated with now.
+///
+/// Workspace members hash their manifests and sources; dependencies use their
+/// Cargo.lock version and checksum. Dependencies missing from Cargo.lock are
+/// hashed like members.
+pub(crate) async fn current_digest(
+    target: &DocTarget,
+    workspace_root: &Path,
+    cargo_lock_path: Option<&Path>,
+) -> Result<CrateDigest> {
+    let crate_name = target.crate_name.as_str();
+    let features = target.features.as_deref();
+    let sources = target.sources.as_ref();
+
+    if !target.is_workspace_member
+        && let Some(lock_path) = cargo_lock_path
+    {
+        let crates = parse_cargo_lock(lock_path).await?;
+        if let Some(pkg) = crates.get(crate_name, target.version.as_deref()) {
+            // Fallback for dependencies without checksums (e.g., path dependencies)
+            let checksum = pkg.checksum.unwrap_or_else(|| Hash::sha256([0u8; 32]));
+            return compute_dependency_digest(crate_name, &pkg.version, checksum, features).await;
+        }
+    }
+    compute_workspace_digest(crate_name, workspace_root, sources, features).await
+}
+
+/// Delete `target`'s docs, search index and digest from the doc directory under
+/// `target_dir`, along with any of its entries in the [`SharedCache`], so that the
+/// next request runs rustdoc again. Returns the number of bytes freed.
+pub(crate) async fn remove_docs(target: &DocTarget, target_dir: &Path) -> u64 {
+    let doc_dir = target_dir.join("doc");
+    let doc_name = target.doc_name();
+    let mut freed = 0;
+    for path in [
+        doc_name.doc_json_path(&doc_dir),
+        doc_name.index_path(&doc_dir),
+        doc_name.digest_path(&doc_dir),
+    ] {
+        if let Ok(metadata) = tokio::fs::metadata(&path).await
+            && tokio::fs::remove_file(&path).await.is_ok()
+        {
+            freed += metadata.len();
+        }
+    }
+    if !target.is_workspace_member {
+        freed += SharedCache::global()
+            .remove_crate(target.crate_name.normalized(), target.version.as_deref())
+            .await;
+    }
+    freed
 }
 
 /// Invokes `cargo +nightly rustdoc` to generate JSON documentation.
@@ -95,34 +265,74 @@ pub(crate) async fn get_docs(
 /// For external dependencies, runs `cargo rustdoc --lib` from the crate's own registry
 /// source directory to avoid a nightly cargo feature resolver bug that panics when the
 /// target package is only a dev-dependency of the workspace.
+///
+/// With `Some(features)`, exactly those features are enabled (`--no-default-features
+/// --features ...`); with `None`, cargo's defaults apply.
+///
+/// `target_dir` is the cargo target directory to build in, pointed at with `--target-dir`.
 pub async fn generate_docs(
     crate_name: &CrateName,
     version: Option<&str>,
     workspace_root: &Path,
+    target_dir: &Path,
     is_workspace_member: bool,
+    features: Option<&[String]>,
 ) -> Result<()> {
     // Validate version to prevent command injection (crate_name already validated)
     if let Some(ver) = version {
         validate_version(ver)?;
     }
+    let feature_args = feature_args(features)?;
 
     if is_workspace_member {
-        generate_docs_workspace_member(crate_name, version, workspace_root).await
+        generate_docs_workspace_member(
+            crate_name,
+            version,
+            workspace_root,
+            target_dir,
+            &feature_args,
+        )
+        .await
     } else {
         // For external packages, find the source dir and run from there to avoid a
         // nightly cargo panic: the feature resolver fails with "did not find features
         // for (pkg, NormalOrDev)" when the target package is only a dev-dependency.
         let source_dir = find_registry_source_dir(crate_name, version, workspace_root).await?;
-        let target_dir = workspace_root.join("target");
-        generate_docs_from_source(crate_name, &source_dir, &target_dir).await
+        generate_docs_from_source(crate_name, &source_dir, target_dir, &feature_args).await
     }
 }
 
+/// Cargo arguments selecting exactly `features`, or none for the defaults.
+fn feature_args(features: Option<&[String]>) -> Result<Vec<String>> {
+    let Some(features) = features else {
+        return Ok(Vec::new());
+    };
+
+    // Features end up on the cargo command line; only allow what Cargo.toml can declare.
+    if let Some(invalid) = features.iter().find(|feature| {
+        feature.is_empty()
+            || !feature
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '+' | '.' | '/'))
+    }) {
+        anyhow::bail!("Invalid feature name '{invalid}'");
+    }
+
+    let mut args = vec!["--no-default-features".to_string()];
+    if !features.is_empty() {
+        args.push("--features".to_string());
+        args.push(features.join(","));
+    }
+    Ok(args)
+}
+
 /// Runs `cargo rustdoc --package X` for a workspace member crate.
 async fn generate_docs_workspace_member(
     crate_name: &CrateName,
     version: Option<&str>,
     workspace_root: &Path,
+    target_dir: &Path,
+    feature_args: &[String],
 ) -> Result<()> {
     // Cargo requires the original hyphenated package name (e.g. "tracing-attributes"),
     // not the underscore-normalized form. Look it up from Cargo.lock if available.
@@ -130,7 +340,7 @@ async fn generate_docs_workspace_member(
         let lock_path = workspace_root.join("Cargo.lock");
         if lock_path.exists() {
             if let Ok(crates) = parse_cargo_lock(&lock_path).await {
-                crates.get(crate_name.normalized()).map_or_else(
+                crates.get(crate_name.normalized(), version).map_or_else(
                     || crate_name.as_str().to_string(),
                     |pkg| pkg.name.as_str().to_string(),
                 )
@@ -148,27 +358,20 @@ async fn generate_docs_workspace_member(
         canonical_name.clone()
     };
 
-    let output = tokio::process::Command::new("cargo")
-        .current_dir(workspace_root)
-        .arg("+nightly")
-        .arg("rustdoc")
-        .arg("--package")
-        .arg(&package_spec)
-        .arg("--lib")
-        .arg("--")
-        .arg("-Z")
-        .arg("unstable-options")
-        .arg("--output-format")
-        .arg("json")
-        .output()
-        .await
-        .context("Failed to execute cargo rustdoc command")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut cargo_args = vec![
+        "--package".to_string(),
+        package_spec.clone(),
+        "--lib".to_string(),
+        "--target-dir".to_string(),
+        target_dir.display().to_string(),
+    ];
+    cargo_args.extend_from_slice(feature_args);
+    let output = run_rustdoc(workspace_root, &cargo_args, crate_name).await?;
+
+    if !output.success {
         tracing::error!(
             package = %package_spec,
-            stderr = %stderr,
+            errors = %output.errors,
             "Documentation generation failed"
         );
         tracing::error!(
@@ -188,29 +391,21 @@ async fn generate_docs_from_source(
     crate_name: &CrateName,
     source_dir: &Path,
     target_dir: &Path,
+    feature_args: &[String],
 ) -> Result<()> {
-    let output = tokio::process::Command::new("cargo")
-        .current_dir(source_dir)
-        .arg("+nightly")
-        .arg("rustdoc")
-        .arg("--lib")
-        .arg("--target-dir")
-        .arg(target_dir)
-        .arg("--")
-        .arg("-Z")
-        .arg("unstable-options")
-        .arg("--output-format")
-        .arg("json")
-        .output()
-        .await
-        .context("Failed to execute cargo rustdoc command")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut cargo_args = vec![
+        "--lib".to_string(),
+        "--target-dir".to_string(),
+        target_dir.display().to_string(),
+    ];
+    cargo_args.extend_from_slice(feature_args);
+    let output = run_rustdoc(source_dir, &cargo_args, crate_name).await?;
+
+    if !output.success {
         tracing::error!(
             crate_name = %crate_name,
             source_dir = %source_dir.display(),
-            stderr = %stderr,
+            errors = %output.errors,
             "Documentation generation failed"
         );
         tracing::error!(
@@ -222,6 +417,119 @@ async fn generate_docs_from_source(
     Ok(())
 }
 
+/// Outcome of a `cargo rustdoc` run.
+struct RustdocOutput {
+    success: bool,
+    /// Rendered compiler errors, or cargo's own stderr when there were none.
+    errors: String,
+}
+
+/// Runs `cargo +nightly rustdoc <cargo_args>` with JSON output, reporting each
+/// finished compilation unit as [`Progress::Compiling`].
+///
+/// When someone observes the progress, the unit total comes from a quick
+/// `--unit-graph` run of the same command; otherwise that run is skipped.
+async fn run_rustdoc(
+    dir: &Path,
+    cargo_args: &[String],
+    crate_name: &CrateName,
+) -> Result<RustdocOutput> {
+    let total = if progress::is_observed() {
+        count_units(dir, cargo_args).await
+    } else {
+        None
+    };
+
+    let mut child = tokio::process::Command::new("cargo")
+        .current_dir(dir)
+        .arg("+nightly")
+        .arg("rustdoc")
+        .args(cargo_args)
+        .arg("--message-format=json")
+        .arg("--")
+        .arg("-Z")
+        .arg("unstable-options")
+        .arg("--output-format")
+        .arg("json")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to execute cargo rustdoc command")?;
+
+    // Drain stderr concurrently so cargo never blocks on a full pipe
+    let mut stderr = child.stderr.take().context("cargo stderr not captured")?;
+    let stderr_task = tokio::spawn(async move {
+        let mut text = String::new();
+        let _ = stderr.read_to_string(&mut text).await;
+        text
+    });
+
+    let stdout = child.stdout.take().context("cargo stdout not captured")?;
+    let mut lines = BufReader::new(stdout).lines();
+    let mut done = 0;
+    let mut errors = Vec::new();
+    while let Some(line) = lines.next_line().await? {
+        match serde_json::from_str::<Message>(&line) {
+            Ok(Message::CompilerArtifact(_) | Message::BuildScriptExecuted(_)) => {
+                done += 1;
+                progress::report(&Progress::Compiling {
+                    crate_name: crate_name.to_string(),
+                    done,
+                    total,
+                });
+            }
+            Ok(Message::CompilerMessage(message))
+                if message.message.level == DiagnosticLevel::Error =>
+            {
+                errors.push(message.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    let status = child
+        .wait()
+        .await
+        .context("Failed to wait for cargo rustdoc")?;
+    let stderr = stderr_task.await.unwrap_or_default();
+    Ok(RustdocOutput {
+        success: status.success(),
+        errors: if errors.is_empty() {
+            stderr
+        } else {
+            errors.concat()
+        },
+    })
+}
+
+/// Number of compilation units `cargo rustdoc <cargo_args>` builds, or `None` if
+/// cargo can't tell.
+async fn count_units(dir: &Path, cargo_args: &[String]) -> Option<usize> {
+    #[derive(serde::Deserialize)]
+    struct UnitGraph {
+        units: Vec<serde::de::IgnoredAny>,
+    }
+
+    let output = tokio::process::Command::new("cargo")
+        .current_dir(dir)
+        .arg("+nightly")
+        .arg("rustdoc")
+        .args(cargo_args)
+        .args(["--unit-graph", "-Z", "unstable-options"])
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        tracing::debug!(
+            stderr = %String::from_utf8_lossy(&output.stderr),
+            "Could not compute unit graph"
+        );
+        return None;
+    }
+    let graph: UnitGraph = serde_json::from_slice(&output.stdout).ok()?;
+    Some(graph.units.len())
+}
+
 /// Locates the unpacked source directory of a registry crate via `cargo metadata`.
 ///
 /// Returns the parent directory of the crate's `Cargo.toml` in the cargo registry cache.
@@ -293,3 +601,52 @@ async fn find_registry_source_dir(
         crate_name.as_str()
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+
+    #[test]
+    fn feature_args_default_features() {
+        let_assert!(Ok(args) = feature_args(None));
+        check!(args.is_empty());
+    }
+
+    #[test]
+    fn feature_args_exact_set() {
+        let features = ["derive".to_string(), "std".to_string()];
+        let_assert!(Ok(args) = feature_args(Some(&features)));
+        check!(args == ["--no-default-features", "--features", "derive,std"]);
+
+        let_assert!(Ok(args) = feature_args(Some(&[])));
+        check!(args == ["--no-default-features"]);
+    }
+
+    #[test]
+    fn feature_args_rejects_injection() {
+        check!(feature_args(Some(&["std --offline".to_string()])).is_err());
+        check!(feature_args(Some(&[String::new()])).is_err());
+    }
+
+    #[test]
+    fn features_override_stored_apart() {
+        let mut target = DocTarget {
+            crate_name: CrateName::new_unchecked("serde"),
+            version: Some("1.0.0".to_string()),
+            is_workspace_member: false,
+            features: Some(vec!["std".to_string()]),
+            features_override: false,
+            sources: None,
+            versioned: false,
+        };
+        check!(target.doc_name().as_str() == "serde");
+
+        target.features_override = true;
+        let std_only = target.doc_name();
+        check!(std_only.as_str().starts_with("serde+"));
+
+        target.features = Some(vec!["derive".to_string(), "std".to_string()]);
+        check!(target.doc_name() != std_only);
+    }
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use assert2::{check, let_assert};
+use common::{IsolatedWorkspace, isolated_workspace};
+use rstest::rstest;
+use rustdoc_mcp::WorkspaceContext;
+use rustdoc_mcp::tools::cache::{
+    CacheAction, CacheRequest, Freshness, StructuredCacheResult, handle_cache,
+    handle_cache_structured,
+};
+use std::collections::HashMap;
+
+fn request(action: CacheAction, crate_name: Option<&str>) -> CacheRequest {
+    CacheRequest {
+        action,
+        crate_name: crate_name.map(str::to_string),
+        ..CacheRequest::default()
+    }
+}
+
+/// Test: Stats list every workspace crate with its docs on disk.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn stats_list_workspace_crates(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Ok(StructuredCacheResult::Stats(stats)) =
+            handle_cache_structured(&isolated_workspace.state, request(CacheAction::Stats, None))
+                .await
+    );
+
+    let_assert!(Some(entry) = stats.crates.iter().find(|c| c.name == "rustdoc-mcp"));
+    check!(entry.doc_bytes > 0);
+    check!(entry.freshness != Freshness::Missing);
+    check!(!entry.in_memory);
+    check!(stats.workspace_bytes >= entry.doc_bytes);
+}
+
+/// Test: Clearing a crate deletes its rustdoc JSON and reports the freed space.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn clear_removes_crate_docs(isolated_workspace: IsolatedWorkspace) {
+    let doc_json = isolated_workspace
+        .root()
+        .join("target/doc/rustdoc_mcp.json");
+    check!(doc_json.exists());
+
+    let_assert!(
+        Ok(StructuredCacheResult::Cleared {
+            crates,
+            freed_bytes,
+        }) = handle_cache_structured(
+            &isolated_workspace.state,
+            request(CacheAction::Clear, Some("rustdoc-mcp"))
+        )
+        .await
+    );
+    check!(crates == ["rustdoc-mcp"]);
+    check!(freed_bytes > 0);
+    check!(!doc_json.exists());
+}
+
+/// Test: Clearing a crate outside the workspace is an error.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn clear_rejects_unknown_crate(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Err(message) = handle_cache(
+            &isolated_workspace.state,
+            request(CacheAction::Clear, Some("no-such-crate"))
+        )
+        .await
+    );
+    check!(message.contains("no-such-crate"), "{message}");
+}
+
+/// Test: Stats report on the workspace a request names, even if another is active.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn stats_report_on_named_workspace(isolated_workspace: IsolatedWorkspace) {
+    let state = &isolated_workspace.state;
+    let other = tempfile::tempdir().unwrap();
+    let other_root = other.path().to_path_buf();
+    state
+        .set_workspace(
+            other_root.clone(),
+            WorkspaceContext {
+                root: other_root.clone(),
+                members: vec![],
+                crate_info: HashMap::new(),
+                root_crate: None,
+                target_dir: other_root.join("target"),
+            },
+            None,
+        )
+        .await;
+
+    let root = isolated_workspace.root().display().to_string();
+    let request = CacheRequest {
+        workspace: Some(root.clone()),
+        ..request(CacheAction::Stats, None)
+    };
+    let_assert!(
+        Ok(StructuredCacheResult::Stats(stats)) = handle_cache_structured(state, request).await
+    );
+    check!(stats.workspace.as_deref() == Some(root.as_str()));
+    check!(stats.crates.iter().any(|c| c.name == "rustdoc-mcp"));
+}
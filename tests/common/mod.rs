@@ -11,6 +11,7 @@
 //!
 //! - `isolated_workspace`: Creates a fully isolated test environment (recommended)
 //! - `isolated_workspace_with_serde`: Includes serde and serde_json dependencies
+//! - `isolated_workspace_with_anyhow_versions`: Two versions of anyhow side by side
 //! - `shared_context`: Uses the real `target/doc/` directory (for specific warm-cache tests)
 //!
 //! # Testing Warm Cache Behavior
@@ -30,7 +31,7 @@
 
 use rstest::fixture;
 use rustdoc_mcp::tools::search::{SearchRequest, handle_search};
-use rustdoc_mcp::{CrateMetadata, CrateName, CrateOrigin, DocState, WorkspaceContext};
+use rustdoc_mcp::{CrateKey, CrateMetadata, CrateName, CrateOrigin, DocState, WorkspaceContext};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -231,54 +232,123 @@ impl IsolatedWorkspace {
             }
         }
 
-        // Note: .digests/ directory is NOT copied because:
-        // - JSON files are copied, so doc_path.exists() returns true
-        // - Digest validation only runs when docs need regeneration
-        // - Since JSON exists, get_docs() is never called in tests
-
-        // Copy Cargo.toml from real project (for WorkspaceContext)
-        let source_cargo = project.join("Cargo.toml");
-        workspace.copy_file(&source_cargo, "Cargo.toml");
-
-        // Copy Cargo.lock from real project
-        let source_lock = project.join("Cargo.lock");
-        if source_lock.exists() {
-            workspace.copy_file(&source_lock, "Cargo.lock");
-        }
-
         // Build crate_info for all requested crates
         let mut crate_info = HashMap::new();
 
         for crate_name in crates {
             let is_local = *crate_name == "rustdoc-mcp";
+            let version = if is_local { "0.2.0" } else { "1.0" };
             crate_info.insert(
-                CrateName::new_unchecked(*crate_name),
+                CrateKey::new(*crate_name, Some(version)),
                 CrateMetadata {
                     origin: if is_local {
                         CrateOrigin::Local
                     } else {
                         CrateOrigin::External
                     },
-                    version: Some(if is_local {
-                        "0.2.0".to_string()
-                    } else {
-                        "1.0".to_string()
-                    }),
+                    version: Some(version.to_string()),
                     description: None,
                     dev_dep: false,
                     name: CrateName::new_unchecked(*crate_name),
                     is_root_crate: is_local,
                     used_by: vec![],
+                    features: None,
+                    sources: None,
+                },
+            );
+        }
+
+        Self::from_crate_info(workspace, crate_info)
+    }
+
+    /// Creates an isolated workspace where `crate_name` is present in several versions.
+    ///
+    /// Each `(version, used_by)` entry gets its own copy of the crate's rustdoc JSON,
+    /// stored as `target/doc/{crate}@{version}.json` like generated versioned docs.
+    pub(crate) fn with_versions(crate_name: &str, versions: &[(&str, &[&str])]) -> Self {
+        init_tracing();
+        let workspace = TempWorkspace::new();
+        workspace.create_dir("target/doc");
+
+        let source_doc_dir = project_root().join("target/doc");
+        let normalized = CrateName::normalize(crate_name);
+        workspace.copy_file(
+            &source_doc_dir.join("rustdoc_mcp.json"),
+            "target/doc/rustdoc_mcp.json",
+        );
+
+        let mut crate_info = HashMap::new();
+        crate_info.insert(
+            CrateKey::new("rustdoc-mcp", Some("0.2.0")),
+            CrateMetadata {
+                origin: CrateOrigin::Local,
+                version: Some("0.2.0".to_string()),
+                description: None,
+                dev_dep: false,
+                name: CrateName::new_unchecked("rustdoc-mcp"),
+                is_root_crate: true,
+                used_by: vec![],
+                features: None,
+                sources: None,
+            },
+        );
+
+        for (version, used_by) in versions {
+            workspace.copy_file(
+                &source_doc_dir.join(format!("{normalized}.json")),
+                &format!("target/doc/{normalized}@{version}.json"),
+            );
+            crate_info.insert(
+                CrateKey::new(crate_name, Some(version)),
+                CrateMetadata {
+                    origin: CrateOrigin::External,
+                    version: Some((*version).to_string()),
+                    description: None,
+                    dev_dep: false,
+                    name: CrateName::new_unchecked(crate_name),
+                    is_root_crate: false,
+                    used_by: used_by
+                        .iter()
+                        .map(|member| CrateName::new_unchecked(*member))
+                        .collect(),
+                    features: None,
+                    sources: None,
                 },
             );
         }
 
+        Self::from_crate_info(workspace, crate_info)
+    }
+
+    /// Registers `crate_info` as the workspace of a fresh `DocState`.
+    fn from_crate_info(
+        workspace: TempWorkspace,
+        crate_info: HashMap<CrateKey, CrateMetadata>,
+    ) -> Self {
+        let project = project_root();
+
+        // Note: .digests/ directory is NOT copied because:
+        // - JSON files are copied, so doc_path.exists() returns true
+        // - Digest validation only runs when docs need regeneration
+        // - Since JSON exists, get_docs() is never called in tests
+
+        // Copy Cargo.toml from real project (for WorkspaceContext)
+        let source_cargo = project.join("Cargo.toml");
+        workspace.copy_file(&source_cargo, "Cargo.toml");
+
+        // Copy Cargo.lock from real project
+        let source_lock = project.join("Cargo.lock");
+        if source_lock.exists() {
+            workspace.copy_file(&source_lock, "Cargo.lock");
+        }
+
         let root = workspace.path().to_path_buf();
         let metadata = WorkspaceContext {
             root: root.clone(),
             members: vec![CrateName::new_unchecked("rustdoc-mcp")],
             crate_info,
             root_crate: Some(CrateName::new_unchecked("rustdoc-mcp")),
+            target_dir: root.join("target"),
         };
 
         let state = Arc::new(DocState::new(None));
@@ -349,6 +419,15 @@ pub(crate) fn isolated_workspace_with_anyhow() -> IsolatedWorkspace {
     IsolatedWorkspace::with_deps(&["rustdoc-mcp", "anyhow"])
 }
 
+/// Creates an isolated workspace with two versions of anyhow, used by different members.
+#[fixture]
+pub(crate) fn isolated_workspace_with_anyhow_versions() -> IsolatedWorkspace {
+    IsolatedWorkspace::with_versions(
+        "anyhow",
+        &[("1.0.98", &["rustdoc-mcp"]), ("0.9.0", &["legacy-tool"])],
+    )
+}
+
 /// Creates an `Arc<DocState>` using the real project directory (shared state).
 ///
 /// **Use with caution!** This fixture uses the actual `target/doc/` directory,
@@ -368,7 +447,7 @@ pub(crate) fn shared_state() -> Arc<DocState> {
 
     // The local crate
     crate_info.insert(
-        CrateName::new_unchecked("rustdoc-mcp"),
+        CrateKey::new("rustdoc-mcp", Some("0.2.0")),
         CrateMetadata {
             origin: CrateOrigin::Local,
             version: Some("0.2.0".to_string()),
@@ -377,12 +456,14 @@ pub(crate) fn shared_state() -> Arc<DocState> {
             name: CrateName::new_unchecked("rustdoc-mcp"),
             is_root_crate: true,
             used_by: vec![],
+            features: None,
+            sources: None,
         },
     );
 
     // External dependencies we test against
     crate_info.insert(
-        CrateName::new_unchecked("serde"),
+        CrateKey::new("serde", Some("1.0")),
         CrateMetadata {
             origin: CrateOrigin::External,
             version: Some("1.0".to_string()),
@@ -391,11 +472,13 @@ pub(crate) fn shared_state() -> Arc<DocState> {
             name: CrateName::new_unchecked("serde"),
             is_root_crate: false,
             used_by: vec![],
+            features: None,
+            sources: None,
         },
     );
 
     crate_info.insert(
-        CrateName::new_unchecked("serde_json"),
+        CrateKey::new("serde_json", Some("1.0")),
         CrateMetadata {
             origin: CrateOrigin::External,
             version: Some("1.0".to_string()),
@@ -404,6 +487,8 @@ pub(crate) fn shared_state() -> Arc<DocState> {
             name: CrateName::new_unchecked("serde_json"),
             is_root_crate: false,
             used_by: vec![],
+            features: None,
+            sources: None,
         },
     );
 
@@ -412,6 +497,7 @@ pub(crate) fn shared_state() -> Arc<DocState> {
         members: vec![CrateName::new_unchecked("rustdoc-mcp")],
         crate_info,
         root_crate: Some(CrateName::new_unchecked("rustdoc-mcp")),
+        target_dir: project_root.join("target"),
     };
 
     let state = Arc::new(DocState::new(None));
@@ -465,8 +551,9 @@ pub(crate) async fn warm_cache(state: &Arc<DocState>, crates: &[&str]) {
             state,
             SearchRequest {
                 query: "_warmup_".to_string(),
-                crate_name: crate_name.to_string(),
+                crate_name: Some(crate_name.to_string()),
                 limit: 1,
+                ..Default::default()
             },
         )
         .await;
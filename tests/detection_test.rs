@@ -2,11 +2,12 @@ mod common;
 
 use assert2::{check, let_assert};
 use common::TempWorkspace;
+use rstest::rstest;
 use rustdoc_mcp::workspace::{
     find_cargo_toml_with_constraints, find_git_root, find_workspace_root, has_workspace_section,
-    is_boundary_directory, is_system_directory,
+    is_boundary_directory, is_system_directory, root_uri_to_path,
 };
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[test]
 fn test_find_cargo_toml_in_current_directory() {
@@ -297,3 +298,15 @@ fn test_multiple_cargo_toml_finds_nearest() {
     let_assert!(Some(found) = result);
     check!(found == workspace.path().join("nested/Cargo.toml"));
 }
+
+#[cfg(unix)]
+#[rstest]
+#[case::plain("file:///home/me/project", Some("/home/me/project"))]
+#[case::localhost("file://localhost/home/me/project", Some("/home/me/project"))]
+#[case::percent_encoded("file:///home/me/my%20project", Some("/home/me/my project"))]
+#[case::remote_host("file://server/share/project", None)]
+#[case::other_scheme("https://example.com/project", None)]
+#[case::truncated_escape("file:///home/me/bad%2", None)]
+fn test_root_uri_to_path(#[case] uri: &str, #[case] expected: Option<&str>) {
+    check!(root_uri_to_path(uri) == expected.map(PathBuf::from));
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use assert2::{check, let_assert};
+use common::{IsolatedWorkspace, isolated_workspace, isolated_workspace_with_serde};
+use rstest::rstest;
+use rustdoc_mcp::tools::find_implementors::{
+    FindImplementorsRequest, handle_find_implementors, handle_find_implementors_structured,
+};
+
+fn request(trait_path: &str) -> FindImplementorsRequest {
+    FindImplementorsRequest {
+        trait_path: trait_path.to_string(),
+        limit: 500,
+        workspace: None,
+    }
+}
+
+/// `serde::Serialize` resolves through the serde_core re-export and finds
+/// implementations both in the workspace crate and in serde_core itself.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn finds_serialize_implementors_across_crates(
+    isolated_workspace_with_serde: IsolatedWorkspace,
+) {
+    let_assert!(
+        Ok(result) = handle_find_implementors_structured(
+            &isolated_workspace_with_serde.state,
+            request("serde::Serialize"),
+        )
+        .await
+    );
+
+    check!(result.matched_traits == ["serde_core::ser::Serialize"]);
+
+    let local = result
+        .implementations
+        .entries
+        .iter()
+        .filter(|e| e.crate_name == "rustdoc_mcp")
+        .map(|e| e.for_type.as_str())
+        .collect::<Vec<_>>();
+    check!(local.iter().any(|ty| ty.ends_with("ItemKind")), "{local:?}");
+    check!(
+        local.iter().any(|ty| ty.ends_with("DetailLevel")),
+        "{local:?}"
+    );
+
+    check!(
+        result
+            .implementations
+            .entries
+            .iter()
+            .any(|e| e.crate_name == "serde_core" && e.for_type == "bool")
+    );
+}
+
+/// Impls over a bare type parameter (e.g. `impl<T: Serialize> Serialize for &T`)
+/// are grouped as blanket impls, not mixed in with concrete implementations.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn groups_blanket_impls_separately(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let_assert!(
+        Ok(result) = handle_find_implementors_structured(
+            &isolated_workspace_with_serde.state,
+            request("serde::Serialize"),
+        )
+        .await
+    );
+
+    let_assert!(
+        Some(reference) = result
+            .blanket
+            .entries
+            .iter()
+            .find(|e| e.for_type == "&'a T")
+    );
+    check!(!reference.generics.is_empty());
+    check!(reference.declaration.starts_with("impl<"));
+
+    check!(
+        result
+            .implementations
+            .entries
+            .iter()
+            .all(|e| e.for_type != "&'a T" && e.for_type != "&'a mut T")
+    );
+}
+
+/// Auto traits only appear through compiler-synthesized impls, which land in
+/// their own group.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn groups_synthetic_auto_trait_impls(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Ok(result) =
+            handle_find_implementors_structured(&isolated_workspace.state, request("Send")).await
+    );
+
+    check!(result.auto.total > 0);
+    check!(
+        result
+            .auto
+            .entries
+            .iter()
+            .any(|e| e.crate_name == "rustdoc_mcp" && e.for_type.ends_with("QueryContext"))
+    );
+}
+
+/// Asking for implementors of something that isn't a trait is an error.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn rejects_non_trait_paths(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Err(msg) =
+            handle_find_implementors(&isolated_workspace.state, request("rustdoc_mcp::DocState"),)
+                .await
+    );
+    check!(msg.contains("not a trait"));
+}
+
+/// Unknown traits render a friendly "nothing found" message instead of an error.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn unknown_trait_reports_no_implementations(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Ok(output) = handle_find_implementors(
+            &isolated_workspace.state,
+            request("DefinitelyNotARealTrait"),
+        )
+        .await
+    );
+    check!(output.contains("No implementations of 'DefinitelyNotARealTrait'"));
+}
+
+/// The rendered output labels each group and truncates to the limit.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn rendered_output_respects_limit(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let request = FindImplementorsRequest {
+        trait_path: "serde::Serialize".to_string(),
+        limit: 2,
+        workspace: None,
+    };
+    let_assert!(
+        Ok(output) = handle_find_implementors(&isolated_workspace_with_serde.state, request).await
+    );
+
+    check!(output.contains("Implementations of 'serde::Serialize'"));
+    check!(output.contains("Blanket implementations"));
+    check!(output.contains("more"));
+}
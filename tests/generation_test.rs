@@ -15,7 +15,7 @@ async fn lockfile_lookup_returns_original_hyphenated_name() {
     let_assert!(Ok(crates) = parse_cargo_lock(&lock_path).await);
 
     // Lookup with underscores should find the entry
-    let entry = crates.get("tracing_attributes");
+    let entry = crates.get("tracing_attributes", None);
     let_assert!(Some(entry) = entry);
 
     // Entry should have original hyphenated name
@@ -35,24 +35,83 @@ async fn generate_docs_with_hyphenated_name() {
     use rustdoc_mcp::workspace::generate_docs;
 
     let workspace_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = workspace_root.join("target");
     let name = CrateName::new_unchecked("tracing-attributes");
 
     assert!(
-        (generate_docs(&name, Some("0.1.30"), &workspace_root, false).await).is_ok(),
+        (generate_docs(
+            &name,
+            Some("0.1.30"),
+            &workspace_root,
+            &target_dir,
+            false,
+            None
+        )
+        .await)
+            .is_ok(),
         "Should succeed"
     );
 }
 
+#[tokio::test]
+async fn generate_docs_reports_compiled_units() {
+    use rustdoc_mcp::progress::{self, Progress, Reporter};
+    use rustdoc_mcp::workspace::generate_docs;
+    use std::sync::{Arc, Mutex};
+
+    let workspace_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = workspace_root.join("target");
+    let name = CrateName::new_unchecked("tracing-attributes");
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    let reporter: Reporter = Arc::new(move |progress| sink.lock().unwrap().push(progress.clone()));
+
+    let result = progress::scope(
+        reporter,
+        generate_docs(
+            &name,
+            Some("0.1.30"),
+            &workspace_root,
+            &target_dir,
+            false,
+            None,
+        ),
+    )
+    .await;
+    check!(result.is_ok());
+
+    // Every unit of the graph is reported, fresh ones included
+    let last = seen.lock().unwrap().last().cloned();
+    let_assert!(
+        Some(Progress::Compiling {
+            done,
+            total: Some(total),
+            ..
+        }) = last
+    );
+    check!(done == total);
+}
+
 #[tokio::test]
 async fn generate_docs_with_normalized_name() {
     use rustdoc_mcp::workspace::generate_docs;
 
     let workspace_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = workspace_root.join("target");
     let name = CrateName::new_unchecked("tracing_attributes");
 
     // Should succeed by looking up "tracing-attributes" from Cargo.lock
     assert!(
-        (generate_docs(&name, Some("0.1.30"), &workspace_root, false).await).is_ok(),
+        (generate_docs(
+            &name,
+            Some("0.1.30"),
+            &workspace_root,
+            &target_dir,
+            false,
+            None
+        )
+        .await)
+            .is_ok(),
         "Should work with normalized name"
     );
 }
@@ -114,6 +173,7 @@ fn query_context_has_negative_cache_api() {
         members: vec![],
         crate_info: HashMap::new(),
         root_crate: None,
+        target_dir: PathBuf::from("/tmp/target"),
     });
 
     let query_ctx = QueryContext::new(workspace);
@@ -143,6 +203,7 @@ async fn load_crate_returns_consistent_errors() {
         members: vec![CrateName::new_unchecked("test")],
         crate_info: HashMap::new(),
         root_crate: Some(CrateName::new_unchecked("test")),
+        target_dir: temp.path().join("target"),
     });
 
     let ctx = QueryContext::new(workspace);
@@ -163,7 +224,7 @@ async fn load_crate_returns_consistent_errors() {
 #[rstest]
 #[tokio::test(flavor = "multi_thread")]
 async fn repeated_load_does_not_retry_generation() {
-    use rustdoc_mcp::{CrateMetadata, CrateOrigin, QueryContext, WorkspaceContext};
+    use rustdoc_mcp::{CrateKey, CrateMetadata, CrateOrigin, QueryContext, WorkspaceContext};
     use std::collections::HashMap;
     use std::sync::Arc;
 
@@ -177,7 +238,7 @@ async fn repeated_load_does_not_retry_generation() {
 
     let mut crate_info = HashMap::new();
     crate_info.insert(
-        CrateName::new_unchecked("fake_dep"),
+        CrateKey::new("fake_dep", Some("1.0.0")),
         CrateMetadata {
             origin: CrateOrigin::External,
             version: Some("1.0.0".to_string()),
@@ -186,6 +247,8 @@ async fn repeated_load_does_not_retry_generation() {
             name: CrateName::new_unchecked("fake_dep"),
             is_root_crate: false,
             used_by: vec![],
+            features: None,
+            sources: None,
         },
     );
 
@@ -194,6 +257,7 @@ async fn repeated_load_does_not_retry_generation() {
         members: vec![CrateName::new_unchecked("test")],
         crate_info,
         root_crate: Some(CrateName::new_unchecked("test")),
+        target_dir: temp.path().join("target"),
     });
 
     let ctx = QueryContext::new(workspace);
@@ -220,8 +284,9 @@ async fn cross_crate_resolution_completes_without_hanging() {
 
     let request = SearchRequest {
         query: "instrument".to_string(),
-        crate_name: "tracing".to_string(),
+        crate_name: Some("tracing".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     // Should complete without infinite loop
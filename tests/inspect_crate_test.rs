@@ -4,7 +4,10 @@ use assert2::{check, let_assert};
 use common::{IsolatedWorkspace, isolated_workspace, isolated_workspace_with_serde};
 use rstest::rstest;
 use rustdoc_mcp::DetailLevel;
-use rustdoc_mcp::tools::inspect_crate::{InspectCrateRequest, handle_inspect_crate};
+use rustdoc_mcp::tools::inspect_crate::{
+    InspectCrateRequest, StructuredCrateResult, handle_inspect_crate,
+    handle_inspect_crate_structured,
+};
 
 // --- Summary Mode Tests (no crate_name) ---
 
@@ -15,6 +18,7 @@ async fn inspect_crate_summary_lists_local(isolated_workspace: IsolatedWorkspace
     let request = InspectCrateRequest {
         crate_name: None,
         detail_level: DetailLevel::Medium,
+        workspace: None,
     };
 
     let_assert!(
@@ -35,6 +39,7 @@ async fn inspect_crate_summary_lists_deps(isolated_workspace_with_serde: Isolate
     let request = InspectCrateRequest {
         crate_name: None,
         detail_level: DetailLevel::Medium,
+        workspace: None,
     };
 
     let_assert!(
@@ -57,6 +62,7 @@ async fn inspect_crate_shows_modules(isolated_workspace: IsolatedWorkspace) {
         crate_name: Some("rustdoc-mcp".to_string()),
         // Use High to ensure all modules are shown (Medium limits to 10, but we have 11)
         detail_level: DetailLevel::High,
+        workspace: None,
     };
 
     let_assert!(
@@ -93,6 +99,7 @@ async fn inspect_crate_shows_exports(isolated_workspace: IsolatedWorkspace) {
     let request = InspectCrateRequest {
         crate_name: Some("rustdoc-mcp".to_string()),
         detail_level: DetailLevel::High,
+        workspace: None,
     };
 
     let_assert!(Ok(output) = handle_inspect_crate(&isolated_workspace.state, request).await);
@@ -111,6 +118,7 @@ async fn inspect_crate_shows_item_counts(isolated_workspace: IsolatedWorkspace)
     let request = InspectCrateRequest {
         crate_name: Some("rustdoc-mcp".to_string()),
         detail_level: DetailLevel::Low,
+        workspace: None,
     };
 
     let_assert!(Ok(output) = handle_inspect_crate(&isolated_workspace.state, request).await);
@@ -134,6 +142,7 @@ async fn inspect_crate_external_dep(isolated_workspace_with_serde: IsolatedWorks
     let request = InspectCrateRequest {
         crate_name: Some("serde".to_string()),
         detail_level: DetailLevel::Medium,
+        workspace: None,
     };
 
     let_assert!(
@@ -154,6 +163,7 @@ async fn inspect_crate_serde_json(isolated_workspace_with_serde: IsolatedWorkspa
     let request = InspectCrateRequest {
         crate_name: Some("serde_json".to_string()),
         detail_level: DetailLevel::High,
+        workspace: None,
     };
 
     let_assert!(
@@ -176,6 +186,7 @@ async fn inspect_crate_nonexistent(isolated_workspace: IsolatedWorkspace) {
     let request = InspectCrateRequest {
         crate_name: Some("nonexistent-crate-xyz".to_string()),
         detail_level: DetailLevel::Medium,
+        workspace: None,
     };
 
     let result = handle_inspect_crate(&isolated_workspace.state, request).await;
@@ -198,6 +209,7 @@ async fn inspect_crate_exports_structure(isolated_workspace: IsolatedWorkspace)
     let request = InspectCrateRequest {
         crate_name: Some("rustdoc-mcp".to_string()),
         detail_level: DetailLevel::High,
+        workspace: None,
     };
 
     let_assert!(Ok(output) = handle_inspect_crate(&isolated_workspace.state, request).await);
@@ -237,3 +249,58 @@ async fn inspect_crate_exports_structure(isolated_workspace: IsolatedWorkspace)
         output
     );
 }
+
+// --- Structured Output Tests ---
+
+/// Test: Structured detail exposes counts, modules and exports as data.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn inspect_crate_structured_detail(isolated_workspace: IsolatedWorkspace) {
+    let request = InspectCrateRequest {
+        crate_name: Some("rustdoc-mcp".to_string()),
+        detail_level: DetailLevel::High,
+        workspace: None,
+    };
+
+    let_assert!(
+        Ok(StructuredCrateResult::Detail(detail)) =
+            handle_inspect_crate_structured(&isolated_workspace.state, request).await
+    );
+    check!(detail.documentation_error.is_none());
+    check!(
+        detail
+            .item_counts
+            .iter()
+            .any(|count| count.kind == "Structs")
+    );
+    let_assert!(Some(modules) = &detail.modules);
+    check!(modules.iter().any(|module| module == "search"));
+    let_assert!(Some(exports) = &detail.exports);
+    check!(exports.types.total > 0);
+
+    let_assert!(Ok(json) = serde_json::to_value(StructuredCrateResult::Detail(detail)));
+    check!(json["mode"] == "detail");
+    check!(json["name"] == "rustdoc-mcp");
+}
+
+/// Test: Structured summary separates members from dependencies.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn inspect_crate_structured_summary(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let request = InspectCrateRequest {
+        crate_name: None,
+        detail_level: DetailLevel::Low,
+        workspace: None,
+    };
+
+    let_assert!(
+        Ok(StructuredCrateResult::Summary {
+            members,
+            dependencies,
+            ..
+        }) = handle_inspect_crate_structured(&isolated_workspace_with_serde.state, request).await
+    );
+    check!(members.iter().any(|entry| entry.name == "rustdoc-mcp"));
+    let_assert!(Some(serde) = dependencies.iter().find(|entry| entry.name == "serde"));
+    check!(serde.version.is_some());
+}
@@ -3,10 +3,13 @@ mod common;
 use assert2::{check, let_assert};
 use common::{
     IsolatedWorkspace, isolated_workspace, isolated_workspace_with_anyhow,
-    isolated_workspace_with_serde,
+    isolated_workspace_with_anyhow_versions, isolated_workspace_with_serde,
 };
 use rstest::rstest;
-use rustdoc_mcp::tools::inspect_item::{InspectItemRequest, handle_inspect_item};
+use rustdoc_mcp::tools::inspect_item::{
+    InspectItemRequest, StructuredInspectResult, handle_inspect_item,
+    handle_inspect_item_structured,
+};
 use rustdoc_mcp::{DetailLevel, ItemKind};
 
 /// Test: Find Serialize trait via serde::Serialize (resolves cross-crate re-exports).
@@ -17,6 +20,8 @@ async fn inspect_finds_serialize_trait(isolated_workspace_with_serde: IsolatedWo
         query: "serde::Serialize".to_string(),
         kind: Some(ItemKind::Trait),
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -34,6 +39,8 @@ async fn inspect_successful_simple_lookup(isolated_workspace_with_serde: Isolate
         query: "serde::Deserialize".to_string(),
         kind: Some(ItemKind::Trait),
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -51,6 +58,8 @@ async fn inspect_successful_qualified_path(isolated_workspace_with_serde: Isolat
         query: "serde::Serialize".to_string(),
         kind: Some(ItemKind::Trait),
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -67,6 +76,8 @@ async fn inspect_no_matches_found(isolated_workspace: IsolatedWorkspace) {
         query: "NonExistentItemXYZ123".to_string(),
         kind: None,
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let result = handle_inspect_item(&isolated_workspace.state, request).await;
@@ -83,6 +94,8 @@ async fn inspect_minimal_verbosity(isolated_workspace_with_serde: IsolatedWorksp
         query: "serde::Deserialize".to_string(),
         kind: Some(ItemKind::Trait),
         detail_level: DetailLevel::Low,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -102,6 +115,8 @@ async fn inspect_full_verbosity(isolated_workspace_with_serde: IsolatedWorkspace
         query: "serde::Deserialize".to_string(),
         kind: Some(ItemKind::Trait),
         detail_level: DetailLevel::High,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -120,6 +135,8 @@ async fn inspect_function_lookup(isolated_workspace_with_serde: IsolatedWorkspac
         query: "serde_json::to_string".to_string(),
         kind: Some(ItemKind::Function),
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -137,6 +154,8 @@ async fn inspect_enum_with_variants(isolated_workspace_with_serde: IsolatedWorks
         query: "serde_json::Value".to_string(),
         kind: Some(ItemKind::Enum),
         detail_level: DetailLevel::High,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -161,6 +180,8 @@ async fn inspect_local_struct_simple_name(isolated_workspace: IsolatedWorkspace)
         query: "QueryContext".to_string(),
         kind: Some(ItemKind::Struct),
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -185,6 +206,8 @@ async fn inspect_local_struct_full_path(isolated_workspace: IsolatedWorkspace) {
         query: "rustdoc_mcp::search::query::QueryContext".to_string(),
         kind: Some(ItemKind::Struct),
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -203,6 +226,8 @@ async fn inspect_local_module(isolated_workspace: IsolatedWorkspace) {
         query: "workspace".to_string(),
         kind: Some(ItemKind::Module),
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -221,6 +246,8 @@ async fn inspect_local_trait(isolated_workspace: IsolatedWorkspace) {
         query: "TypeFormatter".to_string(),
         kind: Some(ItemKind::Struct),
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -252,6 +279,8 @@ async fn inspect_local_servicecontext(isolated_workspace: IsolatedWorkspace) {
         query: "ServiceContext".to_string(),
         kind: Some(ItemKind::Struct),
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -271,6 +300,8 @@ async fn inspect_local_with_hyphenated_crate_name(isolated_workspace: IsolatedWo
         query: "rustdoc-mcp::WorkspaceContext".to_string(),
         kind: Some(ItemKind::Struct),
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -289,6 +320,8 @@ async fn inspect_trait_shows_signature(isolated_workspace: IsolatedWorkspace) {
         query: "TypeFormatter".to_string(),
         kind: Some(ItemKind::Struct),
         detail_level: DetailLevel::Low,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -308,6 +341,8 @@ async fn inspect_trait_shows_generics(isolated_workspace: IsolatedWorkspace) {
         query: "TypeFormatter".to_string(),
         kind: Some(ItemKind::Struct),
         detail_level: DetailLevel::Low,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -328,6 +363,8 @@ async fn inspect_kind_mismatch_suggests_correct_kind(isolated_workspace: Isolate
         query: "QueryContext".to_string(), // exists as a Struct
         kind: Some(ItemKind::Function),    // but asked for Function
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(Err(err) = handle_inspect_item(&isolated_workspace.state, request).await);
@@ -358,6 +395,8 @@ async fn inspect_bare_result_shows_disambiguation(
         query: "Result".to_string(),
         kind: None, // no kind filter — should trigger disambiguation
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let result = handle_inspect_item(&isolated_workspace_with_anyhow.state, request).await;
@@ -404,6 +443,8 @@ async fn inspect_type_alias_shows_qualified_target(
         query: "rustdoc_mcp::Result".to_string(),
         kind: Some(ItemKind::TypeAlias),
         detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
     };
 
     let_assert!(
@@ -432,3 +473,201 @@ async fn inspect_type_alias_shows_qualified_target(
         output
     );
 }
+
+fn high_detail(query: &str) -> InspectItemRequest {
+    InspectItemRequest {
+        query: query.to_string(),
+        kind: None,
+        detail_level: DetailLevel::High,
+        workspace: None,
+        features: None,
+    }
+}
+
+/// Test: `Type::method` resolves inherent methods and shows the owning impl.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn inspect_inherent_method_shows_impl(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let_assert!(
+        Ok(output) = handle_inspect_item(
+            &isolated_workspace_with_serde.state,
+            high_detail("serde_json::Value::pointer"),
+        )
+        .await
+    );
+    check!(output.contains("fn pointer("));
+    check!(output.contains("// impl serde_json::Value"));
+}
+
+/// Test: trait impl methods show the impl header, the trait, and the trait's docs.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn inspect_trait_impl_method_inherits_docs(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let_assert!(
+        Ok(output) = handle_inspect_item(
+            &isolated_workspace_with_serde.state,
+            high_detail("serde_json::Value::deserialize_any"),
+        )
+        .await
+    );
+    check!(output.contains("for serde_json::Value"));
+    check!(output.contains("// from trait serde_core::de::Deserializer"));
+    check!(output.contains("Require the `Deserializer` to figure out"));
+}
+
+/// Test: `Trait::AssocType` and `Trait::method` resolve to the trait's declarations.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn inspect_trait_associated_items(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let_assert!(
+        Ok(output) = handle_inspect_item(
+            &isolated_workspace_with_serde.state,
+            high_detail("serde::Serializer::Ok"),
+        )
+        .await
+    );
+    check!(output.contains("type Ok;"));
+    check!(output.contains("// in trait serde_core::ser::Serializer"));
+
+    let_assert!(
+        Ok(output) = handle_inspect_item(
+            &isolated_workspace_with_serde.state,
+            high_detail("serde::Serializer::serialize_bool"),
+        )
+        .await
+    );
+    check!(output.contains("fn serialize_bool("));
+}
+
+/// Test: `Type::method` works without a crate prefix.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn inspect_method_without_crate_prefix(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Ok(output) = handle_inspect_item(
+            &isolated_workspace.state,
+            high_detail("QueryContext::resolve_path"),
+        )
+        .await
+    );
+    check!(output.contains("fn resolve_path"));
+    check!(output.contains("// impl rustdoc_mcp::QueryContext"));
+}
+
+/// Test: declarative macros render their `macro_rules!` arms.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn inspect_macro_rules_shows_arms(isolated_workspace_with_anyhow: IsolatedWorkspace) {
+    let request = InspectItemRequest {
+        query: "anyhow::bail".to_string(),
+        kind: Some(ItemKind::Macro),
+        detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
+    };
+
+    let_assert!(
+        Ok(output) = handle_inspect_item(&isolated_workspace_with_anyhow.state, request).await
+    );
+    check!(output.starts_with("macro_rules! bail {"));
+    check!(output.contains("($msg:literal $(,)?) => { ... };"));
+    check!(output.contains("Return early with an error."));
+}
+
+/// Test: attribute proc macros render their invocation form and match the `attribute` kind.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn inspect_attribute_proc_macro() {
+    let workspace = IsolatedWorkspace::with_deps(&["rustdoc-mcp", "tracing_attributes"]);
+    let request = InspectItemRequest {
+        query: "tracing_attributes::instrument".to_string(),
+        kind: Some(ItemKind::Attribute),
+        detail_level: DetailLevel::Medium,
+        workspace: None,
+        features: None,
+    };
+
+    let_assert!(Ok(output) = handle_inspect_item(&workspace.state, request).await);
+    check!(output.starts_with("#[instrument]"));
+    check!(output.contains("Instruments a function"));
+
+    let request = InspectItemRequest {
+        query: "tracing_attributes::instrument".to_string(),
+        kind: Some(ItemKind::Derive),
+        detail_level: DetailLevel::Low,
+        workspace: None,
+        features: None,
+    };
+    let_assert!(Err(msg) = handle_inspect_item(&workspace.state, request).await);
+    check!(msg.contains("not a Derive"));
+}
+
+/// Test: `crate@version::Item` resolves against that version only.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn inspect_versioned_path(isolated_workspace_with_anyhow_versions: IsolatedWorkspace) {
+    let request = InspectItemRequest {
+        query: "anyhow@0.9::Error".to_string(),
+        kind: None,
+        detail_level: DetailLevel::Low,
+        workspace: None,
+        features: None,
+    };
+
+    let_assert!(
+        Ok(StructuredInspectResult::Item {
+            crate_name,
+            rendered,
+            ..
+        }) =
+            handle_inspect_item_structured(&isolated_workspace_with_anyhow_versions.state, request)
+                .await
+    );
+    check!(crate_name == "anyhow@0.9.0");
+    check!(rendered.contains("used by legacy-tool"));
+}
+
+/// Test: A bare path into a crate with several versions lists each version and its users.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn inspect_ambiguous_crate_version(
+    isolated_workspace_with_anyhow_versions: IsolatedWorkspace,
+) {
+    let request = InspectItemRequest {
+        query: "anyhow::Error".to_string(),
+        kind: None,
+        detail_level: DetailLevel::Low,
+        workspace: None,
+        features: None,
+    };
+
+    let_assert!(
+        Ok(StructuredInspectResult::Disambiguation { candidates, .. }) =
+            handle_inspect_item_structured(&isolated_workspace_with_anyhow_versions.state, request)
+                .await
+    );
+    let paths: Vec<_> = candidates.iter().map(|c| c.full_path.as_str()).collect();
+    check!(paths == ["anyhow@1.0.98::Error", "anyhow@0.9.0::Error"]);
+    check!(candidates[0].version.as_deref() == Some("1.0.98"));
+    check!(candidates[0].used_by == ["rustdoc-mcp"]);
+    check!(candidates[1].used_by == ["legacy-tool"]);
+}
+
+/// Test: Asking for a version the workspace doesn't have lists the available ones.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn inspect_unknown_crate_version(isolated_workspace_with_anyhow_versions: IsolatedWorkspace) {
+    let request = InspectItemRequest {
+        query: "anyhow@3::Error".to_string(),
+        kind: None,
+        detail_level: DetailLevel::Low,
+        workspace: None,
+        features: None,
+    };
+
+    let_assert!(
+        Err(error) =
+            handle_inspect_item(&isolated_workspace_with_anyhow_versions.state, request).await
+    );
+    check!(error.contains("1.0.98, 0.9.0"));
+}
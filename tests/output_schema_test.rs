@@ -0,0 +1,32 @@
+use assert2::check;
+use rmcp::model::JsonObject;
+use rstest::rstest;
+use rustdoc_mcp::server::output_schema_for_type;
+use rustdoc_mcp::tools::cache::StructuredCacheResult;
+use rustdoc_mcp::tools::find_implementors::StructuredImplementorsResult;
+use rustdoc_mcp::tools::inspect_crate::StructuredCrateResult;
+use rustdoc_mcp::tools::inspect_item::StructuredInspectResult;
+use rustdoc_mcp::tools::search::StructuredSearchResult;
+use std::sync::Arc;
+
+/// Test: Every tool's output schema has an object root, as MCP requires.
+#[rstest]
+#[case::search(output_schema_for_type::<StructuredSearchResult>())]
+#[case::inspect_item(output_schema_for_type::<StructuredInspectResult>())]
+#[case::inspect_crate(output_schema_for_type::<StructuredCrateResult>())]
+#[case::find_implementors(output_schema_for_type::<StructuredImplementorsResult>())]
+#[case::cache(output_schema_for_type::<StructuredCacheResult>())]
+fn output_schema_root_is_object(#[case] schema: Arc<JsonObject>) {
+    check!(schema.get("type") == Some(&serde_json::json!("object")));
+}
+
+/// Test: Tagged result enums document their variants through the tag.
+#[test]
+fn search_output_schema_lists_statuses() {
+    let schema =
+        serde_json::Value::Object((*output_schema_for_type::<StructuredSearchResult>()).clone());
+    let text = schema.to_string();
+    for status in ["hits", "empty", "crate_not_found"] {
+        check!(text.contains(status), "missing status {status}: {text}");
+    }
+}
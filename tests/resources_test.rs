@@ -0,0 +1,73 @@
+mod common;
+
+use assert2::{check, let_assert};
+use common::{IsolatedWorkspace, isolated_workspace, isolated_workspace_with_serde};
+use rstest::rstest;
+use rustdoc_mcp::resources::{list_resources, read_resource, resource_templates};
+
+/// Every workspace crate is listed as a `rustdoc://{crate}` resource, members first.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn lists_workspace_crates(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let resources = list_resources(&isolated_workspace_with_serde.state).await;
+    let uris: Vec<&str> = resources.iter().map(|r| r.raw.uri.as_str()).collect();
+
+    check!(uris.first() == Some(&"rustdoc://rustdoc-mcp"));
+    check!(uris.contains(&"rustdoc://serde"));
+    check!(uris.contains(&"rustdoc://serde_json"));
+
+    let_assert!(Some(serde) = resources.iter().find(|r| r.raw.name == "serde"));
+    let_assert!(Some(description) = &serde.raw.description);
+    check!(!description.contains("not generated"));
+}
+
+/// Templates cover both crate roots and item paths.
+#[test]
+fn templates_cover_crates_and_items() {
+    let templates: Vec<String> = resource_templates()
+        .into_iter()
+        .map(|t| t.raw.uri_template)
+        .collect();
+    check!(templates == ["rustdoc://{crate}", "rustdoc://{crate}/{+path}"]);
+}
+
+/// Reading an item resource renders it like `inspect_item`.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn reads_item_resource(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let_assert!(
+        Ok(text) = read_resource(
+            &isolated_workspace_with_serde.state,
+            "rustdoc://serde/de/Deserialize"
+        )
+        .await
+    );
+    check!(text.contains("trait Deserialize"));
+}
+
+/// Reading a crate resource renders its root module.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn reads_crate_resource(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(Ok(text) = read_resource(&isolated_workspace.state, "rustdoc://rustdoc-mcp").await);
+    check!(text.starts_with("module "));
+}
+
+/// Unknown items and foreign schemes are errors.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn rejects_unknown_resources(isolated_workspace: IsolatedWorkspace) {
+    check!(
+        read_resource(
+            &isolated_workspace.state,
+            "rustdoc://rustdoc-mcp/NoSuchItemAnywhere"
+        )
+        .await
+        .is_err()
+    );
+    check!(
+        read_resource(&isolated_workspace.state, "file:///etc/hosts")
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,134 @@
+//! Ranking evaluation for text search.
+//!
+//! Each judgment pairs a query with the item a user issuing it is looking for,
+//! taken from the expectations in `search_test.rs`. The harness reports the
+//! rank each expected item lands at along with the mean reciprocal rank (MRR),
+//! so the effect of a ranking change can be read off a single run:
+//!
+//! ```text
+//! cargo test --test search_eval_test -- --nocapture
+//! ```
+//!
+//! The assertions only guard against regressions; raise the floors when
+//! ranking improves.
+
+mod common;
+
+use assert2::check;
+use common::IsolatedWorkspace;
+use rustdoc_mcp::tools::search::{SearchRequest, StructuredSearchResult, handle_search_structured};
+
+/// Results inspected per query.
+const DEPTH: usize = 10;
+
+/// Lowest acceptable MRR over every judgment.
+const MIN_MRR: f64 = 0.9;
+
+/// A query and the name of the item it should find. Any item of that name
+/// counts, so a re-export is as good as the original.
+struct Judgment {
+    crate_name: &'static str,
+    query: &'static str,
+    expected: &'static str,
+}
+
+const fn judgment(
+    crate_name: &'static str,
+    query: &'static str,
+    expected: &'static str,
+) -> Judgment {
+    Judgment {
+        crate_name,
+        query,
+        expected,
+    }
+}
+
+const JUDGMENTS: &[Judgment] = &[
+    judgment("rustdoc-mcp", "QueryContext", "QueryContext"),
+    judgment("rustdoc-mcp", "ItemServer", "ItemServer"),
+    judgment("rustdoc-mcp", "CrateOrigin", "CrateOrigin"),
+    judgment("rustdoc-mcp", "TypeFormatter", "TypeFormatter"),
+    judgment("rustdoc-mcp", "WorkspaceContext", "WorkspaceContext"),
+    judgment("rustdoc-mcp", "cache", "cache"),
+    judgment(
+        "rustdoc-mcp",
+        "spawn background worker",
+        "spawn_background_worker",
+    ),
+    judgment("rustdoc-mcp", "handle search", "handle_search"),
+    judgment("rustdoc-mcp", "parse Cargo.lock", "parse_cargo_lock"),
+    judgment("rustdoc-mcp", "root of a git repository", "find_git_root"),
+    judgment(
+        "rustdoc-mcp",
+        "protected system directory",
+        "is_system_directory",
+    ),
+    judgment(
+        "rustdoc-mcp",
+        "generate JSON documentation",
+        "generate_docs",
+    ),
+    judgment(
+        "rustdoc-mcp",
+        "fuzzy crate name suggestion",
+        "CrateSuggestion",
+    ),
+    judgment("serde", "Serialize", "Serialize"),
+    judgment("serde", "Deserialize", "Deserialize"),
+    judgment("serde", "Deserializer", "Deserializer"),
+    judgment("anyhow", "Error", "Error"),
+    judgment("anyhow", "Context", "Context"),
+    judgment("anyhow", "Result", "Result"),
+];
+
+/// 1-based rank of the first item named `judgment.expected`, if within [`DEPTH`].
+async fn rank(workspace: &IsolatedWorkspace, judgment: &Judgment) -> Option<u32> {
+    let request = SearchRequest {
+        query: judgment.query.to_string(),
+        crate_name: Some(judgment.crate_name.to_string()),
+        limit: DEPTH,
+        ..Default::default()
+    };
+    match handle_search_structured(&workspace.state, request).await {
+        Ok(StructuredSearchResult::Hits { hits, .. }) => hits
+            .iter()
+            .position(|hit| hit.full_path.rsplit("::").next() == Some(judgment.expected))
+            .and_then(|idx| u32::try_from(idx + 1).ok()),
+        _ => None,
+    }
+}
+
+/// Test: Every judgment is found, and the expected items rank high overall.
+#[tokio::test(flavor = "multi_thread")]
+async fn search_ranking_meets_judgments() {
+    let workspace = IsolatedWorkspace::with_deps(&[
+        "rustdoc-mcp",
+        "serde",
+        "serde_json",
+        "serde_core",
+        "anyhow",
+    ]);
+
+    let mut reciprocal_ranks = 0.0;
+    let mut missed = vec![];
+    for judgment in JUDGMENTS {
+        let rank = rank(&workspace, judgment).await;
+        let shown = rank.map_or_else(|| "-".to_string(), |rank| rank.to_string());
+        println!("{shown:>4}  {} in {}", judgment.query, judgment.crate_name);
+        if let Some(rank) = rank {
+            reciprocal_ranks += 1.0 / f64::from(rank);
+        } else {
+            missed.push(judgment.query);
+        }
+    }
+    let judged = u32::try_from(JUDGMENTS.len()).expect("judgment count fits in u32");
+    let mrr = reciprocal_ranks / f64::from(judged);
+    println!("MRR@{DEPTH}: {mrr:.3}");
+
+    check!(
+        missed.is_empty(),
+        "Not found in the top {DEPTH}: {missed:?}"
+    );
+    check!(mrr >= MIN_MRR, "MRR@{DEPTH} {mrr:.3} fell below {MIN_MRR}");
+}
@@ -3,10 +3,14 @@ mod common;
 use assert2::{assert, check, let_assert};
 use common::{
     IsolatedWorkspace, isolated_workspace, isolated_workspace_with_anyhow,
-    isolated_workspace_with_serde, warm_cache,
+    isolated_workspace_with_anyhow_versions, isolated_workspace_with_serde, warm_cache,
 };
 use rstest::rstest;
-use rustdoc_mcp::tools::search::{SearchRequest, handle_search};
+use rustdoc_mcp::ItemKind;
+use rustdoc_mcp::tools::search::{
+    SearchMode, SearchRequest, SearchScope, StructuredSearchResult, handle_search,
+    handle_search_structured,
+};
 
 // --- Working Search Tests ---
 // These items ARE indexed and should work.
@@ -17,8 +21,9 @@ use rustdoc_mcp::tools::search::{SearchRequest, handle_search};
 async fn search_finds_querycontext(isolated_workspace: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "QueryContext".to_string(),
-        crate_name: "rustdoc-mcp".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(
@@ -43,8 +48,9 @@ async fn search_finds_querycontext(isolated_workspace: IsolatedWorkspace) {
 async fn search_finds_servercontext(isolated_workspace: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "ServerContext".to_string(),
-        crate_name: "rustdoc-mcp".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(Ok(output) = handle_search(&isolated_workspace.state, request).await);
@@ -61,8 +67,9 @@ async fn search_finds_servercontext(isolated_workspace: IsolatedWorkspace) {
 async fn search_finds_crateorigin(isolated_workspace: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "CrateOrigin".to_string(),
-        crate_name: "rustdoc-mcp".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(Ok(output) = handle_search(&isolated_workspace.state, request).await);
@@ -79,8 +86,9 @@ async fn search_finds_crateorigin(isolated_workspace: IsolatedWorkspace) {
 async fn search_finds_traititerator(isolated_workspace: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "TraitIterator".to_string(),
-        crate_name: "rustdoc-mcp".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(Ok(output) = handle_search(&isolated_workspace.state, request).await);
@@ -102,8 +110,9 @@ async fn search_finds_traititerator(isolated_workspace: IsolatedWorkspace) {
 async fn search_finds_backgroundworker(isolated_workspace: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "BackgroundWorker".to_string(),
-        crate_name: "rustdoc-mcp".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(Ok(output) = handle_search(&isolated_workspace.state, request).await);
@@ -125,8 +134,9 @@ async fn search_finds_backgroundworker(isolated_workspace: IsolatedWorkspace) {
 async fn search_finds_typeformatter_trait(isolated_workspace: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "TypeFormatter".to_string(),
-        crate_name: "rustdoc-mcp".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(Ok(output) = handle_search(&isolated_workspace.state, request).await);
@@ -147,8 +157,9 @@ async fn search_finds_typeformatter_trait(isolated_workspace: IsolatedWorkspace)
 async fn search_finds_module_cache(isolated_workspace: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "cache".to_string(),
-        crate_name: "rustdoc-mcp".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(Ok(output) = handle_search(&isolated_workspace.state, request).await);
@@ -169,8 +180,9 @@ async fn search_finds_module_cache(isolated_workspace: IsolatedWorkspace) {
 async fn search_finds_itemref(isolated_workspace: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "ItemRef".to_string(),
-        crate_name: "rustdoc-mcp".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(Ok(output) = handle_search(&isolated_workspace.state, request).await);
@@ -188,8 +200,9 @@ async fn search_finds_itemref(isolated_workspace: IsolatedWorkspace) {
 async fn search_finds_serde_serialize(isolated_workspace_with_serde: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "Serialize".to_string(),
-        crate_name: "serde".to_string(),
+        crate_name: Some("serde".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(Ok(output) = handle_search(&isolated_workspace_with_serde.state, request).await);
@@ -210,8 +223,9 @@ async fn search_finds_serde_serialize(isolated_workspace_with_serde: IsolatedWor
 async fn search_finds_serde_deserialize(isolated_workspace_with_serde: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "Deserialize".to_string(),
-        crate_name: "serde".to_string(),
+        crate_name: Some("serde".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(Ok(output) = handle_search(&isolated_workspace_with_serde.state, request).await);
@@ -232,8 +246,9 @@ async fn search_finds_serde_deserialize(isolated_workspace_with_serde: IsolatedW
 async fn search_finds_serde_deserializer(isolated_workspace_with_serde: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "Deserializer".to_string(),
-        crate_name: "serde".to_string(),
+        crate_name: Some("serde".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(Ok(output) = handle_search(&isolated_workspace_with_serde.state, request).await);
@@ -258,8 +273,9 @@ async fn search_finds_serde_deserializer(isolated_workspace_with_serde: Isolated
 async fn search_with_fresh_index_build(isolated_workspace: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "QueryContext".to_string(),
-        crate_name: "rustdoc-mcp".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(
@@ -299,8 +315,9 @@ async fn search_works_with_warm_cache(isolated_workspace: IsolatedWorkspace) {
     // Now search should use cached index
     let request = SearchRequest {
         query: "ServerContext".to_string(),
-        crate_name: "rustdoc-mcp".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     let_assert!(
@@ -314,6 +331,59 @@ async fn search_works_with_warm_cache(isolated_workspace: IsolatedWorkspace) {
     );
 }
 
+/// Test: Repeated searches reuse the index held in memory instead of reading
+/// (or rebuilding) the on-disk cache.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn repeated_search_reuses_index_in_memory(isolated_workspace: IsolatedWorkspace) {
+    let request = || SearchRequest {
+        query: "QueryContext".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
+        limit: 5,
+        ..Default::default()
+    };
+    let_assert!(Ok(_) = handle_search(&isolated_workspace.state, request()).await);
+
+    let index_path = isolated_workspace
+        .root()
+        .join("target/doc/rustdoc_mcp.index");
+    assert!(index_path.exists());
+    std::fs::remove_file(&index_path).unwrap();
+
+    let_assert!(Ok(output) = handle_search(&isolated_workspace.state, request()).await);
+    check!(output.contains("QueryContext"), "{output}");
+    check!(
+        !index_path.exists(),
+        "the second search should not have rebuilt the index"
+    );
+}
+
+/// Test: Docs parsed for a search land in the shared memory cache, so other
+/// tools are served without parsing them again.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_keeps_parsed_docs_in_memory(isolated_workspace: IsolatedWorkspace) {
+    let state = &isolated_workspace.state;
+    check!(
+        !state
+            .is_cached(isolated_workspace.root(), "rustdoc-mcp")
+            .await
+    );
+
+    let request = SearchRequest {
+        query: "QueryContext".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
+        limit: 5,
+        ..Default::default()
+    };
+    let_assert!(Ok(_) = handle_search(state, request).await);
+    check!(
+        state
+            .is_cached(isolated_workspace.root(), "rustdoc-mcp")
+            .await
+    );
+}
+
 // --- Edge Cases ---
 
 /// Test: Search for non-existent crate gives helpful error.
@@ -322,8 +392,9 @@ async fn search_works_with_warm_cache(isolated_workspace: IsolatedWorkspace) {
 async fn search_nonexistent_crate_error(isolated_workspace: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "anything".to_string(),
-        crate_name: "nonexistent-crate-xyz".to_string(),
+        crate_name: Some("nonexistent-crate-xyz".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     // Should return Ok with a suggestion message, not an Err
@@ -341,8 +412,9 @@ async fn search_nonexistent_crate_error(isolated_workspace: IsolatedWorkspace) {
 async fn search_empty_query(isolated_workspace: IsolatedWorkspace) {
     let request = SearchRequest {
         query: String::new(),
-        crate_name: "rustdoc-mcp".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
         limit: 5,
+        ..Default::default()
     };
 
     // Empty query should not panic
@@ -369,8 +441,9 @@ async fn concurrent_searches_same_crate(isolated_workspace: IsolatedWorkspace) {
         handles.push(tokio::spawn(async move {
             let request = SearchRequest {
                 query: query.clone(),
-                crate_name: "rustdoc-mcp".to_string(),
+                crate_name: Some("rustdoc-mcp".to_string()),
                 limit: 5,
+                ..Default::default()
             };
             let result = handle_search(&context, request).await;
             (query, result)
@@ -408,8 +481,9 @@ async fn concurrent_cold_cache_searches(isolated_workspace: IsolatedWorkspace) {
         handles.push(tokio::spawn(async move {
             let request = SearchRequest {
                 query: "QueryContext".to_string(),
-                crate_name: "rustdoc-mcp".to_string(),
+                crate_name: Some("rustdoc-mcp".to_string()),
                 limit: 5,
+                ..Default::default()
             };
             let result = handle_search(&context, request).await;
             (i, result)
@@ -463,8 +537,9 @@ async fn concurrent_mixed_operations(isolated_workspace: IsolatedWorkspace) {
                 tokio::time::sleep(tokio::time::Duration::from_millis(i * 10)).await;
                 let request = SearchRequest {
                     query: "ServerContext".to_string(),
-                    crate_name: "rustdoc-mcp".to_string(),
+                    crate_name: Some("rustdoc-mcp".to_string()),
                     limit: 5,
+                    ..Default::default()
                 };
                 handle_search(&context, request).await
             })
@@ -490,8 +565,9 @@ async fn concurrent_mixed_operations(isolated_workspace: IsolatedWorkspace) {
 async fn search_finds_anyhow_error(isolated_workspace_with_anyhow: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "Error".to_string(),
-        crate_name: "anyhow".to_string(),
+        crate_name: Some("anyhow".to_string()),
         limit: 10,
+        ..Default::default()
     };
 
     let_assert!(
@@ -516,8 +592,9 @@ async fn search_finds_anyhow_error(isolated_workspace_with_anyhow: IsolatedWorks
 async fn search_finds_anyhow_context(isolated_workspace_with_anyhow: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "Context".to_string(),
-        crate_name: "anyhow".to_string(),
+        crate_name: Some("anyhow".to_string()),
         limit: 10,
+        ..Default::default()
     };
 
     let_assert!(
@@ -542,8 +619,9 @@ async fn search_finds_anyhow_context(isolated_workspace_with_anyhow: IsolatedWor
 async fn search_finds_anyhow_result(isolated_workspace_with_anyhow: IsolatedWorkspace) {
     let request = SearchRequest {
         query: "Result".to_string(),
-        crate_name: "anyhow".to_string(),
+        crate_name: Some("anyhow".to_string()),
         limit: 10,
+        ..Default::default()
     };
 
     let_assert!(
@@ -561,3 +639,586 @@ async fn search_finds_anyhow_result(isolated_workspace_with_anyhow: IsolatedWork
         output
     );
 }
+
+// --- Signature Search Tests ---
+
+fn signature_request(query: &str, crate_name: &str) -> SearchRequest {
+    SearchRequest {
+        query: query.to_string(),
+        crate_name: Some(crate_name.to_string()),
+        limit: 10,
+        mode: SearchMode::Signature,
+        ..Default::default()
+    }
+}
+
+/// Test: `&str -> Result<T>` finds `serde_json::from_str`, with the generic
+/// return type acting as a wildcard.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn signature_search_finds_from_str(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) = handle_search_structured(
+            &isolated_workspace_with_serde.state,
+            signature_request("&str -> Result<T>", "serde_json"),
+        )
+        .await
+    );
+
+    let_assert!(
+        Some(hit) = hits
+            .iter()
+            .find(|h| h.full_path == "serde_json::de::from_str")
+    );
+    let_assert!(Some(signature) = &hit.signature);
+    check!(signature.starts_with("fn from_str"));
+    check!(
+        hits.iter()
+            .all(|h| h.full_path != "serde_json::de::from_slice"),
+        "from_slice takes &[u8], not &str: {hits:?}"
+    );
+}
+
+/// Test: Inherent methods are found with `Self` resolved to the impl type,
+/// and parameter order in the query does not matter.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn signature_search_matches_inherent_methods(
+    isolated_workspace_with_serde: IsolatedWorkspace,
+) {
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) = handle_search_structured(
+            &isolated_workspace_with_serde.state,
+            signature_request("(&str, &Value) -> Option<&Value>", "serde_json"),
+        )
+        .await
+    );
+
+    check!(
+        hits.iter()
+            .any(|h| h.full_path == "serde_json::value::Value::pointer"),
+        "{hits:?}"
+    );
+}
+
+/// Test: Rendered signature results include the function signature line.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn signature_search_renders_signatures(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let_assert!(
+        Ok(output) = handle_search(
+            &isolated_workspace_with_serde.state,
+            signature_request("-> Value", "serde_json"),
+        )
+        .await
+    );
+    check!(output.contains("Search results for '-> Value'"), "{output}");
+    check!(output.contains("fn "), "{output}");
+}
+
+/// Test: Malformed signature queries are reported with syntax help.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn signature_search_rejects_malformed_query(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Err(msg) = handle_search(
+            &isolated_workspace.state,
+            signature_request("Vec<u8 ->", "rustdoc-mcp"),
+        )
+        .await
+    );
+    check!(msg.contains("Invalid signature query"), "{msg}");
+    check!(msg.contains("wildcards"), "{msg}");
+}
+
+/// Test: Macros are indexed and reported with their kind.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_finds_macros(isolated_workspace_with_anyhow: IsolatedWorkspace) {
+    let request = SearchRequest {
+        query: "bail".to_string(),
+        crate_name: Some("anyhow".to_string()),
+        limit: 5,
+        ..Default::default()
+    };
+
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            handle_search_structured(&isolated_workspace_with_anyhow.state, request).await
+    );
+    let_assert!(Some(hit) = hits.iter().find(|h| h.full_path.ends_with("bail")));
+    check!(hit.kind == "Macro", "{hits:?}");
+}
+
+/// Test: Structured results serialize with a `status` tag for MCP structured content.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn structured_search_serializes_hits(isolated_workspace: IsolatedWorkspace) {
+    let request = SearchRequest {
+        query: "QueryContext".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
+        limit: 5,
+        ..Default::default()
+    };
+
+    let_assert!(Ok(result) = handle_search_structured(&isolated_workspace.state, request).await);
+    let_assert!(Ok(json) = serde_json::to_value(&result));
+    check!(json["status"] == "hits");
+    let_assert!(Some(hits) = json["hits"].as_array());
+    check!(hits.iter().any(|hit| {
+        hit["full_path"]
+            .as_str()
+            .is_some_and(|p| p.ends_with("QueryContext"))
+    }));
+}
+
+/// Test: A crate present in several versions is searched in each, with hits labelled.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_covers_every_crate_version(
+    isolated_workspace_with_anyhow_versions: IsolatedWorkspace,
+) {
+    let request = SearchRequest {
+        query: "Error".to_string(),
+        crate_name: Some("anyhow".to_string()),
+        limit: 20,
+        ..Default::default()
+    };
+
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, versions, .. }) =
+            handle_search_structured(&isolated_workspace_with_anyhow_versions.state, request).await
+    );
+    let searched: Vec<_> = versions.iter().map(|v| v.version.as_str()).collect();
+    check!(searched == ["1.0.98", "0.9.0"]);
+    check!(versions[1].used_by == ["legacy-tool"]);
+
+    for version in ["1.0.98", "0.9.0"] {
+        check!(hits.iter().any(|hit| {
+            hit.version.as_deref() == Some(version)
+                && hit.full_path == format!("anyhow@{version}::Error")
+        }));
+    }
+}
+
+/// Test: `name@version` restricts the search to one version.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_versioned_crate_name(isolated_workspace_with_anyhow_versions: IsolatedWorkspace) {
+    let request = SearchRequest {
+        query: "Error".to_string(),
+        crate_name: Some("anyhow@0.9".to_string()),
+        limit: 20,
+        ..Default::default()
+    };
+
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            handle_search_structured(&isolated_workspace_with_anyhow_versions.state, request).await
+    );
+    check!(!hits.is_empty());
+    check!(
+        hits.iter()
+            .all(|hit| hit.version.as_deref() == Some("0.9.0"))
+    );
+}
+
+// --- Cross-Crate Search Tests ---
+
+/// Test: Omitting `crate_name` searches every crate and labels hits with theirs.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_without_crate_name_searches_every_crate(
+    isolated_workspace_with_serde: IsolatedWorkspace,
+) {
+    let request = SearchRequest {
+        query: "Serialize".to_string(),
+        limit: 20,
+        ..Default::default()
+    };
+
+    let_assert!(
+        Ok(StructuredSearchResult::Hits {
+            crate_name: None,
+            hits,
+            skipped,
+            ..
+        }) = handle_search_structured(&isolated_workspace_with_serde.state, request).await
+    );
+    check!(skipped.is_empty());
+    for crate_name in ["serde_core", "serde_json"] {
+        check!(hits.iter().any(|hit| hit.crate_name == crate_name));
+    }
+    check!(hits.iter().all(|hit| {
+        ["rustdoc-mcp", "serde", "serde_json", "serde_core"].contains(&hit.crate_name.as_str())
+    }));
+}
+
+/// Test: The members scope leaves dependencies out.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_members_scope(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let request = SearchRequest {
+        query: "search".to_string(),
+        limit: 20,
+        scope: SearchScope::Members,
+        ..Default::default()
+    };
+
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            handle_search_structured(&isolated_workspace_with_serde.state, request).await
+    );
+    check!(!hits.is_empty());
+    check!(hits.iter().all(|hit| hit.crate_name == "rustdoc-mcp"));
+}
+
+/// Test: A crate allowlist restricts the search to the listed crates.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_crate_allowlist(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let request = SearchRequest {
+        query: "Value".to_string(),
+        limit: 20,
+        crates: vec!["serde_json".to_string()],
+        ..Default::default()
+    };
+
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            handle_search_structured(&isolated_workspace_with_serde.state, request).await
+    );
+    check!(!hits.is_empty());
+    check!(hits.iter().all(|hit| hit.crate_name == "serde_json"));
+}
+
+/// Test: Crates without generated docs are skipped rather than generated.
+#[tokio::test(flavor = "multi_thread")]
+async fn search_across_crates_skips_missing_docs() {
+    let workspace = IsolatedWorkspace::with_deps(&["rustdoc-mcp", "not-generated"]);
+    let request = SearchRequest {
+        query: "QueryContext".to_string(),
+        ..Default::default()
+    };
+
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { skipped, .. }) =
+            handle_search_structured(&workspace.state, request).await
+    );
+    check!(skipped == ["not-generated"]);
+}
+
+// --- Filter Tests ---
+
+/// Test: Kind filters apply before the limit, so they don't starve the results.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_filters_by_kind(isolated_workspace: IsolatedWorkspace) {
+    let request = SearchRequest {
+        query: "search".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
+        limit: 3,
+        kinds: vec![ItemKind::Function],
+        ..Default::default()
+    };
+
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            handle_search_structured(&isolated_workspace.state, request).await
+    );
+    check!(hits.len() == 3);
+    check!(hits.iter().all(|hit| hit.kind == "Function"), "{hits:?}");
+}
+
+/// Test: A path prefix keeps only items under that module.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_filters_by_path_prefix(isolated_workspace: IsolatedWorkspace) {
+    let request = SearchRequest {
+        query: "search".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
+        limit: 10,
+        path_prefix: Some("rustdoc-mcp::tools::search".to_string()),
+        ..Default::default()
+    };
+
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            handle_search_structured(&isolated_workspace.state, request).await
+    );
+    check!(
+        hits.iter()
+            .all(|hit| hit.full_path.starts_with("rustdoc_mcp::tools::search")),
+        "{hits:?}"
+    );
+}
+
+/// Test: The async filter keeps async functions only.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_filters_async_functions(isolated_workspace: IsolatedWorkspace) {
+    let request = SearchRequest {
+        query: "handle search".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
+        limit: 20,
+        is_async: Some(true),
+        ..Default::default()
+    };
+
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            handle_search_structured(&isolated_workspace.state, request).await
+    );
+    check!(hits.iter().all(|hit| hit.kind == "Function"), "{hits:?}");
+    check!(
+        hits.iter()
+            .any(|hit| hit.full_path.ends_with("::handle_search_structured")),
+        "{hits:?}"
+    );
+}
+
+// --- Fuzzy Matching Tests ---
+
+/// Test: Prefixes and misspellings of a name still find it.
+#[rstest]
+#[case("QueryCont", "QueryContext")]
+#[case("QueryContxt", "QueryContext")]
+#[case("TypeFormater", "TypeFormatter")]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_tolerates_prefixes_and_typos(
+    isolated_workspace: IsolatedWorkspace,
+    #[case] query: &str,
+    #[case] expected: &str,
+) {
+    let request = SearchRequest {
+        query: query.to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
+        limit: 5,
+        ..Default::default()
+    };
+
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            handle_search_structured(&isolated_workspace.state, request).await
+    );
+    check!(
+        hits.iter().any(|hit| hit.full_path.ends_with(expected)),
+        "{query}: {hits:?}"
+    );
+}
+
+// --- Query Language Tests ---
+
+/// Run a text query against the rustdoc-mcp crate.
+async fn query_rustdoc_mcp(
+    workspace: &IsolatedWorkspace,
+    query: &str,
+) -> Result<StructuredSearchResult, String> {
+    let request = SearchRequest {
+        query: query.to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
+        limit: 10,
+        ..Default::default()
+    };
+    handle_search_structured(&workspace.state, request).await
+}
+
+/// Test: A name-qualified phrase only matches names with the words in order.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_matches_qualified_phrases(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            query_rustdoc_mcp(&isolated_workspace, r#"name:"handle search""#).await
+    );
+    check!(
+        hits.iter()
+            .any(|hit| hit.full_path.ends_with("::handle_search")),
+        "{hits:?}"
+    );
+    check!(
+        hits.iter().all(|hit| hit
+            .full_path
+            .rsplit("::")
+            .next()
+            .is_some_and(|name| name.contains("handle_search"))),
+        "{hits:?}"
+    );
+
+    let_assert!(
+        Ok(StructuredSearchResult::Empty { .. }) =
+            query_rustdoc_mcp(&isolated_workspace, r#"name:"search handle""#).await
+    );
+}
+
+/// Test: Excluded words drop the items holding them, only where qualified to.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_excludes_negated_words(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            query_rustdoc_mcp(&isolated_workspace, "handle search -name:structured").await
+    );
+    check!(
+        hits.iter()
+            .any(|hit| hit.full_path.ends_with("::handle_search")),
+        "{hits:?}"
+    );
+    check!(
+        !hits
+            .iter()
+            .any(|hit| hit.full_path.ends_with("::handle_search_structured")),
+        "{hits:?}"
+    );
+}
+
+/// Test: `kind:` in the query filters like the kinds parameter.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_filters_by_query_kind(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            query_rustdoc_mcp(&isolated_workspace, "search kind:fn").await
+    );
+    check!(!hits.is_empty());
+    check!(hits.iter().all(|hit| hit.kind == "Function"), "{hits:?}");
+}
+
+/// Test: `kind:` in the query narrows the kinds parameter rather than adding to it.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_query_kind_narrows_requested_kinds(isolated_workspace: IsolatedWorkspace) {
+    let request = SearchRequest {
+        query: "search kind:fn".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
+        kinds: vec![ItemKind::Function, ItemKind::Struct],
+        ..Default::default()
+    };
+
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            handle_search_structured(&isolated_workspace.state, request).await
+    );
+    check!(!hits.is_empty());
+    check!(hits.iter().all(|hit| hit.kind == "Function"), "{hits:?}");
+}
+
+/// Test: A query `kind:` sharing no kind with the kinds parameter is rejected.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_rejects_conflicting_query_kind(isolated_workspace: IsolatedWorkspace) {
+    let request = SearchRequest {
+        query: "search kind:struct".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
+        kinds: vec![ItemKind::Function],
+        ..Default::default()
+    };
+
+    let_assert!(Err(msg) = handle_search(&isolated_workspace.state, request).await);
+    check!(msg.contains("Invalid search query"), "{msg}");
+    check!(msg.contains("requested kinds"), "{msg}");
+}
+
+/// Test: Features need the crate they apply to.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_rejects_features_without_crate(isolated_workspace: IsolatedWorkspace) {
+    let request = SearchRequest {
+        query: "search".to_string(),
+        features: Some(vec!["derive".to_string()]),
+        ..Default::default()
+    };
+
+    let_assert!(Err(msg) = handle_search(&isolated_workspace.state, request).await);
+    check!(msg.contains("crate_name"), "{msg}");
+}
+
+/// Test: Malformed queries are reported with syntax help.
+#[rstest]
+#[case(r#""handle search"#)]
+#[case("search kind:widget")]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_rejects_malformed_query(
+    isolated_workspace: IsolatedWorkspace,
+    #[case] query: &str,
+) {
+    let_assert!(Err(msg) = query_rustdoc_mcp(&isolated_workspace, query).await);
+    check!(msg.contains("Invalid search query"), "{msg}");
+    check!(msg.contains("kind:"), "{msg}");
+}
+
+// --- Snippet Tests ---
+
+/// Test: Hits carry the doc window best matching the query, with the matched
+/// words marked by byte ranges.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_hits_carry_highlighted_snippets(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            query_rustdoc_mcp(&isolated_workspace, "protected system directory").await
+    );
+    let_assert!(
+        Some(hit) = hits
+            .iter()
+            .find(|hit| hit.full_path.ends_with("::is_system_directory"))
+    );
+    let_assert!(Some(snippet) = &hit.snippet);
+    check!(!snippet.highlights.is_empty());
+    for range in &snippet.highlights {
+        let word = snippet.text[range.start..range.end].to_lowercase();
+        check!(
+            ["protect", "system", "director"]
+                .iter()
+                .any(|stem| word.starts_with(stem)),
+            "{word} in {snippet:?}"
+        );
+    }
+}
+
+/// Test: The snippet comes from deep in the docs when that's where the match is.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_snippet_follows_the_match(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) =
+            query_rustdoc_mcp(&isolated_workspace, "programmatic").await
+    );
+    let_assert!(
+        Some(hit) = hits
+            .iter()
+            .find(|hit| hit.full_path.ends_with("::handle_search"))
+    );
+    let_assert!(Some(snippet) = &hit.snippet);
+    check!(Some(&snippet.text) != hit.first_doc_line.as_ref());
+    check!(snippet.text.starts_with("..."), "{snippet:?}");
+}
+
+/// Test: Rendered results show snippets with matches in bold.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn search_renders_bold_matches(isolated_workspace: IsolatedWorkspace) {
+    let request = SearchRequest {
+        query: "protected system directory".to_string(),
+        crate_name: Some("rustdoc-mcp".to_string()),
+        limit: 3,
+        ..Default::default()
+    };
+
+    let_assert!(Ok(output) = handle_search(&isolated_workspace.state, request).await);
+    check!(output.contains("**protected**"), "{output}");
+}
+
+/// Test: Signature hits have no snippet, as nothing in their docs was matched.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn signature_hits_have_no_snippet(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let_assert!(
+        Ok(StructuredSearchResult::Hits { hits, .. }) = handle_search_structured(
+            &isolated_workspace_with_serde.state,
+            signature_request("&str -> Result<_, _>", "serde_json"),
+        )
+        .await
+    );
+    check!(hits.iter().all(|hit| hit.snippet.is_none()));
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use assert2::{check, let_assert};
+use common::{
+    IsolatedWorkspace, isolated_workspace, isolated_workspace_with_anyhow,
+    isolated_workspace_with_serde,
+};
+use rstest::rstest;
+use rustdoc_mcp::tools::inspect_item::StructuredInspectResult;
+use rustdoc_mcp::tools::view_source::{
+    ViewSourceRequest, handle_view_source, handle_view_source_structured,
+};
+
+fn request(query: &str) -> ViewSourceRequest {
+    ViewSourceRequest {
+        query: query.to_string(),
+        kind: None,
+        workspace: None,
+    }
+}
+
+/// Registry dependencies record absolute span paths under `~/.cargo/registry/src`.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn shows_registry_dependency_source(isolated_workspace_with_serde: IsolatedWorkspace) {
+    let_assert!(
+        Ok(StructuredInspectResult::Item {
+            full_path,
+            rendered,
+            ..
+        }) = handle_view_source_structured(
+            &isolated_workspace_with_serde.state,
+            request("serde_json::from_str"),
+        )
+        .await
+    );
+
+    check!(full_path.ends_with("from_str"));
+    check!(rendered.contains("registry/src"), "{rendered}");
+    check!(rendered.contains("src/de.rs:"), "{rendered}");
+    check!(
+        rendered
+            .lines()
+            .any(|line| line.starts_with('>') && line.contains("pub fn from_str")),
+        "{rendered}"
+    );
+}
+
+/// Lines outside the span are shown as unmarked context.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn includes_context_lines(isolated_workspace_with_anyhow: IsolatedWorkspace) {
+    let_assert!(
+        Ok(output) = handle_view_source(
+            &isolated_workspace_with_anyhow.state,
+            request("anyhow::Result"),
+        )
+        .await
+    );
+
+    check!(output.contains("pub type Result"), "{output}");
+    let marked = output.lines().filter(|l| l.starts_with('>')).count();
+    let context = output.lines().filter(|l| l.starts_with(' ')).count();
+    check!(marked >= 1, "{output}");
+    check!(context >= 1, "{output}");
+}
+
+/// Workspace sources resolve relative to the workspace root; the isolated
+/// fixture only copies doc JSON, so the file is reported as missing.
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn reports_missing_workspace_file(isolated_workspace: IsolatedWorkspace) {
+    let_assert!(
+        Err(msg) =
+            handle_view_source(&isolated_workspace.state, request("rustdoc_mcp::DocState")).await
+    );
+    check!(msg.contains("Source file for"), "{msg}");
+    check!(msg.contains("not found"), "{msg}");
+}